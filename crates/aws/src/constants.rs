@@ -138,6 +138,8 @@ pub const DEFAULT_LOCK_TABLE_NAME: &str = "delta_log";
 pub const LOCK_TABLE_KEY_NAME: &str = "DELTA_DYNAMO_TABLE_NAME";
 pub const BILLING_MODE_KEY_NAME: &str = "DELTA_DYNAMO_BILLING_MODE";
 pub const MAX_ELAPSED_REQUEST_TIME_KEY_NAME: &str = "DELTA_DYNAMO_MAX_ELAPSED_REQUEST_TIME";
+/// Seconds after which a completed commit entry may be removed from the lock table by TTL
+pub const COMMIT_ENTRY_EXPIRATION_KEY_NAME: &str = "DELTA_DYNAMO_COMMIT_ENTRY_EXPIRATION_SECONDS";
 
 pub const ATTR_TABLE_PATH: &str = "tablePath";
 pub const ATTR_FILE_NAME: &str = "fileName";
@@ -161,6 +163,10 @@ pub static CONDITION_DELETE_INCOMPLETE: LazyLock<String> = LazyLock::new(|| {
 });
 
 pub const CONDITION_UPDATE_INCOMPLETE: &str = "complete = :f";
+
+pub static CONDITION_UPDATE_MISSING_EXPIRE_TIME: LazyLock<String> =
+    LazyLock::new(|| format!("complete = :t and attribute_not_exists({ATTR_EXPIRE_TIME})"));
+
 pub const DEFAULT_COMMIT_ENTRY_EXPIRATION_DELAY: Duration = Duration::from_secs(86_400);
 
 pub const DEFAULT_S3_POOL_IDLE_TIMEOUT_SECONDS: u64 = 15;
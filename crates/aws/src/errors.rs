@@ -6,8 +6,10 @@ use aws_credential_types::provider::error::CredentialsError;
 use aws_sdk_dynamodb::{
     error::SdkError,
     operation::{
-        create_table::CreateTableError, delete_item::DeleteItemError, get_item::GetItemError,
+        create_table::CreateTableError, delete_item::DeleteItemError,
+        describe_time_to_live::DescribeTimeToLiveError, get_item::GetItemError,
         put_item::PutItemError, query::QueryError, update_item::UpdateItemError,
+        update_time_to_live::UpdateTimeToLiveError,
     },
 };
 use aws_smithy_runtime_api::client::result::ServiceError;
@@ -51,11 +53,44 @@ pub enum DynamoDbConfigError {
         // config_value: String,
         source: ParseIntError,
     },
+    /// Cannot parse commit entry expiration value into u64
+    #[error("Cannot parse commit entry expiration into u64: {source}")]
+    ParseCommitEntryExpiration { source: ParseIntError },
+
     /// Cannot initialize DynamoDbConfiguration due to some sort of threading issue
     #[error("Cannot initialize dynamodb lock configuration")]
     InitializationError,
 }
 
+/// Errors produced by `S3ConditionalPutLogStore` when the store cannot commit with conditional puts
+#[derive(thiserror::Error, Debug)]
+pub enum ConditionalPutError {
+    #[error(
+        "The S3 store does not support conditional puts: {source}. \
+         Set AWS_S3_LOCKING_PROVIDER=dynamodb to commit through a DynamoDb lock table instead."
+    )]
+    NotSupported {
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+
+    #[error(
+        "The S3 store accepted a conditional put on an existing object, concurrent commits would \
+         overwrite each other. Set AWS_S3_LOCKING_PROVIDER=dynamodb to commit through a DynamoDb \
+         lock table instead."
+    )]
+    NotEnforced,
+
+    #[error(
+        "Conditional put is disabled, concurrent writers would overwrite each other's commits. \
+         Set AWS_S3_LOCKING_PROVIDER=dynamodb to use a DynamoDb lock table, or \
+         AWS_S3_ALLOW_UNSAFE_RENAME=true to opt out of support for concurrent writers."
+    )]
+    Disabled,
+
+    #[error("conditional put check failed: {source}")]
+    ObjectStore { source: object_store::Error },
+}
+
 /// Errors produced by `DynamoDbLockClient`
 #[derive(thiserror::Error, Debug)]
 pub enum LockClientError {
@@ -124,6 +159,32 @@ impl From<QueryError> for LockClientError {
     }
 }
 
+impl From<DescribeTimeToLiveError> for LockClientError {
+    fn from(err: DescribeTimeToLiveError) -> Self {
+        match err {
+            DescribeTimeToLiveError::ResourceNotFoundException(_) => {
+                LockClientError::LockTableNotFound
+            }
+            _ => LockClientError::GenericDynamoDb {
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
+impl From<UpdateTimeToLiveError> for LockClientError {
+    fn from(err: UpdateTimeToLiveError) -> Self {
+        match err {
+            UpdateTimeToLiveError::ResourceNotFoundException(_) => {
+                LockClientError::LockTableNotFound
+            }
+            _ => LockClientError::GenericDynamoDb {
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
 impl From<PutItemError> for LockClientError {
     fn from(err: PutItemError) -> Self {
         match err {
@@ -195,3 +256,5 @@ impl_from_service_error!(PutItemError);
 impl_from_service_error!(QueryError);
 impl_from_service_error!(UpdateItemError);
 impl_from_service_error!(DeleteItemError);
+impl_from_service_error!(DescribeTimeToLiveError);
+impl_from_service_error!(UpdateTimeToLiveError);
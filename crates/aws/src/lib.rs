@@ -7,6 +7,7 @@ pub mod constants;
 mod credentials;
 pub mod errors;
 pub mod logstore;
+pub mod maintenance;
 #[cfg(feature = "native-tls")]
 mod native;
 pub mod storage;
@@ -23,13 +24,13 @@ use aws_sdk_dynamodb::{
     },
     types::{
         AttributeDefinition, AttributeValue, BillingMode, KeySchemaElement, KeyType,
-        ScalarAttributeType,
+        ScalarAttributeType, TimeToLiveSpecification, TimeToLiveStatus,
     },
 };
 use deltalake_core::logstore::object_store::aws::AmazonS3ConfigKey;
 use deltalake_core::logstore::{
-    LogStore, LogStoreFactory, ObjectStoreRef, StorageConfig, default_logstore, logstore_factories,
-    object_store_factories,
+    LogStore, LogStoreConfig, LogStoreFactory, ObjectStoreRef, StorageConfig, default_logstore,
+    logstore_factories, object_store_factories,
 };
 use deltalake_core::{DeltaResult, Path};
 use errors::{DynamoDbConfigError, LockClientError};
@@ -83,6 +84,18 @@ impl LogStoreFactory for S3LogStoreFactory {
             ));
         }
 
+        let conditional_put = s3_options
+            .iter()
+            .find(|(key, _)| {
+                let key = key.to_ascii_lowercase();
+                [
+                    AmazonS3ConfigKey::ConditionalPut.as_ref(),
+                    "conditional_put",
+                ]
+                .contains(&key.as_str())
+            })
+            .map(|(_, value)| value.to_ascii_lowercase());
+
         let s3_options = S3StorageOptions::from_map(&s3_options)?;
         if s3_options.locking_provider.as_deref() == Some("dynamodb") {
             debug!(
@@ -96,6 +109,34 @@ impl LogStoreFactory for S3LogStoreFactory {
                 root_store,
             )?));
         }
+
+        if !s3_options.allow_unsafe_rename {
+            match conditional_put.as_deref() {
+                Some("etag") => {
+                    debug!(
+                        "S3LogStoreFactory has been asked to create a LogStore where the underlying store has conditional put enabled"
+                    );
+                    return Ok(logstore::conditional_put_s3_logstore(
+                        prefixed_store,
+                        root_store,
+                        location,
+                        options,
+                    ));
+                }
+                Some("disabled") => {
+                    // Reading does not need conditional puts, the first commit fails instead
+                    return Ok(Arc::new(
+                        logstore::S3ConditionalPutLogStore::new(
+                            prefixed_store,
+                            root_store,
+                            LogStoreConfig::new(location, options.clone()),
+                        )
+                        .with_conditional_put_disabled(),
+                    ));
+                }
+                _ => (),
+            }
+        }
         Ok(default_logstore(
             prefixed_store,
             root_store,
@@ -213,10 +254,19 @@ impl DynamoDbLockClient {
             )
             .map_err(|err| DynamoDbConfigError::ParseMaxElapsedRequestTime { source: err })?;
 
+        let commit_entry_expiration = std::env::var(constants::COMMIT_ENTRY_EXPIRATION_KEY_NAME)
+            .ok()
+            .map_or_else(
+                || Ok(constants::DEFAULT_COMMIT_ENTRY_EXPIRATION_DELAY),
+                |secs| u64::from_str(&secs).map(Duration::from_secs),
+            )
+            .map_err(|err| DynamoDbConfigError::ParseCommitEntryExpiration { source: err })?;
+
         let config = DynamoDbConfig::builder()
             .billing_mode(billing_mode)
             .lock_table_name(lock_table_name)
             .max_elapsed_request_time(max_elapsed_request_time)
+            .commit_entry_expiration(commit_entry_expiration)
             .sdk_config(sdk_config.clone())
             .build();
         Ok(Self::builder()
@@ -224,6 +274,14 @@ impl DynamoDbLockClient {
             .config(config)
             .build())
     }
+
+    /// Set the delay after which completed commit entries expire, overriding the
+    /// `DELTA_DYNAMO_COMMIT_ENTRY_EXPIRATION_SECONDS` environment variable.
+    pub fn with_commit_entry_expiration(mut self, commit_entry_expiration: Duration) -> Self {
+        self.config.commit_entry_expiration = commit_entry_expiration;
+        self
+    }
+
     fn create_dynamodb_sdk_config(
         sdk_config: &SdkConfig,
         dynamodb_override_endpoint: Option<String>,
@@ -323,11 +381,62 @@ impl DynamoDbLockClient {
         }
     }
 
+    /// Enable DynamoDb's time-to-live on the `expireTime` attribute of the lock table, so that
+    /// completed commit entries are removed once they expire.
+    ///
+    /// Safe to call when TTL is already enabled on the table.
+    pub async fn try_enable_ttl(&self) -> Result<EnableTtlResult, LockClientError> {
+        let description = self
+            .dynamodb_client
+            .describe_time_to_live()
+            .table_name(&self.config.lock_table_name)
+            .send()
+            .await?;
+        if let Some(ttl) = description.time_to_live_description() {
+            match ttl.time_to_live_status() {
+                Some(TimeToLiveStatus::Enabled | TimeToLiveStatus::Enabling)
+                    if ttl.attribute_name() == Some(constants::ATTR_EXPIRE_TIME) =>
+                {
+                    return Ok(EnableTtlResult::AlreadyEnabled);
+                }
+                Some(TimeToLiveStatus::Enabled | TimeToLiveStatus::Enabling) => {
+                    return Err(LockClientError::InconsistentData {
+                        description: format!(
+                            "time-to-live of lock table '{}' is enabled on attribute '{}' instead of '{}'",
+                            self.config.lock_table_name,
+                            ttl.attribute_name().unwrap_or_default(),
+                            constants::ATTR_EXPIRE_TIME,
+                        ),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        let specification = TimeToLiveSpecification::builder()
+            .attribute_name(constants::ATTR_EXPIRE_TIME)
+            .enabled(true)
+            .build()
+            .unwrap();
+        self.dynamodb_client
+            .update_time_to_live()
+            .table_name(&self.config.lock_table_name)
+            .time_to_live_specification(specification)
+            .send()
+            .await?;
+        Ok(EnableTtlResult::Enabled)
+    }
+
     /// Get the name of the lock table for transactional commits used by the DynamoDb lock client.
     pub fn get_lock_table_name(&self) -> String {
         self.config.lock_table_name.clone()
     }
 
+    /// Get the region of the lock table, including any DynamoDb specific override.
+    pub fn get_lock_table_region(&self) -> Option<&aws_sdk_dynamodb::config::Region> {
+        self.dynamodb_client.config().region()
+    }
+
     pub fn get_dynamodb_config(&self) -> &DynamoDbConfig {
         &self.config
     }
@@ -468,17 +577,85 @@ impl DynamoDbLockClient {
             .collect()
     }
 
+    /// List the latest `limit` entries of the delta table on `table_path` which need maintenance.
+    ///
+    /// These are entries that are not complete, i.e. the commit may not have been moved to its
+    /// final location yet, and complete entries without an `expireTime`, which are never removed
+    /// by DynamoDb's time-to-live.
+    pub async fn list_entries_needing_maintenance(
+        &self,
+        table_path: &str,
+        limit: i64,
+    ) -> Result<Vec<CommitEntry>, LockClientError> {
+        Ok(self
+            .get_latest_entries(table_path, limit)
+            .await?
+            .into_iter()
+            .filter(|entry| !entry.complete || entry.expire_time.is_none())
+            .collect())
+    }
+
+    /// Set the `expireTime` of a complete log entry which does not have one.
+    pub async fn set_expire_time(
+        &self,
+        version: i64,
+        table_path: &str,
+    ) -> Result<UpdateLogEntryResult, LockClientError> {
+        let res = self
+            .retry(
+                || async {
+                    let _ = self
+                        .dynamodb_client
+                        .update_item()
+                        .table_name(self.get_lock_table_name())
+                        .set_key(Some(get_primary_key(version, table_path)))
+                        .update_expression("SET expireTime = :e".to_owned())
+                        .set_expression_attribute_values(Some(HashMap::from([
+                            (":e".to_owned(), num_attr(self.expire_time_epoch())),
+                            (":t".into(), string_attr("true")),
+                        ])))
+                        .condition_expression(
+                            constants::CONDITION_UPDATE_MISSING_EXPIRE_TIME.as_str(),
+                        )
+                        .send()
+                        .await?;
+                    Ok(())
+                },
+                |err: &SdkError<_, _>| {
+                    matches!(
+                        err.as_service_error(),
+                        Some(UpdateItemError::ProvisionedThroughputExceededException(_))
+                    )
+                },
+            )
+            .await;
+
+        match res {
+            Ok(()) => Ok(UpdateLogEntryResult::UpdatePerformed),
+            Err(err) => match err.as_service_error() {
+                Some(UpdateItemError::ProvisionedThroughputExceededException(_)) => {
+                    Err(LockClientError::ProvisionedThroughputExceeded)
+                }
+                Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                    Ok(UpdateLogEntryResult::AlreadyCompleted)
+                }
+                _ => Err(err.into()),
+            },
+        }
+    }
+
+    /// Epoch seconds at which an entry completed now is safe to be deleted
+    fn expire_time_epoch(&self) -> u64 {
+        system_time_to_epoch(&(SystemTime::now() + self.config.commit_entry_expiration))
+    }
+
     /// Update existing log entry
     pub async fn update_commit_entry(
         &self,
         version: i64,
         table_path: &str,
     ) -> Result<UpdateLogEntryResult, LockClientError> {
-        let seconds_since_epoch = (SystemTime::now()
-            + constants::DEFAULT_COMMIT_ENTRY_EXPIRATION_DELAY)
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let seconds_since_epoch = self.expire_time_epoch();
         let res = self
             .retry(
                 || async {
@@ -685,6 +862,9 @@ pub struct DynamoDbConfig {
     pub lock_table_name: String,
     /// Maximum time to wait for DynamoDB requests
     pub max_elapsed_request_time: Duration,
+    /// Delay after which completed commit entries expire
+    #[builder(default = constants::DEFAULT_COMMIT_ENTRY_EXPIRATION_DELAY)]
+    pub commit_entry_expiration: Duration,
     /// AWS SDK configuration
     pub sdk_config: SdkConfig,
 }
@@ -695,6 +875,7 @@ impl PartialEq for DynamoDbConfig {
         self.billing_mode == other.billing_mode
             && self.lock_table_name == other.lock_table_name
             && self.max_elapsed_request_time == other.max_elapsed_request_time
+            && self.commit_entry_expiration == other.commit_entry_expiration
             && self.sdk_config.endpoint_url() == other.sdk_config.endpoint_url()
             && self.sdk_config.region() == other.sdk_config.region()
     }
@@ -710,6 +891,15 @@ pub enum CreateLockTableResult {
     TableAlreadyExists,
 }
 
+/// Represents the possible, positive outcomes of calling `DynamoDbClient::try_enable_ttl()`
+#[derive(Debug, PartialEq)]
+pub enum EnableTtlResult {
+    /// Time-to-live has been enabled on the `expireTime` attribute.
+    Enabled,
+    /// Time-to-live was already enabled on the `expireTime` attribute.
+    AlreadyEnabled,
+}
+
 /// Extract a field from an item's attribute value map, producing a descriptive error
 /// of the various failure cases.
 fn extract_required_string_field<'a>(
@@ -774,6 +964,8 @@ mod tests {
 
     use pretty_assertions::assert_eq;
 
+    use deltalake_core::kernel::transaction::TransactionError;
+    use deltalake_core::logstore::CommitOrBytes;
     use object_store::memory::InMemory;
     use serial_test::serial;
 
@@ -831,8 +1023,8 @@ mod tests {
         Ok(())
     }
 
-    /// In cases where there is no dynamodb specified locking provider, this should get a default
-    /// logstore
+    /// In cases where there is no dynamodb specified locking provider, this should get the
+    /// conditional put logstore
     #[test]
     #[serial]
     fn test_logstore_factory_default() {
@@ -845,7 +1037,37 @@ mod tests {
         let logstore = factory
             .with_options(store.clone(), store, &url, &Default::default())
             .unwrap();
-        assert_eq!(logstore.name(), "DefaultLogStore");
+        assert_eq!(logstore.name(), "S3ConditionalPutLogStore");
+    }
+
+    /// Tables can still be read with conditional put disabled, only committing fails
+    #[tokio::test]
+    #[serial]
+    async fn test_logstore_factory_conditional_put_disabled() {
+        let factory = S3LogStoreFactory::default();
+        let store = Arc::new(InMemory::new());
+        let url = Url::parse("s3://test-bucket").unwrap();
+        unsafe {
+            std::env::remove_var(crate::constants::AWS_S3_LOCKING_PROVIDER);
+        }
+        let options = StorageConfig::parse_options([("conditional_put", "disabled")]).unwrap();
+        let logstore = factory
+            .with_options(store.clone(), store, &url, &options)
+            .unwrap();
+        assert_eq!(logstore.read_commit_entry(0).await.unwrap(), None);
+
+        let result = logstore
+            .write_commit_entry(
+                0,
+                CommitOrBytes::LogBytes(bytes::Bytes::from("{}")),
+                uuid::Uuid::new_v4(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransactionError::LogStoreError { .. })
+        ));
+        assert_eq!(logstore.read_commit_entry(0).await.unwrap(), None);
     }
 
     #[test]
@@ -0,0 +1,315 @@
+//! [`LogStore`] implementation for S3 backends supporting conditional writes.
+//!
+//! S3 and most S3 compatible stores honor `If-None-Match: *` on `PutObject`, which lets the
+//! commit be written atomically to `N.json` without a DynamoDb lock table or a rename.
+
+use std::sync::{Arc, OnceLock};
+
+use bytes::Bytes;
+use deltalake_core::logstore::*;
+use deltalake_core::{
+    DeltaResult, kernel::transaction::TransactionError, logstore::ObjectStoreRef,
+};
+use object_store::path::Path;
+use object_store::{Error as ObjectStoreError, ObjectStore, PutMode, PutOptions};
+use tokio::runtime::Handle;
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::errors::ConditionalPutError;
+
+fn put_options() -> &'static PutOptions {
+    static PUT_OPTS: OnceLock<PutOptions> = OnceLock::new();
+    PUT_OPTS.get_or_init(|| PutOptions {
+        mode: PutMode::Create,
+        ..Default::default()
+    })
+}
+
+/// Return the [S3ConditionalPutLogStore] implementation with the provided configuration options
+///
+/// Whether the store honors conditional puts is checked in the background right away, see
+/// [`S3ConditionalPutLogStore::start_conditional_put_check`].
+pub fn conditional_put_s3_logstore(
+    store: ObjectStoreRef,
+    root_store: ObjectStoreRef,
+    location: &Url,
+    options: &StorageConfig,
+) -> Arc<dyn LogStore> {
+    let log_store = S3ConditionalPutLogStore::new(
+        store,
+        root_store,
+        LogStoreConfig::new(location, options.clone()),
+    );
+    log_store.start_conditional_put_check();
+    Arc::new(log_store)
+}
+
+/// [`LogStore`] implementation committing with conditional puts (`If-None-Match: *`)
+///
+/// Commits are written by the [default log store](default_logstore), this store only makes sure
+/// the backend actually rejects overwrites before the first commit.
+#[derive(Debug)]
+pub struct S3ConditionalPutLogStore {
+    inner: Arc<dyn LogStore>,
+    /// Set once the store has been verified to reject overwrites
+    supported: Arc<OnceCell<()>>,
+    /// Conditional puts were disabled in the storage options, committing always fails
+    conditional_put_disabled: bool,
+}
+
+impl S3ConditionalPutLogStore {
+    /// Create a new instance of [`S3ConditionalPutLogStore`]
+    ///
+    /// # Arguments
+    ///
+    /// * `prefixed_store` - A shared reference to an [`object_store::ObjectStore`]
+    ///   with "/" pointing at delta table root (i.e. where `_delta_log` is located).
+    /// * `root_store` - A shared reference to an [`object_store::ObjectStore`] with "/"
+    ///   pointing at root of the storage system.
+    /// * `config` - Configuration of the log store.
+    pub fn new(
+        prefixed_store: ObjectStoreRef,
+        root_store: ObjectStoreRef,
+        config: LogStoreConfig,
+    ) -> Self {
+        Self {
+            inner: default_logstore(
+                prefixed_store,
+                root_store,
+                config.location(),
+                config.options(),
+            ),
+            supported: Arc::new(OnceCell::new()),
+            conditional_put_disabled: false,
+        }
+    }
+
+    /// Reject every commit, for stores configured with `conditional_put=disabled`.
+    ///
+    /// Reading the table works as usual, so read-only users do not need a locking provider.
+    pub fn with_conditional_put_disabled(mut self) -> Self {
+        self.conditional_put_disabled = true;
+        self
+    }
+
+    /// Start checking whether the store rejects overwrites on the IO runtime, or the current
+    /// runtime if none is configured.
+    ///
+    /// A store without support is reported in the logs as soon as the check completes, the first
+    /// commit waits for the check and fails with the same error. Without a runtime the check is
+    /// only run by the first commit.
+    pub fn start_conditional_put_check(&self) {
+        if self.conditional_put_disabled {
+            return;
+        }
+        let handle = match self.config().options().runtime.as_ref() {
+            Some(runtime) => runtime.get_handle(),
+            None => match Handle::try_current() {
+                Ok(handle) => handle,
+                Err(_) => return,
+            },
+        };
+        let store = self.object_store(None);
+        let supported = self.supported.clone();
+        let location = self.config().location().clone();
+        handle.spawn(async move {
+            if let Err(err) = check_support(store, &supported, &location).await {
+                warn!("{location} cannot be committed to: {err}");
+            }
+        });
+    }
+
+    /// Verify that the store rejects a conditional put on an existing object.
+    ///
+    /// Stores ignoring `If-None-Match` would silently overwrite concurrent commits, so this is
+    /// checked once per log store by writing a probe object at the table root twice.
+    pub async fn check_conditional_put_support(&self) -> Result<(), ConditionalPutError> {
+        if self.conditional_put_disabled {
+            return Err(ConditionalPutError::Disabled);
+        }
+        check_support(
+            self.object_store(None),
+            &self.supported,
+            self.config().location(),
+        )
+        .await
+    }
+}
+
+async fn check_support(
+    store: ObjectStoreRef,
+    supported: &OnceCell<()>,
+    location: &Url,
+) -> Result<(), ConditionalPutError> {
+    supported
+        .get_or_try_init(|| async {
+            // outside of `_delta_log`, and hidden from readers and vacuum by the leading `_`
+            let probe = Path::from(format!("_conditional_put_check_{}.tmp", Uuid::new_v4()));
+            store
+                .put_opts(&probe, Bytes::new().into(), put_options().clone())
+                .await
+                .map_err(map_unsupported)?;
+            let second = store
+                .put_opts(&probe, Bytes::new().into(), put_options().clone())
+                .await;
+            let _ = store.delete(&probe).await;
+            match second {
+                Err(ObjectStoreError::AlreadyExists { .. })
+                | Err(ObjectStoreError::Precondition { .. }) => {
+                    debug!("Conditional put is supported by {location}");
+                    Ok(())
+                }
+                Ok(_) => Err(ConditionalPutError::NotEnforced),
+                Err(err) => Err(map_unsupported(err)),
+            }
+        })
+        .await?;
+    Ok(())
+}
+
+fn map_unsupported(err: ObjectStoreError) -> ConditionalPutError {
+    match err {
+        ObjectStoreError::NotImplemented | ObjectStoreError::NotSupported { .. } => {
+            ConditionalPutError::NotSupported {
+                source: Box::new(err),
+            }
+        }
+        err => ConditionalPutError::ObjectStore { source: err },
+    }
+}
+
+#[async_trait::async_trait]
+impl LogStore for S3ConditionalPutLogStore {
+    fn name(&self) -> String {
+        "S3ConditionalPutLogStore".into()
+    }
+
+    async fn read_commit_entry(&self, version: i64) -> DeltaResult<Option<Bytes>> {
+        self.inner.read_commit_entry(version).await
+    }
+
+    /// Tries to commit a prepared commit file. Returns [`TransactionError::VersionAlreadyExists`]
+    /// if the given `version` already exists. The caller should handle the retry logic itself.
+    /// This is low-level transaction API. If user does not want to maintain the commit loop then
+    /// the `DeltaTransaction.commit` is desired to be used as it handles `try_commit_transaction`
+    /// with retry logic.
+    async fn write_commit_entry(
+        &self,
+        version: i64,
+        commit_or_bytes: CommitOrBytes,
+        operation_id: Uuid,
+    ) -> Result<(), TransactionError> {
+        self.check_conditional_put_support().await.map_err(|err| {
+            TransactionError::LogStoreError {
+                msg: format!("cannot commit to {}", self.config().location()),
+                source: Box::new(err),
+            }
+        })?;
+        self.inner
+            .write_commit_entry(version, commit_or_bytes, operation_id)
+            .await
+    }
+
+    async fn abort_commit_entry(
+        &self,
+        version: i64,
+        commit_or_bytes: CommitOrBytes,
+        operation_id: Uuid,
+    ) -> Result<(), TransactionError> {
+        self.inner
+            .abort_commit_entry(version, commit_or_bytes, operation_id)
+            .await
+    }
+
+    async fn get_latest_version(&self, current_version: i64) -> DeltaResult<i64> {
+        get_latest_version(self, current_version).await
+    }
+
+    fn object_store(&self, operation_id: Option<Uuid>) -> Arc<dyn ObjectStore> {
+        self.inner.object_store(operation_id)
+    }
+
+    fn root_object_store(&self, operation_id: Option<Uuid>) -> Arc<dyn ObjectStore> {
+        self.inner.root_object_store(operation_id)
+    }
+
+    fn config(&self) -> &LogStoreConfig {
+        self.inner.config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use object_store::memory::InMemory;
+
+    fn log_store() -> S3ConditionalPutLogStore {
+        let store = Arc::new(InMemory::new());
+        let url = Url::parse("s3://bucket/table/").unwrap();
+        S3ConditionalPutLogStore::new(
+            store.clone(),
+            store,
+            LogStoreConfig::new(&url, Default::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_write_commit_entry() {
+        let log_store = log_store();
+        log_store
+            .write_commit_entry(
+                0,
+                CommitOrBytes::LogBytes(Bytes::from("{}")),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        let result = log_store
+            .write_commit_entry(
+                0,
+                CommitOrBytes::LogBytes(Bytes::from("{}")),
+                Uuid::new_v4(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransactionError::VersionAlreadyExists(0))
+        ));
+        assert_eq!(
+            log_store.read_commit_entry(0).await.unwrap(),
+            Some(Bytes::from("{}"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_conditional_put_support() {
+        let log_store = log_store();
+        log_store.check_conditional_put_support().await.unwrap();
+        // the probe object does not stay behind
+        let files = object_store::ObjectStore::list(log_store.object_store(None).as_ref(), None);
+        assert_eq!(futures::StreamExt::count(files).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_put_checked_when_built() {
+        let store = Arc::new(InMemory::new());
+        let url = Url::parse("s3://bucket/table/").unwrap();
+        let log_store = S3ConditionalPutLogStore::new(
+            store.clone(),
+            store,
+            LogStoreConfig::new(&url, Default::default()),
+        );
+        log_store.start_conditional_put_check();
+        for _ in 0..100 {
+            if log_store.supported.initialized() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(log_store.supported.initialized());
+    }
+}
@@ -7,11 +7,14 @@ use crate::errors::LockClientError;
 use crate::storage::S3StorageOptions;
 use crate::{CommitEntry, DynamoDbLockClient, UpdateLogEntryResult, constants};
 
+use std::time::Duration;
+
 use bytes::Bytes;
 use tracing::{debug, error, warn};
 use typed_builder::TypedBuilder;
 use url::Url;
 
+use deltalake_core::logstore::object_store::ObjectStore;
 use deltalake_core::logstore::*;
 use deltalake_core::table::normalize_table_url;
 use deltalake_core::{
@@ -82,6 +85,32 @@ impl S3DynamoDbLogStore {
                 source: Box::new(err),
             },
         })?;
+        let lock_client = match s3_options
+            .extra_opts
+            .get(constants::COMMIT_ENTRY_EXPIRATION_KEY_NAME)
+        {
+            Some(secs) => {
+                let secs = secs.parse::<u64>().map_err(|err| {
+                    DeltaTableError::Generic(format!(
+                        "invalid value for {}: {err}",
+                        constants::COMMIT_ENTRY_EXPIRATION_KEY_NAME
+                    ))
+                })?;
+                lock_client.with_commit_entry_expiration(Duration::from_secs(secs))
+            }
+            None => lock_client,
+        };
+        // The lock table lives in a single region, writers in other regions contend on it with
+        // higher latency and must all agree on the same table to keep commits atomic.
+        if let (Some(s3_region), Some(dynamodb_region)) =
+            (s3_options.region(), lock_client.get_lock_table_region())
+            && s3_region != dynamodb_region
+        {
+            warn!(
+                "DynamoDb lock table '{}' is in region {dynamodb_region} while table {location} is in region {s3_region}",
+                lock_client.get_lock_table_name()
+            );
+        }
         Ok(Self::builder()
             .prefixed_store(prefixed_store)
             .root_store(root_store)
@@ -97,84 +126,90 @@ impl S3DynamoDbLogStore {
         &self,
         entry: &CommitEntry,
     ) -> Result<RepairLogEntryResult, TransactionError> {
-        // java does this, do we need it?
-        if entry.complete {
-            return Ok(RepairLogEntryResult::AlreadyCompleted);
-        }
-        for retry in 0..=MAX_REPAIR_RETRIES {
-            match write_commit_entry(
-                self.object_store(None).as_ref(),
-                entry.version,
-                &entry.temp_path,
-            )
-            .await
-            {
-                Ok(()) => {
-                    debug!("Successfully committed entry for version {}", entry.version);
-                    return self.try_complete_entry(entry, true).await;
-                }
-                // `N.json` has already been moved, complete the entry in DynamoDb just in case
-                Err(TransactionError::ObjectStore {
-                    source: ObjectStoreError::NotFound { .. },
-                }) => {
-                    warn!(
-                        "It looks like the {}.json has already been moved, we got 404 from ObjectStorage.",
-                        entry.version
-                    );
-                    return self.try_complete_entry(entry, false).await;
-                }
-                Err(err) if retry == MAX_REPAIR_RETRIES => return Err(err),
-                Err(err) => {
-                    debug!("retry #{retry} on log entry {entry:?} failed to move commit: '{err}'")
-                }
+        repair_entry(
+            &self.lock_client,
+            self.object_store(None).as_ref(),
+            self.table_path.as_str(),
+            entry,
+        )
+        .await
+    }
+}
+
+/// Repair an incomplete log entry of the table at `table_path`, `object_store` must be rooted
+/// at that table.
+pub(crate) async fn repair_entry(
+    lock_client: &DynamoDbLockClient,
+    object_store: &dyn ObjectStore,
+    table_path: &str,
+    entry: &CommitEntry,
+) -> Result<RepairLogEntryResult, TransactionError> {
+    // java does this, do we need it?
+    if entry.complete {
+        return Ok(RepairLogEntryResult::AlreadyCompleted);
+    }
+    for retry in 0..=MAX_REPAIR_RETRIES {
+        match write_commit_entry(object_store, entry.version, &entry.temp_path).await {
+            Ok(()) => {
+                debug!("Successfully committed entry for version {}", entry.version);
+                return try_complete_entry(lock_client, table_path, entry, true).await;
+            }
+            // `N.json` has already been moved, complete the entry in DynamoDb just in case
+            Err(TransactionError::ObjectStore {
+                source: ObjectStoreError::NotFound { .. },
+            }) => {
+                warn!(
+                    "It looks like the {}.json has already been moved, we got 404 from ObjectStorage.",
+                    entry.version
+                );
+                return try_complete_entry(lock_client, table_path, entry, false).await;
+            }
+            Err(err) if retry == MAX_REPAIR_RETRIES => return Err(err),
+            Err(err) => {
+                debug!("retry #{retry} on log entry {entry:?} failed to move commit: '{err}'")
             }
         }
-        unreachable!("for loop yields Ok or Err in body when retry = MAX_REPAIR_RETRIES")
     }
+    unreachable!("for loop yields Ok or Err in body when retry = MAX_REPAIR_RETRIES")
+}
 
-    /// Update an incomplete log entry to completed.
-    async fn try_complete_entry(
-        &self,
-        entry: &CommitEntry,
-        copy_performed: bool,
-    ) -> Result<RepairLogEntryResult, TransactionError> {
-        debug!("try_complete_entry for {entry:?}, {copy_performed}");
-        for retry in 0..=MAX_REPAIR_RETRIES {
-            match self
-                .lock_client
-                .update_commit_entry(entry.version, self.table_path.as_str())
-                .await
-                .map_err(|err| TransactionError::LogStoreError {
-                    msg: format!(
-                        "unable to complete entry for '{}': failure to write to DynamoDb",
-                        entry.version
-                    ),
-                    source: Box::new(err),
-                }) {
-                Ok(x) => return Ok(Self::map_retry_result(x, copy_performed)),
-                Err(err) if retry == MAX_REPAIR_RETRIES => return Err(err),
-                Err(err) => error!(
-                    "retry #{retry} on log entry {entry:?} failed to update lock db: '{err}'"
+/// Update an incomplete log entry to completed.
+async fn try_complete_entry(
+    lock_client: &DynamoDbLockClient,
+    table_path: &str,
+    entry: &CommitEntry,
+    copy_performed: bool,
+) -> Result<RepairLogEntryResult, TransactionError> {
+    debug!("try_complete_entry for {entry:?}, {copy_performed}");
+    for retry in 0..=MAX_REPAIR_RETRIES {
+        match lock_client
+            .update_commit_entry(entry.version, table_path)
+            .await
+            .map_err(|err| TransactionError::LogStoreError {
+                msg: format!(
+                    "unable to complete entry for '{}': failure to write to DynamoDb",
+                    entry.version
                 ),
+                source: Box::new(err),
+            }) {
+            Ok(x) => return Ok(map_retry_result(x, copy_performed)),
+            Err(err) if retry == MAX_REPAIR_RETRIES => return Err(err),
+            Err(err) => {
+                error!("retry #{retry} on log entry {entry:?} failed to update lock db: '{err}'")
             }
         }
-        unreachable!("for loop yields Ok or Err in body when retry = MAX_REPAIR_RETRIES")
     }
+    unreachable!("for loop yields Ok or Err in body when retry = MAX_REPAIR_RETRIES")
+}
 
-    fn map_retry_result(
-        result: UpdateLogEntryResult,
-        copy_performed: bool,
-    ) -> RepairLogEntryResult {
-        match result {
-            UpdateLogEntryResult::UpdatePerformed if copy_performed => {
-                RepairLogEntryResult::MovedFileAndFixedEntry
-            }
-            UpdateLogEntryResult::UpdatePerformed => RepairLogEntryResult::FixedEntry,
-            UpdateLogEntryResult::AlreadyCompleted if copy_performed => {
-                RepairLogEntryResult::MovedFile
-            }
-            UpdateLogEntryResult::AlreadyCompleted => RepairLogEntryResult::AlreadyCompleted,
+fn map_retry_result(result: UpdateLogEntryResult, copy_performed: bool) -> RepairLogEntryResult {
+    match result {
+        UpdateLogEntryResult::UpdatePerformed if copy_performed => {
+            RepairLogEntryResult::MovedFileAndFixedEntry
         }
+        UpdateLogEntryResult::UpdatePerformed => RepairLogEntryResult::FixedEntry,
+        UpdateLogEntryResult::AlreadyCompleted if copy_performed => RepairLogEntryResult::MovedFile,
+        UpdateLogEntryResult::AlreadyCompleted => RepairLogEntryResult::AlreadyCompleted,
     }
 }
 
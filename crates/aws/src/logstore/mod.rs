@@ -1,11 +1,16 @@
 //! Contains the different logstore implementations for S3.
 //! - S3LogStore (used when copy-if-not-exists or unsafe_rename is passed)
 //! - S3DynamoDBLogStore (used when DynamoDB is the locking client)
+//! - S3ConditionalPutLogStore (used when conditional put is enabled, the default)
 
+mod conditional_put_logstore;
 mod default_logstore;
 mod dynamodb_logstore;
 
+pub use conditional_put_logstore::S3ConditionalPutLogStore;
+pub use conditional_put_logstore::conditional_put_s3_logstore;
 pub use default_logstore::S3LogStore;
 pub use default_logstore::default_s3_logstore;
 pub use dynamodb_logstore::RepairLogEntryResult;
 pub use dynamodb_logstore::S3DynamoDbLogStore;
+pub(crate) use dynamodb_logstore::repair_entry;
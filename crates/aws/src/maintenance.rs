@@ -0,0 +1,233 @@
+//! Maintenance of the DynamoDb lock table.
+//!
+//! Commit entries are normally repaired lazily, whenever a writer or reader of the same table
+//! stumbles upon an incomplete entry. [`LockTableMaintenance`] repairs the incomplete entries of
+//! a set of tables at once and sets a missing `expireTime` on complete entries, so DynamoDb's
+//! time-to-live eventually removes them.
+//!
+//! Only the latest entries of every table are looked at, since a writer always repairs the
+//! previous entry of a table before committing the next version.
+//!
+//! ```ignore
+//! deltalake_aws::register_handlers(None);
+//! let metrics = LockTableMaintenance::new(&lock_client, ["s3://bucket/table"], storage_options)
+//!     .run()
+//!     .await?;
+//! println!("repaired {} entries", metrics.num_repaired());
+//! ```
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use deltalake_core::DeltaResult;
+use deltalake_core::logstore::store_for;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use crate::errors::LockClientError;
+use crate::logstore::{RepairLogEntryResult, repair_entry};
+use crate::{CommitEntry, DynamoDbLockClient, UpdateLogEntryResult};
+
+/// Number of latest entries of a table looked at by default
+const DEFAULT_ENTRIES_PER_TABLE: i64 = 100;
+
+/// Repairs and expires the commit entries of a set of tables in a DynamoDb lock table.
+pub struct LockTableMaintenance<'a> {
+    lock_client: &'a DynamoDbLockClient,
+    table_paths: Vec<String>,
+    storage_options: HashMap<String, String>,
+    entries_per_table: i64,
+}
+
+/// Counters and timings of a [`LockTableMaintenance`] run.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LockTableMaintenanceMetrics {
+    /// Number of distinct tables with entries needing maintenance
+    pub num_tables: usize,
+    /// Number of entries needing maintenance among the latest entries of the tables
+    pub num_scanned_entries: usize,
+    /// Entries whose commit file was moved and whose entry was completed
+    pub num_moved_file_and_fixed_entry: usize,
+    /// Entries which were completed, the commit file had already been moved
+    pub num_fixed_entry: usize,
+    /// Entries whose commit file was moved, another writer completed the entry
+    pub num_moved_file: usize,
+    /// Entries another writer repaired while the maintenance was running
+    pub num_already_completed: usize,
+    /// Complete entries which got an `expireTime`
+    pub num_expire_time_set: usize,
+    /// Entries which could not be repaired or expired
+    pub num_failed: usize,
+    /// Time spent listing the latest entries of the tables
+    pub scan_duration: Duration,
+    /// Time spent repairing and expiring entries
+    pub repair_duration: Duration,
+}
+
+impl LockTableMaintenanceMetrics {
+    /// Number of entries that were completed by this run.
+    pub fn num_repaired(&self) -> usize {
+        self.num_moved_file_and_fixed_entry + self.num_fixed_entry + self.num_moved_file
+    }
+
+    fn record_repair(&mut self, result: &RepairLogEntryResult) {
+        match result {
+            RepairLogEntryResult::MovedFileAndFixedEntry => {
+                self.num_moved_file_and_fixed_entry += 1
+            }
+            RepairLogEntryResult::FixedEntry => self.num_fixed_entry += 1,
+            RepairLogEntryResult::MovedFile => self.num_moved_file += 1,
+            RepairLogEntryResult::AlreadyCompleted => self.num_already_completed += 1,
+        }
+    }
+}
+
+impl<'a> LockTableMaintenance<'a> {
+    /// Create a maintenance run for the tables on `table_paths` in the lock table of `lock_client`.
+    ///
+    /// The `storage_options` are used to access the S3 location of every table, so they must
+    /// grant access to all of them.
+    pub fn new(
+        lock_client: &'a DynamoDbLockClient,
+        table_paths: impl IntoIterator<Item = impl Into<String>>,
+        storage_options: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            lock_client,
+            table_paths: table_paths
+                .into_iter()
+                .map(|path| path.into().trim_end_matches('/').to_string())
+                .collect(),
+            storage_options,
+            entries_per_table: DEFAULT_ENTRIES_PER_TABLE,
+        }
+    }
+
+    /// Number of latest entries of every table to look at, 100 by default
+    pub fn with_entries_per_table(mut self, entries_per_table: i64) -> Self {
+        self.entries_per_table = entries_per_table;
+        self
+    }
+
+    /// Repair all incomplete entries, in version order per table, and set the `expireTime` of
+    /// complete entries which do not have one.
+    ///
+    /// Failures on single entries are counted in the metrics and do not stop the run. Once an
+    /// entry of a table fails, later versions of that table are left for the next run, since they
+    /// can only complete after the failed one.
+    pub async fn run(&self) -> Result<LockTableMaintenanceMetrics, LockClientError> {
+        let mut metrics = LockTableMaintenanceMetrics::default();
+
+        let start = Instant::now();
+        let mut tables: Vec<(&str, Vec<CommitEntry>)> = Vec::new();
+        for table_path in &self.table_paths {
+            let entries = self
+                .lock_client
+                .list_entries_needing_maintenance(table_path, self.entries_per_table)
+                .await?;
+            if !entries.is_empty() {
+                metrics.num_scanned_entries += entries.len();
+                tables.push((table_path.as_str(), entries));
+            }
+        }
+        metrics.scan_duration = start.elapsed();
+        metrics.num_tables = tables.len();
+
+        let start = Instant::now();
+        for (table_path, mut entries) in tables {
+            entries.sort_by_key(|entry| entry.version);
+            self.maintain_table(table_path, &entries, &mut metrics)
+                .await;
+        }
+        metrics.repair_duration = start.elapsed();
+
+        info!(
+            lock_table = %self.lock_client.get_lock_table_name(),
+            num_tables = metrics.num_tables,
+            num_scanned_entries = metrics.num_scanned_entries,
+            num_repaired = metrics.num_repaired(),
+            num_moved_file_and_fixed_entry = metrics.num_moved_file_and_fixed_entry,
+            num_fixed_entry = metrics.num_fixed_entry,
+            num_moved_file = metrics.num_moved_file,
+            num_already_completed = metrics.num_already_completed,
+            num_expire_time_set = metrics.num_expire_time_set,
+            num_failed = metrics.num_failed,
+            scan_duration_ms = metrics.scan_duration.as_millis() as u64,
+            repair_duration_ms = metrics.repair_duration.as_millis() as u64,
+            "DynamoDb lock table maintenance completed"
+        );
+        Ok(metrics)
+    }
+
+    async fn maintain_table(
+        &self,
+        table_path: &str,
+        entries: &[CommitEntry],
+        metrics: &mut LockTableMaintenanceMetrics,
+    ) {
+        let needs_repair = entries.iter().any(|entry| !entry.complete);
+        let object_store = if needs_repair {
+            match self.object_store(table_path) {
+                Ok(store) => Some(store),
+                Err(err) => {
+                    warn!("cannot access table {table_path} to repair its entries: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        for (idx, entry) in entries.iter().enumerate() {
+            if entry.complete {
+                match self
+                    .lock_client
+                    .set_expire_time(entry.version, table_path)
+                    .await
+                {
+                    Ok(UpdateLogEntryResult::UpdatePerformed) => metrics.num_expire_time_set += 1,
+                    Ok(UpdateLogEntryResult::AlreadyCompleted) => (),
+                    Err(err) => {
+                        warn!(
+                            "failed to set expireTime of {table_path} version {}: {err}",
+                            entry.version
+                        );
+                        metrics.num_failed += 1;
+                    }
+                }
+                continue;
+            }
+
+            let Some(object_store) = object_store.as_ref() else {
+                metrics.num_failed += entries[idx..].iter().filter(|e| !e.complete).count();
+                return;
+            };
+            match repair_entry(self.lock_client, object_store.as_ref(), table_path, entry).await {
+                Ok(result) => {
+                    debug!(
+                        "repaired {table_path} version {}: {result:?}",
+                        entry.version
+                    );
+                    metrics.record_repair(&result);
+                }
+                Err(err) => {
+                    warn!(
+                        "failed to repair {table_path} version {}: {err}",
+                        entry.version
+                    );
+                    metrics.num_failed += entries[idx..].iter().filter(|e| !e.complete).count();
+                    return;
+                }
+            }
+        }
+    }
+
+    fn object_store(
+        &self,
+        table_path: &str,
+    ) -> DeltaResult<deltalake_core::logstore::ObjectStoreRef> {
+        let url = Url::parse(table_path).map_err(|_| {
+            deltalake_core::DeltaTableError::InvalidTableLocation(table_path.to_string())
+        })?;
+        store_for(&url, &self.storage_options)
+    }
+}
@@ -0,0 +1,86 @@
+//! Integration test to verify commits through S3 conditional puts (`If-None-Match: *`),
+//! without a DynamoDb lock table.
+#![cfg(feature = "integration_test")]
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use deltalake_aws::logstore::S3ConditionalPutLogStore;
+use deltalake_core::kernel::transaction::TransactionError;
+use deltalake_core::kernel::{DataType, PrimitiveType};
+use deltalake_core::logstore::{CommitOrBytes, LogStore, LogStoreConfig};
+use deltalake_core::operations::create::CreateBuilder;
+use deltalake_core::{DeltaTableBuilder, ensure_table_uri};
+use deltalake_test::utils::*;
+use serial_test::serial;
+use url::Url;
+use uuid::Uuid;
+
+mod common;
+use common::*;
+
+pub type TestResult<T> = Result<T, Box<dyn std::error::Error + 'static>>;
+
+async fn conditional_put_log_store(
+    context: &IntegrationContext,
+    table_name: &str,
+) -> TestResult<S3ConditionalPutLogStore> {
+    let table_name = format!("{table_name}_{}", Uuid::new_v4());
+    let table_uri = context.uri_for_table(TestTables::Custom(table_name));
+    let log_store = DeltaTableBuilder::from_url(Url::parse(&table_uri)?)?
+        .with_allow_http(true)
+        .build_storage()?;
+    Ok(S3ConditionalPutLogStore::new(
+        log_store.object_store(None),
+        log_store.root_object_store(None),
+        LogStoreConfig::new(&ensure_table_uri(&table_uri)?, Default::default()),
+    ))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_conditional_put_support() -> TestResult<()> {
+    let context = IntegrationContext::new(Box::new(S3Integration::default()))?;
+    let log_store = conditional_put_log_store(&context, "conditional_put_support").await?;
+    log_store.check_conditional_put_support().await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_conditional_put_concurrent_commit() -> TestResult<()> {
+    let context = IntegrationContext::new(Box::new(S3Integration::default()))?;
+    let log_store = Arc::new(conditional_put_log_store(&context, "conditional_put_commit").await?);
+
+    let table = CreateBuilder::new()
+        .with_log_store(log_store.clone())
+        .with_column(
+            "id",
+            DataType::Primitive(PrimitiveType::Integer),
+            true,
+            None,
+        )
+        .await?;
+    assert_eq!(table.version(), Some(0));
+
+    log_store
+        .write_commit_entry(
+            1,
+            CommitOrBytes::LogBytes(Bytes::from("{}")),
+            Uuid::new_v4(),
+        )
+        .await?;
+    let result = log_store
+        .write_commit_entry(
+            1,
+            CommitOrBytes::LogBytes(Bytes::from("{}")),
+            Uuid::new_v4(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(TransactionError::VersionAlreadyExists(1))
+    ));
+    assert_eq!(log_store.get_latest_version(0).await?, 1);
+    Ok(())
+}
@@ -8,8 +8,9 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use aws_sdk_dynamodb::types::BillingMode;
 use deltalake_aws::logstore::{RepairLogEntryResult, S3DynamoDbLogStore};
+use deltalake_aws::maintenance::LockTableMaintenance;
 use deltalake_aws::storage::S3StorageOptions;
-use deltalake_aws::{CommitEntry, DynamoDbConfig, DynamoDbLockClient};
+use deltalake_aws::{CommitEntry, DynamoDbConfig, DynamoDbLockClient, EnableTtlResult};
 use deltalake_core::ensure_table_uri;
 use deltalake_core::kernel::transaction::CommitBuilder;
 use deltalake_core::kernel::{Action, Add, DataType, PrimitiveType, StructField, StructType};
@@ -213,6 +214,83 @@ async fn test_repair_commit_entry() -> TestResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn test_lock_table_maintenance() -> TestResult<()> {
+    let context = IntegrationContext::new(Box::new(S3Integration::default()))?;
+    deltalake_aws::register_handlers(None);
+    let client = make_client()?;
+    let first = prepare_table(&context, "maintenance_first").await?;
+    let second = prepare_table(&context, "maintenance_second").await?;
+
+    create_incomplete_commit_entry(&first, 1, "unfinished_commit").await?;
+    create_incomplete_commit_entry(&first, 2, "unfinished_commit").await?;
+    let entry = create_incomplete_commit_entry(&second, 1, "unfinished_commit").await?;
+    second
+        .log_store()
+        .object_store(None)
+        .rename_if_not_exists(&entry.temp_path, &commit_uri_from_version(entry.version))
+        .await?;
+
+    let table_paths = [
+        first.table_url().to_string(),
+        second.table_url().to_string(),
+    ];
+    let metrics = LockTableMaintenance::new(&client, table_paths.clone(), OPTIONS.clone())
+        .run()
+        .await?;
+    assert_eq!(metrics.num_tables, 2);
+    assert_eq!(metrics.num_moved_file_and_fixed_entry, 2);
+    assert_eq!(metrics.num_fixed_entry, 1);
+    assert_eq!(metrics.num_failed, 0);
+    validate_lock_table_state(&first, 2).await?;
+    validate_lock_table_state(&second, 1).await?;
+
+    // a second run has nothing left to do for these tables
+    let metrics = LockTableMaintenance::new(&client, table_paths, OPTIONS.clone())
+        .run()
+        .await?;
+    assert_eq!(metrics.num_repaired(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_enable_ttl() -> TestResult<()> {
+    let _context = IntegrationContext::new(Box::new(S3Integration::default()))?;
+    let client = make_client()?;
+    let result = client.try_enable_ttl().await?;
+    assert!(matches!(
+        result,
+        EnableTtlResult::Enabled | EnableTtlResult::AlreadyEnabled
+    ));
+    assert_eq!(
+        EnableTtlResult::AlreadyEnabled,
+        client.try_enable_ttl().await?
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_commit_entry_expiration() -> TestResult<()> {
+    let context = IntegrationContext::new(Box::new(S3Integration::default()))?;
+    let client = make_client()?.with_commit_entry_expiration(Duration::from_secs(3600));
+    let table = prepare_table(&context, "commit_entry_expiration").await?;
+    let entry = create_incomplete_commit_entry(&table, 1, "unfinished_commit").await?;
+    client
+        .update_commit_entry(entry.version, table.table_url().as_str())
+        .await?;
+    let entry = client
+        .get_commit_entry(table.table_url().as_str(), 1)
+        .await?
+        .expect("no entry!");
+    let expire_time = entry.expire_time.unwrap();
+    assert!(expire_time <= SystemTime::now() + Duration::from_secs(3600));
+    assert!(expire_time >= SystemTime::now() + Duration::from_secs(3500));
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn test_repair_on_update() -> TestResult<()> {
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use deltalake_core::logstore::{
-    LogStore, LogStoreFactory, ObjectStoreFactory, ObjectStoreRef, StorageConfig, default_logstore,
+    LogStore, LogStoreFactory, ObjectStoreFactory, ObjectStoreRef, StorageConfig,
     logstore_factories, object_store_factories,
 };
 use deltalake_core::{DeltaResult, DeltaTableError, Path};
@@ -14,6 +14,9 @@ use url::Url;
 
 mod config;
 pub mod error;
+pub mod logstore;
+
+pub use logstore::AzureLogStore;
 
 trait AzureOptions {
     fn as_azure_options(&self) -> HashMap<AzureConfigKey, String>;
@@ -74,7 +77,7 @@ impl LogStoreFactory for AzureFactory {
         location: &Url,
         options: &StorageConfig,
     ) -> DeltaResult<Arc<dyn LogStore>> {
-        Ok(default_logstore(
+        Ok(logstore::azure_logstore(
             prefixed_store,
             root_store,
             location,
@@ -109,4 +112,14 @@ mod tests {
         let converted = options.as_azure_options();
         assert_eq!(converted.get(&AzureConfigKey::AccessKey), Some(&value));
     }
+
+    #[test]
+    fn test_azure_factory() {
+        let store: ObjectStoreRef = Arc::new(object_store::memory::InMemory::new());
+        let location = Url::parse("az://container/table").unwrap();
+        let logstore = AzureFactory {}
+            .with_options(store.clone(), store, &location, &StorageConfig::default())
+            .unwrap();
+        assert_eq!(logstore.name(), "AzureLogStore");
+    }
 }
@@ -0,0 +1,179 @@
+//! [`LogStore`] implementation for Azure Blob Storage and ADLS Gen2.
+//!
+//! Commits are written with `If-None-Match: *`, which Azure enforces natively, so a commit
+//! fails instead of overwriting a `N.json` written concurrently by another writer.
+
+use std::sync::{Arc, OnceLock};
+
+use bytes::Bytes;
+use deltalake_core::DeltaResult;
+use deltalake_core::kernel::transaction::TransactionError;
+use deltalake_core::logstore::{
+    CommitOrBytes, LogStore, LogStoreConfig, ObjectStoreRef, StorageConfig,
+    commit_uri_from_version, get_latest_version, read_commit_entry,
+};
+use object_store::{Error as ObjectStoreError, ObjectStore, PutMode, PutOptions};
+use url::Url;
+use uuid::Uuid;
+
+fn put_options() -> &'static PutOptions {
+    static PUT_OPTS: OnceLock<PutOptions> = OnceLock::new();
+    PUT_OPTS.get_or_init(|| PutOptions {
+        mode: PutMode::Create,
+        ..Default::default()
+    })
+}
+
+/// Return the [AzureLogStore] implementation with the provided configuration options
+pub fn azure_logstore(
+    store: ObjectStoreRef,
+    root_store: ObjectStoreRef,
+    location: &Url,
+    options: &StorageConfig,
+) -> Arc<dyn LogStore> {
+    Arc::new(AzureLogStore::new(
+        store,
+        root_store,
+        LogStoreConfig::new(location, options.clone()),
+    ))
+}
+
+/// [`LogStore`] implementation committing with Azure's `If-None-Match` precondition
+#[derive(Debug, Clone)]
+pub struct AzureLogStore {
+    prefixed_store: ObjectStoreRef,
+    root_store: ObjectStoreRef,
+    config: LogStoreConfig,
+}
+
+impl AzureLogStore {
+    /// Create a new instance of [`AzureLogStore`]
+    ///
+    /// # Arguments
+    ///
+    /// * `prefixed_store` - A shared reference to an [`object_store::ObjectStore`]
+    ///   with "/" pointing at delta table root (i.e. where `_delta_log` is located).
+    /// * `root_store` - A shared reference to an [`object_store::ObjectStore`] with "/"
+    ///   pointing at root of the storage system.
+    /// * `config` - Configuration of the log store.
+    pub fn new(
+        prefixed_store: ObjectStoreRef,
+        root_store: ObjectStoreRef,
+        config: LogStoreConfig,
+    ) -> Self {
+        Self {
+            prefixed_store,
+            root_store,
+            config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LogStore for AzureLogStore {
+    fn name(&self) -> String {
+        "AzureLogStore".into()
+    }
+
+    async fn read_commit_entry(&self, version: i64) -> DeltaResult<Option<Bytes>> {
+        read_commit_entry(self.object_store(None).as_ref(), version).await
+    }
+
+    /// Tries to commit a prepared commit file. Returns [`TransactionError::VersionAlreadyExists`]
+    /// if the given `version` already exists. The caller should handle the retry logic itself.
+    /// This is low-level transaction API. If user does not want to maintain the commit loop then
+    /// the `DeltaTransaction.commit` is desired to be used as it handles `try_commit_transaction`
+    /// with retry logic.
+    async fn write_commit_entry(
+        &self,
+        version: i64,
+        commit_or_bytes: CommitOrBytes,
+        _operation_id: Uuid,
+    ) -> Result<(), TransactionError> {
+        let log_bytes = match commit_or_bytes {
+            CommitOrBytes::LogBytes(log_bytes) => log_bytes,
+            _ => unreachable!(), // Azure log store should never get a tmp_commit
+        };
+        self.object_store(None)
+            .put_opts(
+                &commit_uri_from_version(version),
+                log_bytes.into(),
+                put_options().clone(),
+            )
+            .await
+            .map_err(|err| match err {
+                // Azure answers 409 `BlobAlreadyExists` or 412 `ConditionNotMet` depending on
+                // whether the blob was committed before or during the request
+                ObjectStoreError::AlreadyExists { .. } | ObjectStoreError::Precondition { .. } => {
+                    TransactionError::VersionAlreadyExists(version)
+                }
+                err => TransactionError::from(err),
+            })?;
+        Ok(())
+    }
+
+    async fn abort_commit_entry(
+        &self,
+        _version: i64,
+        commit_or_bytes: CommitOrBytes,
+        _operation_id: Uuid,
+    ) -> Result<(), TransactionError> {
+        match &commit_or_bytes {
+            CommitOrBytes::LogBytes(_) => Ok(()),
+            _ => unreachable!(), // Azure log store should never get a tmp_commit
+        }
+    }
+
+    async fn get_latest_version(&self, current_version: i64) -> DeltaResult<i64> {
+        get_latest_version(self, current_version).await
+    }
+
+    fn object_store(&self, _operation_id: Option<Uuid>) -> Arc<dyn ObjectStore> {
+        self.prefixed_store.clone()
+    }
+
+    fn root_object_store(&self, _operation_id: Option<Uuid>) -> Arc<dyn ObjectStore> {
+        self.root_store.clone()
+    }
+
+    fn config(&self) -> &LogStoreConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_write_commit_entry_conflict() {
+        let store = Arc::new(InMemory::new());
+        let url = Url::parse("az://container/table/").unwrap();
+        let log_store = AzureLogStore::new(
+            store.clone(),
+            store,
+            LogStoreConfig::new(&url, Default::default()),
+        );
+        log_store
+            .write_commit_entry(
+                0,
+                CommitOrBytes::LogBytes(Bytes::from("{}")),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        let result = log_store
+            .write_commit_entry(
+                0,
+                CommitOrBytes::LogBytes(Bytes::from("{}")),
+                Uuid::new_v4(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransactionError::VersionAlreadyExists(0))
+        ));
+    }
+}
@@ -6,11 +6,15 @@ use std::collections::HashMap;
 use bytes::Bytes;
 use deltalake_core::DeltaTableBuilder;
 use deltalake_core::data_catalog::storage::ListingSchemaProvider;
+use deltalake_core::kernel::transaction::TransactionError;
+use deltalake_core::logstore::CommitOrBytes;
 use deltalake_test::read::read_table_paths;
+use deltalake_test::utils::TestTables;
 use deltalake_test::{IntegrationContext, TestResult, test_concurrent_writes, test_read_tables};
 use object_store::path::Path;
 use serial_test::serial;
 use url::Url;
+use uuid::Uuid;
 
 mod context;
 use context::*;
@@ -43,6 +47,30 @@ async fn test_concurrency_azure() -> TestResult {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_conflicting_commit_azure() -> TestResult {
+    let context = IntegrationContext::new(Box::new(MsftIntegration::default()))?;
+    let table_uri = context.uri_for_table(TestTables::Custom("conflicting_commit".into()));
+    let log_store = DeltaTableBuilder::from_url(Url::parse(&table_uri)?)?
+        .with_allow_http(true)
+        .build_storage()?;
+    assert_eq!(log_store.name(), "AzureLogStore");
+
+    let commit = || CommitOrBytes::LogBytes(Bytes::from_static(b"{}"));
+    log_store
+        .write_commit_entry(0, commit(), Uuid::new_v4())
+        .await?;
+    let result = log_store
+        .write_commit_entry(0, commit(), Uuid::new_v4())
+        .await;
+    assert!(matches!(
+        result,
+        Err(TransactionError::VersionAlreadyExists(0))
+    ));
+    Ok(())
+}
+
 // NOTE: This test is ignored based on [this
 // comment](https://github.com/delta-io/delta-rs/pull/1564#issuecomment-1721048753) and we should
 // figure out a way to re-enable this test at least in the GitHub Actions CI environment
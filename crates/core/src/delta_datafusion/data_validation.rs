@@ -4,12 +4,14 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use arrow::array::AsArray;
+use arrow::array::{Array, ArrayRef, AsArray, BooleanArray, BooleanBufferBuilder};
 use arrow::compute::{filter_record_batch, not};
-use arrow_array::RecordBatch;
+use arrow_array::{OffsetSizeTrait, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
 use arrow_cast::pretty::pretty_format_batches;
 use arrow_schema::{DataType, SchemaRef};
 use datafusion::catalog::Session;
+use datafusion::common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion::common::{
     DFSchema, DFSchemaRef, Statistics, exec_err, plan_datafusion_err, plan_err,
 };
@@ -18,26 +20,31 @@ use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::{
     RecordBatchStream, SendableRecordBatchStream, SessionState, TaskContext,
 };
+use datafusion::functions::core::expr_fn::get_field;
+use datafusion::logical_expr::expr::ScalarFunction;
 use datafusion::logical_expr::utils::conjunction;
 use datafusion::logical_expr::{
-    ColumnarValue, LogicalPlan, Operator, UserDefinedLogicalNode, UserDefinedLogicalNodeCore,
+    ColumnarValue, LogicalPlan, Operator, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature,
+    UserDefinedLogicalNode, UserDefinedLogicalNodeCore, Volatility,
 };
 use datafusion::optimizer::simplify_expressions::simplify_predicates;
+use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::execution_plan::CardinalityEffect;
 use datafusion::physical_plan::filter_pushdown::{FilterDescription, FilterPushdownPhase};
 use datafusion::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionPlan, PhysicalExpr, PlanProperties,
 };
 use datafusion::physical_planner::{ExtensionPlanner, PhysicalPlanner};
-use datafusion::prelude::{Expr, binary_expr, col};
+use datafusion::prelude::{Expr, binary_expr, col, ident, lit};
 use datafusion::scalar::ScalarValue;
+use delta_kernel::schema::{DataType as DeltaDataType, StructType};
 use delta_kernel::table_configuration::TableConfiguration;
 use delta_kernel::table_features::TableFeature;
 use futures::Stream;
 use itertools::Itertools as _;
 use pin_project_lite::pin_project;
 
-use crate::delta_datafusion::expr::parse_predicate_expression;
+use crate::delta_datafusion::expr::{fmt_expr_to_sql, parse_predicate_expression};
 use crate::delta_datafusion::table_provider::simplify_expr;
 use crate::table::config::TablePropertiesExt as _;
 use crate::table::{Constraint, GeneratedColumn};
@@ -153,12 +160,7 @@ pub(crate) fn validation_predicates(
 ) -> Result<Vec<Expr>> {
     let df_schema = DFSchema::try_from(input.schema())?;
 
-    let mut validations = table_configuration
-        .schema()
-        .fields()
-        .filter(|f| !f.is_nullable())
-        .map(|f| col(f.name()).is_not_null())
-        .collect_vec();
+    let mut validations = nullability_predicates(table_configuration.schema().as_ref());
 
     if table_configuration.is_feature_enabled(&TableFeature::Invariants) {
         let invariants = table_configuration
@@ -167,7 +169,10 @@ pub(crate) fn validation_predicates(
             .map_err(|e| plan_datafusion_err!("Failed to read invariants from schema: {}", e))?;
         for invariant in invariants {
             let expr = parse_predicate_expression(&df_schema, &invariant.invariant_sql, session)?;
-            validations.push(expr);
+            validations.push(expr.alias(format!(
+                "Invariant on {} ({})",
+                invariant.field_name, invariant.invariant_sql
+            )));
         }
     }
 
@@ -189,6 +194,232 @@ pub(crate) fn validation_predicates(
     Ok(validations)
 }
 
+/// NOT NULL checks for all non-nullable fields of the schema, including nested fields
+///
+/// Fields nested in structs are only required to be set if their parent struct is set. Values
+/// nested in lists and maps are checked by [`NestedNotNull`].
+pub(crate) fn nullability_predicates(schema: &StructType) -> Vec<Expr> {
+    let mut predicates = Vec::new();
+    for field in schema.fields() {
+        let column = ident(field.name());
+        if !field.is_nullable() {
+            predicates.push(
+                column
+                    .clone()
+                    .is_not_null()
+                    .alias(not_null_name(field.name())),
+            );
+        }
+        let access = FieldAccess::Struct(column);
+        nested_nullability_predicates(field.data_type(), field.name(), &access, &mut predicates);
+    }
+    predicates
+}
+
+/// How a nested value is reached from its top level column
+#[derive(Clone)]
+enum FieldAccess {
+    /// The value is reached via struct fields only
+    Struct(Expr),
+    /// The value is nested in a list or map of `root`, `path` names the segments below it
+    Nested { root: Expr, path: Vec<String> },
+}
+
+impl FieldAccess {
+    fn child(&self, name: &str) -> Self {
+        match self {
+            Self::Struct(value) => Self::Struct(get_field(value.clone(), name)),
+            Self::Nested { root, path } => Self::Nested {
+                root: root.clone(),
+                path: path.iter().cloned().chain([name.to_string()]).collect(),
+            },
+        }
+    }
+
+    fn nested(&self, segment: &str) -> Self {
+        match self {
+            Self::Struct(value) => Self::Nested {
+                root: value.clone(),
+                path: vec![segment.to_string()],
+            },
+            Self::Nested { .. } => self.child(segment),
+        }
+    }
+
+    /// Predicate that is `true` if the value is set wherever its parent is set
+    fn not_null(&self, parent: &Self) -> Expr {
+        match (self, parent) {
+            (Self::Struct(value), Self::Struct(parent)) => {
+                parent.clone().is_null().or(value.clone().is_not_null())
+            }
+            (Self::Nested { root, path }, _) => NestedNotNull::expr(root.clone(), path),
+            (Self::Struct(value), Self::Nested { .. }) => value.clone().is_not_null(),
+        }
+    }
+}
+
+fn nested_nullability_predicates(
+    data_type: &DeltaDataType,
+    path: &str,
+    access: &FieldAccess,
+    predicates: &mut Vec<Expr>,
+) {
+    let mut visit = |name: &str, data_type: &DeltaDataType, nullable: bool, nested: bool| {
+        let child_path = format!("{path}.{name}");
+        let child = if nested {
+            access.nested(name)
+        } else {
+            access.child(name)
+        };
+        if !nullable {
+            predicates.push(child.not_null(access).alias(not_null_name(&child_path)));
+        }
+        nested_nullability_predicates(data_type, &child_path, &child, predicates);
+    };
+    match data_type {
+        DeltaDataType::Struct(inner) => {
+            for field in inner.fields() {
+                visit(field.name(), field.data_type(), field.is_nullable(), false);
+            }
+        }
+        DeltaDataType::Array(inner) => {
+            visit("element", &inner.element_type, inner.contains_null, true);
+        }
+        DeltaDataType::Map(inner) => {
+            // arrow map keys are never null
+            visit("key", &inner.key_type, true, true);
+            visit("value", &inner.value_type, inner.value_contains_null, true);
+        }
+        _ => {}
+    }
+}
+
+fn not_null_name(path: &str) -> String {
+    format!("NOT NULL constraint on {path}")
+}
+
+/// Checks that no value below a list or map column is null.
+///
+/// Called as `delta_nested_not_null(column, 'element', 'field', ...)`, the path segments name
+/// struct fields, `element` for list elements and `key` / `value` for map entries. A row is
+/// valid unless the value at the path is null while all of its parents are set.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub(crate) struct NestedNotNull {
+    signature: Signature,
+}
+
+impl NestedNotNull {
+    fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+
+    fn expr(root: Expr, path: &[String]) -> Expr {
+        let udf = Arc::new(ScalarUDF::new_from_impl(Self::new()));
+        let args = std::iter::once(root)
+            .chain(path.iter().map(|segment| lit(segment.as_str())))
+            .collect();
+        Expr::ScalarFunction(ScalarFunction::new_udf(udf, args))
+    }
+}
+
+impl ScalarUDFImpl for NestedNotNull {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "delta_nested_not_null"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let Some((root, path)) = args.args.split_first() else {
+            return exec_err!("delta_nested_not_null expects at least one argument");
+        };
+        let path: Vec<_> = path
+            .iter()
+            .map(|segment| match segment {
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(segment))) => Ok(segment.as_str()),
+                other => {
+                    exec_err!("delta_nested_not_null expects string path segments, got {other:?}")
+                }
+            })
+            .try_collect()?;
+        let root = root.to_array(args.number_rows)?;
+        let invalid = null_violations(&root, &path)?;
+        Ok(ColumnarValue::Array(Arc::new(not(&invalid)?)))
+    }
+}
+
+/// Mask of the rows of `array` with a null value at `path` below a set parent
+fn null_violations(array: &ArrayRef, path: &[&str]) -> Result<BooleanArray> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Ok((0..array.len()).map(|i| Some(array.is_null(i))).collect());
+    };
+    let invalid = match (array.data_type(), *segment) {
+        (DataType::Struct(_), name) => {
+            let array = array.as_struct();
+            let Some(child) = array.column_by_name(name) else {
+                return exec_err!("Struct field {name} not found");
+            };
+            let child = null_violations(child, rest)?;
+            (0..array.len())
+                .map(|i| Some(array.is_valid(i) && child.value(i)))
+                .collect()
+        }
+        (DataType::List(_), "element") => {
+            let array = array.as_list::<i32>();
+            list_violations(array, array.values(), rest)?
+        }
+        (DataType::LargeList(_), "element") => {
+            let array = array.as_list::<i64>();
+            list_violations(array, array.values(), rest)?
+        }
+        (DataType::Map(_, _), "key" | "value") => {
+            let array = array.as_map();
+            let entries = if *segment == "key" {
+                array.keys()
+            } else {
+                array.values()
+            };
+            let mut builder = BooleanBufferBuilder::new(array.len());
+            let child = null_violations(entries, rest)?;
+            for (i, window) in array.value_offsets().windows(2).enumerate() {
+                let (start, end) = (window[0] as usize, window[1] as usize);
+                builder.append(array.is_valid(i) && (start..end).any(|j| child.value(j)));
+            }
+            BooleanArray::new(builder.finish(), None)
+        }
+        (data_type, segment) => {
+            return exec_err!("Cannot resolve path segment {segment} in {data_type}");
+        }
+    };
+    Ok(invalid)
+}
+
+fn list_violations<O: OffsetSizeTrait>(
+    array: &arrow_array::GenericListArray<O>,
+    values: &ArrayRef,
+    path: &[&str],
+) -> Result<BooleanArray> {
+    let child = null_violations(values, path)?;
+    let mut builder = BooleanBufferBuilder::new(array.len());
+    for (i, window) in array.value_offsets().windows(2).enumerate() {
+        let (start, end) = (window[0].as_usize(), window[1].as_usize());
+        builder.append(array.is_valid(i) && (start..end).any(|j| child.value(j)));
+    }
+    Ok(BooleanArray::new(builder.finish(), None))
+}
+
 pub(crate) fn constraints_to_exprs<'a>(
     session: &dyn Session,
     df_schema: &DFSchema,
@@ -196,7 +427,13 @@ pub(crate) fn constraints_to_exprs<'a>(
 ) -> Result<Vec<Expr>> {
     Ok(constraints
         .into_iter()
-        .map(|constraint| parse_predicate_expression(df_schema, &constraint.expr, session))
+        .map(|constraint| {
+            let expr = parse_predicate_expression(df_schema, &constraint.expr, session)?;
+            Ok::<_, DeltaTableError>(expr.alias(format!(
+                "CHECK constraint {} ({})",
+                constraint.name, constraint.expr
+            )))
+        })
         .try_collect()?)
 }
 
@@ -211,7 +448,10 @@ pub(crate) fn generated_columns_to_exprs<'a>(
             let expr = parse_predicate_expression(df_schema, &gen_col.generation_expr, session)?;
             let col_expr = col(&gen_col.name);
             let validation_expr = binary_expr(col_expr, Operator::IsNotDistinctFrom, expr);
-            Ok::<_, DataFusionError>(validation_expr)
+            Ok::<_, DataFusionError>(validation_expr.alias(format!(
+                "Generated column {} ({})",
+                gen_col.name, gen_col.generation_expr
+            )))
         })
         .collect()
 }
@@ -233,20 +473,31 @@ pub struct DataValidationExec {
     input: Arc<dyn ExecutionPlan>,
     /// The expression to use for checking data validity
     check_expression: Arc<dyn PhysicalExpr>,
+    /// The individual checks, used to describe which rule invalid data violates
+    checks: Arc<Vec<ValidationCheck>>,
 }
 
 impl DataValidationExec {
     /// Create a new [`DataValidationExec`] if there are any predicates to apply
     /// otherwise return the input execution plan as-is.
+    ///
+    /// Aliased predicates are reported with their alias as the rule name when violated.
     pub fn try_new_with_predicates(
         session: &dyn Session,
         input: Arc<dyn ExecutionPlan>,
         predicates: Vec<Expr>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let df_schema = DFSchema::try_from(input.schema())?;
+        let df_schema: DFSchemaRef = DFSchema::try_from(input.schema())?.into();
+        let checks: Vec<_> = predicates
+            .iter()
+            .map(|predicate| ValidationCheck::try_new(session, &df_schema, predicate.clone()))
+            .try_collect()?;
+        let predicates = predicates.into_iter().map(Expr::unalias).collect();
         if let Some(validation_expr) = conjunction(simplify_predicates(predicates)?) {
-            let check_expression = simplify_expr(session, df_schema.into(), validation_expr)?;
-            return Ok(Arc::new(Self::try_new(input, check_expression)?));
+            let check_expression = simplify_expr(session, df_schema, validation_expr)?;
+            let mut exec = Self::try_new(input, check_expression)?;
+            exec.checks = Arc::new(checks);
+            return Ok(Arc::new(exec));
         }
         Ok(input)
     }
@@ -266,10 +517,152 @@ impl DataValidationExec {
         Ok(Self {
             input,
             check_expression,
+            checks: Arc::new(Vec::new()),
         })
     }
 }
 
+/// A single validation rule, evaluated when a batch fails validation to describe the violation
+#[derive(Debug)]
+struct ValidationCheck {
+    /// Description of the rule
+    name: String,
+    /// Expression evaluating to `true` for valid rows
+    predicate: Arc<dyn PhysicalExpr>,
+    /// Columns and nested fields referenced by the rule, keyed by their path
+    values: Vec<(String, Arc<dyn PhysicalExpr>)>,
+}
+
+impl ValidationCheck {
+    fn try_new(session: &dyn Session, df_schema: &DFSchemaRef, predicate: Expr) -> Result<Self> {
+        let (name, predicate) = match predicate {
+            Expr::Alias(alias) => (alias.name, *alias.expr),
+            predicate => (
+                fmt_expr_to_sql(&predicate).unwrap_or_else(|_| predicate.to_string()),
+                predicate,
+            ),
+        };
+        let values = referenced_values(&predicate)
+            .into_iter()
+            .map(|(path, value)| Ok((path, simplify_expr(session, df_schema.clone(), value)?)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name,
+            predicate: simplify_expr(session, df_schema.clone(), predicate)?,
+            values,
+        })
+    }
+
+    /// Describe the first row of `batch` violating this check, if any
+    fn describe_violation(&self, batch: &RecordBatch) -> Result<Option<String>> {
+        let valid = self
+            .predicate
+            .evaluate(batch)?
+            .into_array(batch.num_rows())?;
+        let valid = valid.as_boolean();
+        let Some(row) = (0..batch.num_rows()).find(|i| valid.is_null(*i) || !valid.value(*i))
+        else {
+            return Ok(None);
+        };
+
+        let row = batch.slice(row, 1);
+        let options = FormatOptions::default().with_null("NULL");
+        let mut message = format!("{} violated by row with values:", self.name);
+        for (path, value) in &self.values {
+            let value = value.evaluate(&row)?.into_array(1)?;
+            let value = ArrayFormatter::try_new(value.as_ref(), &options)?.value(0);
+            message.push_str(&format!("\n - {path} : {value}"));
+        }
+        Ok(Some(message))
+    }
+}
+
+/// Columns and struct fields referenced by `expr` along with their dotted path
+fn referenced_values(expr: &Expr) -> Vec<(String, Expr)> {
+    fn value_path(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Column(column) => Some(column.name.clone()),
+            Expr::ScalarFunction(func) if func.name() == "get_field" => {
+                match func.args.as_slice() {
+                    [base, Expr::Literal(ScalarValue::Utf8(Some(name)), _)] => {
+                        value_path(base).map(|path| format!("{path}.{name}"))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    let mut values: Vec<(String, Expr)> = Vec::new();
+    expr.apply(|expr| {
+        let Some(path) = value_path(expr) else {
+            return Ok(TreeNodeRecursion::Continue);
+        };
+        if !values.iter().any(|(existing, _)| existing == &path) {
+            values.push((path, expr.clone()));
+        }
+        Ok(TreeNodeRecursion::Jump)
+    })
+    .expect("collecting referenced values is infallible");
+    values
+}
+
+/// Row level evaluation of the data validation rules of a table.
+///
+/// Where [`DataValidationExec`] fails on the first invalid batch, this reports which rows of a
+/// batch violate the rules, so writers can route them elsewhere.
+#[derive(Debug)]
+pub(crate) struct RowValidator {
+    check_expression: Arc<dyn PhysicalExpr>,
+}
+
+impl RowValidator {
+    /// Create a validator for batches of the given `schema`, returns `None` when the table has
+    /// no validation rules.
+    pub(crate) fn try_new(
+        session: &dyn Session,
+        schema: SchemaRef,
+        table_configuration: &TableConfiguration,
+    ) -> Result<Option<Self>> {
+        let input: Arc<dyn ExecutionPlan> = Arc::new(EmptyExec::new(schema.clone()));
+        let predicates = validation_predicates(session, input, table_configuration)?
+            .into_iter()
+            .map(Expr::unalias)
+            .collect();
+        let Some(validation_expr) = conjunction(simplify_predicates(predicates)?) else {
+            return Ok(None);
+        };
+        let df_schema = DFSchema::try_from(schema)?;
+        Ok(Some(Self {
+            check_expression: simplify_expr(session, df_schema.into(), validation_expr)?,
+        }))
+    }
+
+    /// Mask of the rows passing validation, rows evaluating to `NULL` are invalid.
+    pub(crate) fn valid_rows(&self, batch: &RecordBatch) -> Result<BooleanArray> {
+        match self.check_expression.evaluate(batch)? {
+            ColumnarValue::Array(array) => Ok(array
+                .as_boolean()
+                .iter()
+                .map(|valid| Some(valid == Some(true)))
+                .collect()),
+            ColumnarValue::Scalar(value) => Ok(BooleanArray::from(vec![
+                matches!(
+                    value,
+                    ScalarValue::Boolean(Some(true))
+                );
+                batch.num_rows()
+            ])),
+        }
+    }
+
+    /// Human readable description of the validation rules
+    pub(crate) fn description(&self) -> String {
+        self.check_expression.to_string()
+    }
+}
+
 impl DisplayAs for DataValidationExec {
     fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match t {
@@ -316,6 +709,7 @@ impl ExecutionPlan for DataValidationExec {
         Ok(Arc::new(Self {
             input: children.remove(0),
             check_expression: Arc::clone(&self.check_expression),
+            checks: Arc::clone(&self.checks),
         }))
     }
 
@@ -324,11 +718,14 @@ impl ExecutionPlan for DataValidationExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        Ok(Box::pin(DataValidationStream::new(
-            self.input.execute(partition, context)?,
-            self.input.schema(),
-            Arc::clone(&self.check_expression),
-        )))
+        Ok(Box::pin(
+            DataValidationStream::new(
+                self.input.execute(partition, context)?,
+                self.input.schema(),
+                Arc::clone(&self.check_expression),
+            )
+            .with_checks(Arc::clone(&self.checks)),
+        ))
     }
 
     fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
@@ -352,6 +749,7 @@ impl ExecutionPlan for DataValidationExec {
             Ok(Some(Arc::new(Self {
                 input: repartitioned,
                 check_expression: Arc::clone(&self.check_expression),
+                checks: Arc::clone(&self.checks),
             })))
         } else {
             Ok(None)
@@ -367,6 +765,7 @@ impl ExecutionPlan for DataValidationExec {
         Some(Arc::new(Self {
             input: input_with_fetch,
             check_expression: Arc::clone(&self.check_expression),
+            checks: Arc::clone(&self.checks),
         }))
     }
 
@@ -391,6 +790,9 @@ pin_project! {
         // The expression to use for checking data validity
         check_expression: Arc<dyn PhysicalExpr>,
 
+        // The individual checks, used to describe violations
+        checks: Arc<Vec<ValidationCheck>>,
+
         // The schema of the output stream
         schema: SchemaRef,
 
@@ -409,10 +811,17 @@ impl<S> DataValidationStream<S> {
     ) -> DataValidationStream<S> {
         DataValidationStream {
             check_expression,
+            checks: Arc::new(Vec::new()),
             schema,
             stream,
         }
     }
+
+    /// Describe violations using the individual `checks` of the check expression
+    fn with_checks(mut self, checks: Arc<Vec<ValidationCheck>>) -> Self {
+        self.checks = checks;
+        self
+    }
 }
 
 impl<S> Stream for DataValidationStream<S>
@@ -437,10 +846,17 @@ where
                             let invalid_slice =
                                 invalid_data.slice(0, invalid_data.num_rows().min(5));
                             let preview = pretty_format_batches(&[invalid_slice])?;
+                            let mut violation = String::new();
+                            for check in this.checks.iter() {
+                                if let Some(description) = check.describe_violation(&batch)? {
+                                    violation = format!("\n{description}\n");
+                                    break;
+                                }
+                            }
                             return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
                                 DeltaTableError::InvalidData {
                                     message: format!(
-                                        "Invalid data found: {invalid_count} rows failed validation check. \nPreview of invalid data:\n\n{preview}"
+                                        "Invalid data found: {invalid_count} rows failed validation check. {violation}\nPreview of invalid data:\n\n{preview}"
                                     ),
                                 },
                             )))));
@@ -1040,4 +1456,107 @@ mod tests {
 
         Ok(())
     }
+
+    fn nested_test_batch() -> RecordBatch {
+        use arrow::datatypes::Fields;
+        use arrow_array::{ListArray, StructArray, types::Int32Type};
+
+        let struct_fields = Fields::from(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]);
+        let nested = StructArray::new(
+            struct_fields.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(-3)])),
+                Arc::new(Int32Array::from(vec![Some(1), None, None])),
+            ],
+            Some(vec![true, false, true].into()),
+        );
+        let items = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1)]),
+            None,
+            Some(vec![Some(2), None]),
+        ]);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("s", DataType::Struct(struct_fields), true),
+            Field::new("items", items.data_type().clone(), true),
+        ]));
+        RecordBatch::try_new(schema, vec![Arc::new(nested), Arc::new(items)]).unwrap()
+    }
+
+    async fn validate_nested(predicates: Vec<Expr>) -> Result<Vec<RecordBatch>> {
+        let batch = nested_test_batch();
+        let ctx = SessionContext::new();
+        let memory_exec = get_memory_exec(&ctx.state(), batch.schema(), vec![batch]).await;
+        let validated_exec =
+            DataValidationExec::try_new_with_predicates(&ctx.state(), memory_exec, predicates)?;
+        collect(validated_exec, ctx.task_ctx()).await
+    }
+
+    #[tokio::test]
+    async fn test_validation_nested_not_null() -> Result<()> {
+        use delta_kernel::schema::{ArrayType, StructField};
+
+        let struct_type = |b_nullable: bool| {
+            DeltaDataType::Struct(Box::new(
+                StructType::try_new(vec![
+                    StructField::nullable("a", DeltaDataType::INTEGER),
+                    StructField::new("b", DeltaDataType::INTEGER, b_nullable),
+                ])
+                .unwrap(),
+            ))
+        };
+        let schema = |b_nullable: bool, contains_null: bool| {
+            StructType::try_new(vec![
+                StructField::nullable("s", struct_type(b_nullable)),
+                StructField::nullable(
+                    "items",
+                    ArrayType::new(DeltaDataType::INTEGER, contains_null),
+                ),
+            ])
+            .unwrap()
+        };
+
+        // a null struct does not require its fields to be set
+        assert!(
+            validate_nested(nullability_predicates(&schema(true, true)))
+                .await
+                .is_ok()
+        );
+
+        let err = validate_nested(nullability_predicates(&schema(false, true)))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid data found: 1 rows failed validation check."));
+        assert!(err.contains("NOT NULL constraint on s.b violated by row with values:"));
+        assert!(err.contains(" - s.b : NULL"));
+
+        let err = validate_nested(nullability_predicates(&schema(true, false)))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("NOT NULL constraint on items.element violated"));
+        assert!(err.contains(" - items : [2, NULL]"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validation_nested_check_constraint() -> Result<()> {
+        let batch = nested_test_batch();
+        let ctx = SessionContext::new();
+        let df_schema = DFSchema::try_from(batch.schema())?;
+        let constraint = Constraint::new("positive", "s IS NULL OR s.a > 0");
+        let predicates = constraints_to_exprs(&ctx.state(), &df_schema, [&constraint])?;
+
+        let err = validate_nested(predicates).await.unwrap_err().to_string();
+        assert!(err.contains(
+            "CHECK constraint positive (s IS NULL OR s.a > 0) violated by row with values:"
+        ));
+        assert!(err.contains(" - s.a : -3"));
+
+        Ok(())
+    }
 }
@@ -433,6 +433,23 @@ impl MatchedFilesScan {
     }
 }
 
+/// Kernel predicate to skip files which cannot contain data matching a predicate.
+///
+/// Terms that cannot be translated to kernel predicates are dropped, so the
+/// resulting predicate never excludes files with matching data. This is used
+/// to load only the files a DML operation may touch into snapshots which were
+/// loaded without files.
+pub(crate) fn file_skipping_predicate(predicate: &Expr) -> Result<Arc<Predicate>> {
+    let skipping_pred = simplify_predicates(split_conjunction_owned(predicate.clone()))?;
+    Ok(to_skipping_predicate(&skipping_pred))
+}
+
+fn to_skipping_predicate(terms: &[Expr]) -> Arc<Predicate> {
+    Arc::new(Predicate::and_from(
+        terms.iter().flat_map(|p| to_delta_predicate(p).ok()),
+    ))
+}
+
 /// Create a table scan plan for reading all data from
 /// all files which contain any data matching a predicate.
 ///
@@ -477,11 +494,7 @@ pub(crate) async fn scan_files_where_matches(
     // This is a best effort predicate and downstream code needs to also
     // apply the explicit file selection so we can ignore errors in the
     // conversion.
-    let delta_predicate = Arc::new(Predicate::and_from(
-        skipping_pred
-            .iter()
-            .flat_map(|p| to_delta_predicate(p).ok()),
-    ));
+    let delta_predicate = to_skipping_predicate(&skipping_pred);
 
     let predicate = conjunction(skipping_pred.clone()).unwrap_or(lit(true));
 
@@ -71,7 +71,8 @@ pub use self::table_provider::next::DeltaScan as DeltaScanNext;
 pub(crate) use self::utils::*;
 pub use cdf::scan::DeltaCdfTableProvider;
 pub(crate) use data_validation::{
-    DataValidationExec, constraints_to_exprs, generated_columns_to_exprs, validation_predicates,
+    DataValidationExec, RowValidator, constraints_to_exprs, generated_columns_to_exprs,
+    validation_predicates,
 };
 pub(crate) use find_files::*;
 pub use table_provider::{
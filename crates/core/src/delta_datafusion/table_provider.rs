@@ -1,7 +1,7 @@
 use std::any::Any;
 use std::borrow::Cow;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use arrow::array::BooleanArray;
 use arrow::compute::filter_record_batch;
@@ -28,6 +28,7 @@ use datafusion::logical_expr::simplify::SimplifyContext;
 use datafusion::logical_expr::utils::split_conjunction;
 use datafusion::logical_expr::{BinaryExpr, LogicalPlan, Operator};
 use datafusion::optimizer::simplify_expressions::ExprSimplifier;
+use datafusion::physical_expr::expressions::{col as physical_col, in_list, lit as physical_lit};
 use datafusion::physical_optimizer::pruning::PruningPredicate;
 use datafusion::physical_plan::filter_pushdown::{FilterDescription, FilterPushdownPhase};
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
@@ -47,6 +48,7 @@ use futures::TryStreamExt as _;
 use futures::future::BoxFuture;
 use object_store::ObjectMeta;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 use url::Url;
 use uuid::Uuid;
 
@@ -54,13 +56,17 @@ use crate::delta_datafusion::engine::AsObjectStoreUrl;
 
 use crate::delta_datafusion::table_provider::next::SnapshotWrapper;
 use crate::delta_datafusion::{
-    DataFusionMixins as _, FindFilesExprProperties, LogDataHandler, get_null_of_arrow_type,
-    register_store, to_correct_scalar_value,
+    DataFusionMixins as _, FindFilesExprProperties, LogDataHandler, create_session,
+    get_null_of_arrow_type, register_store, to_correct_scalar_value,
 };
 use crate::kernel::transaction::PROTOCOL;
-use crate::kernel::{Add, EagerSnapshot, Snapshot};
+use crate::kernel::{Add, EagerSnapshot, Snapshot, StructTypeExt as _};
 use crate::logstore::LogStore;
+use crate::operations::write::column_defaults::{cd_is_enabled, column_default_exprs};
 use crate::protocol::SaveMode;
+use crate::protocol::partition_stats::{
+    PartitionStatsPruning, partition_stats_enabled, read_partition_stats,
+};
 use crate::table::normalize_table_url;
 use crate::{DeltaResult, DeltaTable, DeltaTableError, logstore::LogStoreRef};
 
@@ -83,6 +89,8 @@ pub struct DeltaScanConfigBuilder {
     pub(super) wrap_partition_values: Option<bool>,
     /// Whether to push down filter in end result or just prune the files
     pub(super) enable_parquet_pushdown: bool,
+    /// Whether to collect exact column bounds to answer aggregates from statistics
+    pub(super) enable_aggregate_statistics: bool,
     /// Schema to scan table with
     pub(super) schema: Option<SchemaRef>,
 }
@@ -94,6 +102,7 @@ impl Default for DeltaScanConfigBuilder {
             file_column_name: None,
             wrap_partition_values: None,
             enable_parquet_pushdown: true,
+            enable_aggregate_statistics: false,
             schema: None,
         }
     }
@@ -133,6 +142,13 @@ impl DeltaScanConfigBuilder {
         self
     }
 
+    /// Collect the exact bounds of the scanned columns from the file statistics
+    /// When enabled, aggregates like `MIN` and `MAX` may be answered without reading data
+    pub fn with_aggregate_statistics(mut self, enable: bool) -> Self {
+        self.enable_aggregate_statistics = enable;
+        self
+    }
+
     /// Use the provided [SchemaRef] for the [DeltaScan]
     pub fn with_schema(mut self, schema: SchemaRef) -> Self {
         self.schema = Some(schema);
@@ -179,6 +195,7 @@ impl DeltaScanConfigBuilder {
             file_column_name,
             wrap_partition_values: self.wrap_partition_values.unwrap_or(true),
             enable_parquet_pushdown: self.enable_parquet_pushdown,
+            enable_aggregate_statistics: self.enable_aggregate_statistics,
             schema: self.schema.clone(),
             schema_force_view_types: true,
         })
@@ -194,6 +211,10 @@ pub struct DeltaScanConfig {
     pub wrap_partition_values: bool,
     /// Allow pushdown of the scan filter, defaults to true
     pub enable_parquet_pushdown: bool,
+    /// Collect the exact bounds of the scanned columns from the file statistics, so
+    /// aggregates like `MIN` and `MAX` can be answered without reading data, defaults to false
+    #[serde(default)]
+    pub enable_aggregate_statistics: bool,
     /// If true, parquet reader will read columns of `Utf8`/`Utf8Large`
     /// with Utf8View, and `Binary`/`BinaryLarge` with `BinaryView`
     pub schema_force_view_types: bool,
@@ -214,6 +235,7 @@ impl DeltaScanConfig {
             file_column_name: None,
             wrap_partition_values: true,
             enable_parquet_pushdown: true,
+            enable_aggregate_statistics: false,
             schema_force_view_types: true,
             schema: None,
         }
@@ -225,6 +247,7 @@ impl DeltaScanConfig {
             file_column_name: None,
             wrap_partition_values: true,
             enable_parquet_pushdown: config_options.execution.parquet.pushdown_filters,
+            enable_aggregate_statistics: false,
             schema_force_view_types: config_options.execution.parquet.schema_force_view_types,
             schema: None,
         }
@@ -247,6 +270,12 @@ impl DeltaScanConfig {
         self
     }
 
+    /// Collect the exact bounds of the scanned columns from the file statistics
+    pub fn with_aggregate_statistics(mut self, enable: bool) -> Self {
+        self.enable_aggregate_statistics = enable;
+        self
+    }
+
     /// Use the provided [SchemaRef] for the [DeltaScan]
     ///
     /// This schema will be used when reading data from the underlying files.
@@ -403,8 +432,24 @@ impl<'a> DeltaScanBuilder<'a> {
                     let num_containers = self.snapshot.num_containers();
 
                     let files_to_prune = if let Some(predicate) = &logical_filter {
-                        let pruning_predicate =
+                        let mut pruning_predicate =
                             PruningPredicate::try_new(predicate.clone(), logical_schema.clone())?;
+                        if let Some(partition_filter) = partition_stats_filter(
+                            self.snapshot,
+                            self.log_store.as_ref(),
+                            &pruning_predicate,
+                            &logical_schema,
+                        )
+                        .await
+                        {
+                            pruning_predicate = PruningPredicate::try_new(
+                                datafusion::physical_expr::conjunction([
+                                    predicate.clone(),
+                                    partition_filter,
+                                ]),
+                                logical_schema.clone(),
+                            )?;
+                        }
                         pruning_predicate.prune(self.snapshot)?
                     } else {
                         vec![true; num_containers]
@@ -672,6 +717,7 @@ pub struct TableProviderBuilder {
     snapshot: Option<SnapshotWrapper>,
     file_column: Option<String>,
     table_version: Option<Version>,
+    aggregate_statistics: bool,
     /// Predicates used only for file skipping in kernel log replay
     file_skipping_predicates: Option<Vec<Expr>>,
 }
@@ -689,6 +735,7 @@ impl TableProviderBuilder {
             snapshot: None,
             file_column: None,
             table_version: None,
+            aggregate_statistics: false,
             file_skipping_predicates: None,
         }
     }
@@ -726,6 +773,15 @@ impl TableProviderBuilder {
         self
     }
 
+    /// Collect the exact bounds of the scanned columns from the file statistics.
+    ///
+    /// This allows aggregates like `MIN` and `MAX` to be answered from statistics without
+    /// reading any data, at the cost of parsing the statistics of every scanned column.
+    pub fn with_aggregate_statistics(mut self, enable: bool) -> Self {
+        self.aggregate_statistics = enable;
+        self
+    }
+
     /// Add predicates applied only during file skipping.
     ///
     /// There are cases where we may want to skip files that definitely do
@@ -744,7 +800,8 @@ impl TableProviderBuilder {
     }
 
     pub async fn build(self) -> Result<next::DeltaScan> {
-        let mut config = DeltaScanConfig::new();
+        let mut config =
+            DeltaScanConfig::new().with_aggregate_statistics(self.aggregate_statistics);
         if let Some(file_column) = self.file_column {
             config = config.with_file_column_name(file_column);
         }
@@ -840,6 +897,7 @@ pub struct DeltaTableProvider {
     config: DeltaScanConfig,
     schema: Arc<Schema>,
     files: Option<Vec<Add>>,
+    column_defaults: OnceLock<HashMap<String, Expr>>,
 }
 
 impl DeltaTableProvider {
@@ -855,6 +913,7 @@ impl DeltaTableProvider {
             log_store,
             config,
             files: None,
+            column_defaults: OnceLock::new(),
         })
     }
 
@@ -863,6 +922,40 @@ impl DeltaTableProvider {
         self.files = Some(files);
         self
     }
+
+    /// Parse the default values declared by the columns of the table.
+    ///
+    /// Defaults that cannot be parsed are logged and skipped, so they only affect inserts
+    /// relying on them and never reading the table.
+    fn resolve_column_defaults(&self) -> HashMap<String, Expr> {
+        if !cd_is_enabled(&self.snapshot) {
+            return HashMap::new();
+        }
+        let defaults = match self.snapshot.schema().get_column_defaults() {
+            Ok(defaults) if !defaults.is_empty() => defaults,
+            Ok(_) => return HashMap::new(),
+            Err(err) => {
+                warn!("Ignoring the column defaults of the table: {err}");
+                return HashMap::new();
+            }
+        };
+        let session = create_session().state();
+        defaults
+            .iter()
+            .filter_map(|default| {
+                match column_default_exprs(&session, std::slice::from_ref(default)) {
+                    Ok(mut exprs) => exprs.pop(),
+                    Err(err) => {
+                        warn!(
+                            "Ignoring the default value of column {}: {err}",
+                            default.get_name()
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -923,6 +1016,12 @@ impl TableProvider for DeltaTableProvider {
         self.snapshot.log_data().statistics()
     }
 
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.column_defaults
+            .get_or_init(|| self.resolve_column_defaults())
+            .get(column)
+    }
+
     /// Insert the data into the delta table
     /// Insert operation is only supported for Append and Overwrite
     /// Return the execution plan
@@ -1097,6 +1196,82 @@ impl ExecutionPlan for DeltaScan {
     }
 }
 
+/// A predicate on the partition columns selecting the partitions that may contain rows
+/// matching the predicate according to the partition statistics summary of the table.
+///
+/// Each partition column is restricted to the values it takes in the remaining partitions, so
+/// the partitions are pruned along with the file statistics in a single pass over the files of
+/// the snapshot. Columns that are not part of the scan schema, or take a null value in one of
+/// the remaining partitions, are not restricted.
+///
+/// Returns `None` if the table is not partitioned, has no summary, or the summary does not
+/// prune any partition.
+async fn partition_stats_filter(
+    snapshot: &EagerSnapshot,
+    log_store: &dyn LogStore,
+    pruning_predicate: &PruningPredicate,
+    schema: &SchemaRef,
+) -> Option<Arc<dyn PhysicalExpr>> {
+    if !partition_stats_enabled(snapshot) || snapshot.metadata().partition_columns().is_empty() {
+        return None;
+    }
+    let summary = match read_partition_stats(log_store, snapshot.version(), None).await {
+        Ok(Some(summary))
+            if &summary.partition_columns == snapshot.metadata().partition_columns() =>
+        {
+            summary
+        }
+        Ok(_) => return None,
+        Err(err) => {
+            warn!("ignoring partition statistics: {err}");
+            return None;
+        }
+    };
+    let keep = pruning_predicate
+        .prune(&PartitionStatsPruning::new(
+            &summary,
+            pruning_predicate.schema(),
+        ))
+        .ok()?;
+    if keep.iter().all(|keep| *keep) {
+        return None;
+    }
+    let partitions: Vec<_> = summary
+        .partitions
+        .iter()
+        .zip(keep)
+        .filter_map(|(partition, keep)| keep.then_some(partition))
+        .collect();
+    if partitions.is_empty() {
+        return Some(physical_lit(false));
+    }
+
+    let filters: Vec<_> = summary
+        .partition_columns
+        .iter()
+        .filter_map(|column| {
+            let (_, field) = schema.column_with_name(column)?;
+            let mut values = HashSet::new();
+            for partition in &partitions {
+                let value = partition.partition_values.get(column).cloned().flatten()?;
+                let value =
+                    to_correct_scalar_value(&serde_json::Value::String(value), field.data_type())
+                        .ok()
+                        .flatten()?;
+                values.insert(value);
+            }
+            in_list(
+                physical_col(column, schema).ok()?,
+                values.into_iter().map(physical_lit).collect(),
+                &false,
+                schema,
+            )
+            .ok()
+        })
+        .collect();
+    (!filters.is_empty()).then(|| datafusion::physical_expr::conjunction(filters))
+}
+
 /// The logical schema for a Deltatable is different from the protocol level schema since partition
 /// columns must appear at the end of the schema. This is to align with how partition are handled
 /// at the physical level
@@ -1274,6 +1449,7 @@ mod tests {
     use datafusion::datasource::listing::PartitionedFile;
     use datafusion::execution::context::SessionState;
     use datafusion::logical_expr::dml::InsertOp;
+    use delta_kernel::schema::MetadataValue;
     use object_store::path::Path;
     use std::sync::Arc;
 
@@ -1360,6 +1536,101 @@ mod tests {
         assert!(result_plan.metrics().is_some());
     }
 
+    fn provider_for(table: &DeltaTable) -> DeltaTableProvider {
+        let snapshot = table.snapshot().unwrap().snapshot().clone();
+        let scan_config = DeltaScanConfigBuilder::new().build(&snapshot).unwrap();
+        DeltaTableProvider::try_new(snapshot, table.log_store(), scan_config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_column_default_from_add_columns() {
+        let table = create_test_table().await.unwrap();
+        let table = table
+            .add_columns()
+            .with_fields([
+                StructField::nullable("status", DataType::STRING)
+                    .with_metadata([("CURRENT_DEFAULT", "'open'")]),
+                StructField::nullable("score", DataType::LONG)
+                    .with_metadata([("CURRENT_DEFAULT", "missing_column")]),
+            ])
+            .await
+            .unwrap();
+
+        let provider = provider_for(&table);
+        let default = provider.get_column_default("status").unwrap();
+        assert!(default.to_string().contains("open"));
+        // A default that cannot be parsed does not break the provider
+        assert_eq!(provider.get_column_default("score"), None);
+        assert_eq!(provider.get_column_default("id"), None);
+    }
+
+    #[tokio::test]
+    async fn test_column_default_from_update_field_metadata() {
+        let table = create_test_table().await.unwrap();
+        assert_eq!(provider_for(&table).get_column_default("value"), None);
+
+        let table = table
+            .update_field_metadata()
+            .with_field_name("value")
+            .with_metadata(std::collections::HashMap::from([(
+                "CURRENT_DEFAULT".to_string(),
+                MetadataValue::String("'unknown'".to_string()),
+            )]))
+            .await
+            .unwrap();
+
+        let provider = provider_for(&table);
+        let default = provider.get_column_default("value").unwrap();
+        assert!(default.to_string().contains("unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_prunes_partitions_from_partition_stats() -> DeltaResult<()> {
+        use crate::TableProperty;
+        use crate::writer::test_utils::{get_delta_schema, get_record_batch_from_rows};
+        use datafusion::prelude::{SessionContext, col, lit};
+
+        let mut table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_partition_columns(["modified"])
+            .with_configuration_property(TableProperty::PartitionStatsEnabled, Some("true"))
+            .await?;
+        for row in [
+            ("A", 1, "2021-02-01"),
+            ("B", 2, "2021-02-01"),
+            ("A", 10, "2021-02-02"),
+            ("B", 3, "2021-02-02"),
+        ] {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[row])])
+                .await?;
+        }
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let scan = DeltaScanBuilder::new(table.snapshot()?.snapshot(), table.log_store(), &state)
+            .with_filter(Some(col("value").gt(lit(5))))
+            .build()
+            .await?;
+        let metrics = scan.metrics().unwrap();
+        let count = |name: &str| metrics.sum_by_name(name).map(|m| m.as_usize());
+        assert_eq!(count("files_scanned"), Some(1));
+        assert_eq!(count("files_pruned"), Some(3));
+
+        // a predicate no partition can satisfy prunes all files
+        let scan = DeltaScanBuilder::new(table.snapshot()?.snapshot(), table.log_store(), &state)
+            .with_filter(Some(col("value").gt(lit(100))))
+            .build()
+            .await?;
+        let metrics = scan.metrics().unwrap();
+        assert_eq!(
+            metrics.sum_by_name("files_scanned").map(|m| m.as_usize()),
+            Some(0)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_partitioned_file_from_action() {
         let mut partition_values = std::collections::HashMap::new();
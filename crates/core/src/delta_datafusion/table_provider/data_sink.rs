@@ -118,7 +118,7 @@ impl DataSink for DeltaDataSink {
         let config = WriterConfig::new(
             self.snapshot.read_schema(),
             partition_columns.clone(),
-            None,
+            table_props.parquet_writer_settings().writer_properties(),
             Some(table_props.target_file_size().get() as usize),
             None,
             table_props.num_indexed_cols(),
@@ -126,7 +126,8 @@ impl DataSink for DeltaDataSink {
                 .data_skipping_stats_columns
                 .as_ref()
                 .map(|c| c.iter().map(|c| c.to_string()).collect_vec()),
-        );
+        )
+        .with_bloom_filter_columns(&table_props.bloom_filter_columns());
 
         let mut writer = DeltaWriter::new(object_store, config);
         let mut total_rows = 0u64;
@@ -205,7 +205,7 @@ impl TableProvider for DeltaScan {
                         engine.clone(),
                         esn.snapshot().version() as u64,
                         Box::new(files.to_vec().into_iter()),
-                        None,
+                        esn.files_predicate(),
                     )
                 } else {
                     scan_plan.scan.scan_metadata(engine.clone())
@@ -358,4 +358,107 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_scan_prunes_with_bloom_filters() -> TestResult {
+        use arrow_array::{Int32Array, RecordBatch, StringArray};
+        use arrow_schema::{DataType as ArrowDataType, Field, Schema};
+        use datafusion::physical_plan::displayable;
+        use parquet::file::properties::WriterProperties;
+
+        use crate::kernel::{DataType, StructField};
+        use crate::{DeltaTable, DeltaTableBuilder};
+
+        let tmp_dir = tempfile::tempdir()?;
+        let table_url = url::Url::from_directory_path(tmp_dir.path()).unwrap();
+        let table = DeltaTableBuilder::from_url(table_url)?
+            .build()?
+            .create()
+            .with_columns([
+                StructField::new("id", DataType::STRING, false),
+                StructField::new("value", DataType::INTEGER, false),
+            ])
+            .with_configuration([
+                ("delta.bloomFilter.id.enabled", Some("true")),
+                ("delta.bloomFilter.id.fpp", Some("0.01")),
+                ("delta.bloomFilter.id.ndv", Some("100")),
+            ])
+            .await?;
+
+        // ids are spread over all row groups, so min/max statistics cannot prune them
+        let values: Vec<i32> = (0..1000).collect();
+        let ids: Vec<String> = values
+            .iter()
+            .map(|i| format!("{:04}", i * 37 % 1000))
+            .collect();
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("id", ArrowDataType::Utf8, false),
+                Field::new("value", ArrowDataType::Int32, false),
+            ])),
+            vec![
+                Arc::new(StringArray::from(ids)),
+                Arc::new(Int32Array::from(values)),
+            ],
+        )?;
+        let table: DeltaTable = table
+            .write(vec![batch])
+            .with_writer_properties(
+                WriterProperties::builder()
+                    .set_max_row_group_size(100)
+                    .build(),
+            )
+            .await?;
+
+        let session = Arc::new(create_session().into_inner());
+        let state = session.state_ref().read().clone();
+        let snapshot = Snapshot::try_new(&table.log_store(), Default::default(), None).await?;
+        // filter pushdown is disabled by default in the session
+        let provider = DeltaScan::new(snapshot, DeltaScanConfig::new_from_session(&state))?;
+        session.register_table("delta_table", Arc::new(provider))?;
+
+        let scan = async |sql: &str| -> TestResult<_> {
+            let df = session.sql(sql).await?;
+            let plan = df.create_physical_plan().await?;
+            let batches = collect_partitioned(plan.clone(), session.task_ctx())
+                .await?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let mut visitor = DeltaScanVisitor::default();
+            visit_execution_plan(plan.as_ref(), &mut visitor).unwrap();
+            let display = displayable(plan.as_ref()).indent(true).to_string();
+            Ok((batches, visitor.total_bytes_scanned.unwrap(), display))
+        };
+
+        let (_, full_bytes, _) = scan("SELECT * FROM delta_table").await?;
+
+        let (batches, bytes, display) =
+            scan("SELECT value FROM delta_table WHERE id = '0037'").await?;
+        let expected = vec![
+            "+-------+",
+            "| value |",
+            "+-------+",
+            "| 1     |",
+            "+-------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &batches);
+        assert!(display.contains("predicate="), "{display}");
+        assert!(bytes < full_bytes, "{bytes} >= {full_bytes}");
+
+        let (batches, bytes, _) =
+            scan("SELECT value FROM delta_table WHERE id IN ('0037', '0074')").await?;
+        let expected = vec![
+            "+-------+",
+            "| value |",
+            "+-------+",
+            "| 1     |",
+            "| 2     |",
+            "+-------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &batches);
+        assert!(bytes < full_bytes, "{bytes} >= {full_bytes}");
+
+        Ok(())
+    }
 }
@@ -609,7 +609,7 @@ mod tests {
     use crate::{
         assert_batches_sorted_eq,
         delta_datafusion::{session::create_session, table_provider::next::FILE_ID_COLUMN_DEFAULT},
-        test_utils::{TestResult, open_fs_path},
+        test_utils::{TestResult, TestTables, open_fs_path},
     };
 
     #[tokio::test]
@@ -927,18 +927,52 @@ mod tests {
         let provider = table.table_provider().await?;
         let session = Arc::new(create_session().into_inner());
 
-        // for scans without prodicates, we gather only top level statistic
-        // and omit collecting column level statistics
+        // for scans without prodicates, we only gather top level statistics
         let scan = provider.scan(&session.state(), None, &[], None).await?;
         let statistics = scan.partition_statistics(None)?;
         assert_eq!(statistics.num_rows, Precision::Exact(5));
         assert_eq!(statistics.total_byte_size, Precision::Inexact(3240));
-        for col_stat in statistics.column_statistics.iter() {
+        for col_stat in &statistics.column_statistics {
             assert_eq!(col_stat.null_count, Precision::Absent);
             assert_eq!(col_stat.min_value, Precision::Absent);
             assert_eq!(col_stat.max_value, Precision::Absent);
         }
 
+        // when answering aggregates from statistics, we additionally gather column level
+        // statistics for columns whose bounds are exact
+        let aggregate_provider = table
+            .table_provider()
+            .with_aggregate_statistics(true)
+            .await?;
+        let scan = aggregate_provider
+            .scan(&session.state(), None, &[], None)
+            .await?;
+        let statistics = scan.partition_statistics(None)?;
+        assert_eq!(statistics.num_rows, Precision::Exact(5));
+        for (col_stat, field) in statistics
+            .column_statistics
+            .iter()
+            .zip(provider.schema().fields())
+        {
+            match field.data_type() {
+                DataType::Boolean => {}
+                DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Date32 => {
+                    assert!(matches!(col_stat.null_count, Precision::Exact(_)));
+                    assert!(matches!(col_stat.min_value, Precision::Exact(_)));
+                    assert!(matches!(col_stat.max_value, Precision::Exact(_)));
+                }
+                _ => {
+                    assert_eq!(col_stat.null_count, Precision::Absent);
+                    assert_eq!(col_stat.min_value, Precision::Absent);
+                    assert_eq!(col_stat.max_value, Precision::Absent);
+                }
+            }
+        }
+
         // for scans with predicates, we gather full statistics
         let predicates = table
             .snapshot()?
@@ -1069,6 +1103,84 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_aggregates_from_statistics() -> TestResult {
+        let query = "SELECT count(*), min(value), max(value) FROM delta_table";
+        let scans_data = |plan: &Arc<dyn ExecutionPlan>| {
+            let plan = format!("{plan:?}");
+            plan.contains("DeltaScanExec") || plan.contains("DeltaScanMetaExec")
+        };
+
+        // Without deletion vectors the aggregates are answered from the file statistics
+        let table = TestTables::WithDvSmall
+            .table_builder()?
+            .with_version(0)
+            .load()
+            .await?;
+        let session = Arc::new(create_session().into_inner());
+        session.register_table(
+            "delta_table",
+            table
+                .table_provider()
+                .with_aggregate_statistics(true)
+                .await?,
+        )?;
+        let df = session.sql(query).await?;
+        assert!(!scans_data(&df.clone().create_physical_plan().await?));
+        let expected = vec![
+            "+----------+------------------------+------------------------+",
+            "| count(*) | min(delta_table.value) | max(delta_table.value) |",
+            "+----------+------------------------+------------------------+",
+            "| 10       | 0                      | 9                      |",
+            "+----------+------------------------+------------------------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &df.collect().await?);
+
+        // Column bounds are only collected when explicitly requested
+        let session = Arc::new(create_session().into_inner());
+        session.register_table("delta_table", table.table_provider().await?)?;
+        let df = session.sql(query).await?;
+        assert!(scans_data(&df.clone().create_physical_plan().await?));
+        assert_batches_sorted_eq!(&expected, &df.collect().await?);
+
+        // Deleted rows are still included in the statistics, so the data needs to be read
+        let table = TestTables::WithDvSmall.table_builder()?.load().await?;
+        let session = Arc::new(create_session().into_inner());
+        session.register_table(
+            "delta_table",
+            table
+                .table_provider()
+                .with_aggregate_statistics(true)
+                .await?,
+        )?;
+        let df = session.sql(query).await?;
+        assert!(scans_data(&df.clone().create_physical_plan().await?));
+        let expected = vec![
+            "+----------+------------------------+------------------------+",
+            "| count(*) | min(delta_table.value) | max(delta_table.value) |",
+            "+----------+------------------------+------------------------+",
+            "| 8        | 1                      | 8                      |",
+            "+----------+------------------------+------------------------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &df.collect().await?);
+
+        // Row counts account for deletion vectors without reading the data
+        let df = session
+            .sql("SELECT count(*) AS count FROM delta_table")
+            .await?;
+        assert!(!scans_data(&df.clone().create_physical_plan().await?));
+        let expected = vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 8     |",
+            "+-------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &df.collect().await?);
+
+        Ok(())
+    }
+
     // DV test helpers
     const DV_TABLE_PATH: &str = "../../dat/v0.0.3/reader_tests/generated/deletion_vectors/delta";
 
@@ -21,6 +21,7 @@ use dashmap::DashMap;
 use datafusion::common::HashMap;
 use datafusion::common::config::ConfigOptions;
 use datafusion::common::error::{DataFusionError, Result};
+use datafusion::common::stats::Precision;
 use datafusion::execution::{RecordBatchStream, SendableRecordBatchStream, TaskContext};
 use datafusion::physical_expr::EquivalenceProperties;
 use datafusion::physical_plan::execution_plan::{
@@ -123,6 +124,14 @@ impl DeltaScanMetaExec {
             properties,
         }
     }
+
+    /// Number of rows of a file that are not removed by its deletion vector
+    fn num_selected_rows(&self, file_id: &str, row_count: usize) -> usize {
+        match self.selection_vectors.get(file_id) {
+            Some(selection) => row_count - selection.iter().filter(|keep| !**keep).count(),
+            None => row_count,
+        }
+    }
 }
 
 impl ExecutionPlan for DeltaScanMetaExec {
@@ -202,8 +211,7 @@ impl ExecutionPlan for DeltaScanMetaExec {
     }
 
     fn statistics(&self) -> Result<Statistics> {
-        // self.input.partition_statistics(None)
-        Ok(Statistics::new_unknown(self.schema().as_ref()))
+        self.partition_statistics(None)
     }
 
     fn supports_limit_pushdown(&self) -> bool {
@@ -222,10 +230,21 @@ impl ExecutionPlan for DeltaScanMetaExec {
         None
     }
 
-    fn partition_statistics(&self, _: Option<usize>) -> Result<Statistics> {
-        // TODO: handle statistics conversion properly to leverage parquet plan statistics.
-        // self.input.partition_statistics(partition)
-        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        let files = match partition {
+            Some(partition) => self.input.get(partition).into_iter().collect_vec(),
+            None => self.input.iter().collect_vec(),
+        };
+        // Row counts are exact and deleted rows are known from the loaded deletion vectors,
+        // which allows answering e.g. `COUNT(*)` without executing the scan.
+        let num_rows = files
+            .into_iter()
+            .flatten()
+            .map(|(file_id, row_count)| self.num_selected_rows(file_id, *row_count))
+            .sum();
+        let mut statistics = Statistics::new_unknown(self.schema().as_ref());
+        statistics.num_rows = Precision::Exact(num_rows);
+        Ok(statistics)
     }
 
     fn gather_filters_for_pushdown(
@@ -85,7 +85,13 @@ pub(super) async fn execution_plan(
     engine: Arc<dyn Engine>,
     limit: Option<usize>,
 ) -> Result<Arc<dyn ExecutionPlan>> {
-    let (files, transforms, dvs, metrics) = replay_files(engine, &scan_plan, stream).await?;
+    let (files, transforms, dvs, metrics) = replay_files(
+        engine,
+        &scan_plan,
+        stream,
+        config.enable_aggregate_statistics,
+    )
+    .await?;
 
     let file_id_field = config.file_id_field();
     if scan_plan.is_metadata_only() {
@@ -136,13 +142,14 @@ async fn replay_files(
     engine: Arc<dyn Engine>,
     scan_plan: &KernelScanPlan,
     stream: ScanMetadataStream,
+    include_exact_bounds: bool,
 ) -> Result<(
     Vec<ScanFileContext>,
     HashMap<String, Arc<Expression>>,
     DashMap<String, Vec<bool>>,
     ExecutionPlanMetricsSet,
 )> {
-    let mut stream = ScanFileStream::new(engine, &scan_plan.scan, stream);
+    let mut stream = ScanFileStream::new(engine, &scan_plan.scan, stream, include_exact_bounds);
     let mut files = Vec::new();
     while let Some(file) = stream.try_next().await? {
         files.extend(file);
@@ -188,7 +195,12 @@ async fn get_data_scan_plan(
     // this is used to create a DataSourceExec plan for each store
     // To correlate the data with the original file, we add the file url as a partition value
     // This is required to apply the correct transform to the data in downstream processing.
-    let to_partitioned_file = |f: ScanFileContext| {
+    let to_partitioned_file = |mut f: ScanFileContext| {
+        // File statistics include the rows removed by deletion vectors, so they only bound
+        // the data read from the file.
+        if dvs.contains_key(f.file_url.as_str()) {
+            f.stats = f.stats.to_inexact();
+        }
         if let Some(part_stata) = &f.partitions {
             update_partition_stats(part_stata, &f.stats, &mut partition_stats)?;
         }
@@ -240,6 +252,7 @@ async fn get_data_scan_plan(
         limit,
         &file_id_field,
         predicate,
+        scan_plan.parquet_pushdown,
     )
     .await?;
 
@@ -305,6 +318,9 @@ async fn get_read_plan(
     limit: Option<usize>,
     file_id_field: &FieldRef,
     predicate: Option<&Expr>,
+    // Whether the predicate filters rows while decoding, in addition to pruning row groups
+    // and pages with statistics and bloom filters.
+    pushdown_filters: bool,
 ) -> Result<Arc<dyn ExecutionPlan>> {
     let mut plans = Vec::new();
 
@@ -343,7 +359,7 @@ async fn get_read_plan(
             let physical = logical2physical(pred, full_read_schema.as_ref());
             file_source = file_source
                 .with_predicate(physical)
-                .with_pushdown_filters(true);
+                .with_pushdown_filters(pushdown_filters);
         }
 
         let file_group: FileGroup = files.into_iter().map(|file| file.0).collect();
@@ -594,6 +610,7 @@ mod tests {
             None,
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -616,6 +633,7 @@ mod tests {
             Some(1),
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -641,6 +659,7 @@ mod tests {
             Some(1),
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -720,6 +739,7 @@ mod tests {
             None,
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -755,6 +775,7 @@ mod tests {
             None,
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -857,6 +878,7 @@ mod tests {
             None,
             &file_id_field,
             None,
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -926,6 +948,7 @@ mod tests {
             None,
             &file_id_field,
             Some(&predicate),
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -1002,6 +1025,7 @@ mod tests {
             None,
             &file_id_field,
             Some(&predicate),
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -1078,6 +1102,7 @@ mod tests {
             None,
             &file_id_field,
             Some(&predicate),
+            true,
         )
         .await?;
         let batches = collect(plan, session.task_ctx()).await?;
@@ -37,6 +37,7 @@ use crate::delta_datafusion::engine::{
 };
 use crate::delta_datafusion::table_provider::next::FILE_ID_COLUMN_DEFAULT;
 use crate::kernel::{Scan, Snapshot};
+use crate::table::config::TablePropertiesExt as _;
 
 /// Logical scan plan for Delta tables using Delta Kernel.
 ///
@@ -55,7 +56,7 @@ use crate::kernel::{Scan, Snapshot};
 ///
 /// Predicates are assigned to two levels:
 /// - Kernel scan: File-level skipping using table statistics (pushed to [`scan`])
-/// - Parquet scan: File/Row-level filtering within files ([`parquet_predicate`])
+/// - Parquet scan: Row group pruning and Row-level filtering within files ([`parquet_predicate`])
 #[derive(Clone, Debug)]
 pub(crate) struct KernelScanPlan {
     /// Wrapped kernel scan to produce logical file stream
@@ -71,6 +72,9 @@ pub(crate) struct KernelScanPlan {
     pub(crate) parquet_read_schema: SchemaRef,
     /// If set, indicates a predicate to apply at the Parquet scan level
     pub(crate) parquet_predicate: Option<Expr>,
+    /// Whether the parquet predicate also filters rows while decoding, otherwise it is
+    /// only used to prune row groups and pages with statistics and bloom filters
+    pub(crate) parquet_pushdown: bool,
     /// Predicate passed to delta kernel for file skipping,
     ///
     /// If this is configured, the predicates pushed into the scan will
@@ -212,6 +216,7 @@ impl KernelScanPlan {
             result_projection,
             parquet_read_schema,
             parquet_predicate,
+            parquet_pushdown: config.enable_parquet_pushdown,
             skipping_predicate,
         })
     }
@@ -406,18 +411,29 @@ pub(crate) fn supports_filters_pushdown(
         .as_deref()
         .unwrap_or(FILE_ID_COLUMN_DEFAULT);
 
-    // Parquet predicate pushdown is enabled only when we can safely apply it at read time.
-    // Deletion vectors require preserving row order for selection masks, and row tracking
-    // disables predicate pushdown in the read plan.
-    let parquet_pushdown_enabled = scan_config.enable_parquet_pushdown
-        && !config.is_feature_enabled(&TableFeature::RowTracking)
-        && !config.is_feature_enabled(&TableFeature::DeletionVectors);
+    let parquet_pushdown_enabled = parquet_predicate_enabled(config, scan_config);
     filter
         .iter()
         .map(|f| process_predicate(f, config, file_id_field, parquet_pushdown_enabled).pushdown)
         .collect()
 }
 
+/// Whether predicates are passed to the parquet scan.
+///
+/// Parquet predicates are enabled only when we can safely apply them at read time.
+/// Deletion vectors require preserving row order for selection masks, and row tracking
+/// disables predicate pushdown in the read plan.
+///
+/// Without filter pushdown, tables writing bloom filters still get a parquet predicate,
+/// which is then only used to prune row groups (see [`KernelScanPlan::parquet_pushdown`]).
+/// This turns point lookups on high cardinality columns into reads of single row groups.
+fn parquet_predicate_enabled(config: &TableConfiguration, scan_config: &DeltaScanConfig) -> bool {
+    !config.is_feature_enabled(&TableFeature::RowTracking)
+        && !config.is_feature_enabled(&TableFeature::DeletionVectors)
+        && (scan_config.enable_parquet_pushdown
+            || !config.table_properties().bloom_filter_columns().is_empty())
+}
+
 /// Process a list of filter expressions and determine which
 /// predicates can be pushed down to the parquet scan and which
 /// can be handled at the kernel scan level.
@@ -437,9 +453,7 @@ fn process_filters(
         .as_deref()
         .unwrap_or(FILE_ID_COLUMN_DEFAULT);
 
-    let parquet_pushdown_enabled = scan_config.enable_parquet_pushdown
-        && !config.is_feature_enabled(&TableFeature::RowTracking)
-        && !config.is_feature_enabled(&TableFeature::DeletionVectors);
+    let parquet_pushdown_enabled = parquet_predicate_enabled(config, scan_config);
     let (parquet, kernel): (Vec<_>, Vec<_>) = filters
         .iter()
         .map(|f| process_predicate(f, config, file_id_field, parquet_pushdown_enabled))
@@ -28,7 +28,7 @@ use delta_kernel::{
         Scan as KernelScan, ScanMetadata,
         state::{DvInfo, ScanFile},
     },
-    schema::{DataType, Schema, StructField},
+    schema::{DataType, PrimitiveType, Schema, StructField},
 };
 use futures::Stream;
 use itertools::Itertools;
@@ -37,7 +37,7 @@ use url::Url;
 
 use crate::{
     DeltaResult,
-    delta_datafusion::engine::to_datafusion_scalar,
+    delta_datafusion::{engine::to_datafusion_scalar, stats_bound_precision},
     kernel::{
         LogicalFileView, ReceiverStreamBuilder, Scan, StructDataExt,
         arrow::engine_ext::stats_schema, parse_stats_column_with_schema,
@@ -55,38 +55,67 @@ impl ReplayStats {
     }
 }
 
-/// Extracts column names referenced in the scan's physical predicate.
+/// Extracts the names of the columns whose statistics are needed for planning.
 ///
-/// Returns a set of column names that are actually used in predicates, which helps
-/// optimize statistics parsing to only process relevant columns.
-fn extract_predicate_columns(scan: &KernelScan) -> Option<HashSet<String>> {
-    scan.physical_predicate().map(|predicate| {
-        predicate
-            .references()
-            .into_iter()
-            .map(|col_name| col_name.to_string().trim_matches('`').to_string())
-            .collect()
-    })
+/// These are the columns referenced in the scan's physical predicate, used for pruning, and -
+/// if `include_exact_bounds` is set - the scanned columns whose statistics record exact bounds,
+/// used to answer aggregates like `MIN` and `MAX` from statistics. This helps optimize
+/// statistics parsing to only process relevant columns.
+fn extract_stats_columns(scan: &KernelScan, include_exact_bounds: bool) -> Option<HashSet<String>> {
+    let mut columns: HashSet<_> = scan
+        .physical_predicate()
+        .map(|predicate| {
+            predicate
+                .references()
+                .into_iter()
+                .map(|col_name| col_name.to_string().trim_matches('`').to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    if include_exact_bounds {
+        columns.extend(
+            scan.physical_schema()
+                .fields()
+                .filter(|field| has_exact_bounds(field.data_type()))
+                .map(|field| field.name().clone()),
+        );
+    }
+    (!columns.is_empty()).then_some(columns)
+}
+
+/// Whether the min / max statistics of the type are exact, see [`stats_bound_precision`]
+fn has_exact_bounds(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Primitive(
+            PrimitiveType::Boolean
+                | PrimitiveType::Byte
+                | PrimitiveType::Short
+                | PrimitiveType::Integer
+                | PrimitiveType::Long
+                | PrimitiveType::Date
+        )
+    )
 }
 
-/// Create a stats schema containing only columns needed for predicate evaluation
+/// Create a stats schema containing only columns needed for planning
 ///
 /// Returns:
-/// - If no predicate: Schema with just `numRecords`
-/// - If predicate exists: Schema with `numRecords` + stats for referenced columns only
+/// - If no stats columns: Schema with just `numRecords`
+/// - If stats columns exist: Schema with `numRecords` + stats for these columns only
 fn create_minimal_stats_schema(
     scan: &KernelScan,
-    predicate_columns: Option<&HashSet<String>>,
+    stats_columns: Option<&HashSet<String>>,
 ) -> Arc<Schema> {
-    match predicate_columns {
+    match stats_columns {
         None => {
-            // No predicate - only need numRecords for file statistics
+            // No stats columns - only need numRecords for file statistics
             Arc::new(
                 Schema::try_new(vec![StructField::nullable("numRecords", DataType::LONG)]).unwrap(),
             )
         }
         Some(cols) if cols.is_empty() => {
-            // Empty predicate columns - minimal schema
+            // Empty stats columns - minimal schema
             Arc::new(
                 Schema::try_new(vec![StructField::nullable("numRecords", DataType::LONG)]).unwrap(),
             )
@@ -101,7 +130,7 @@ fn create_minimal_stats_schema(
                 .collect();
 
             if filtered_fields.is_empty() {
-                // Predicate references only partition columns - minimal schema
+                // Stats columns are only partition columns - minimal schema
                 Arc::new(
                     Schema::try_new(vec![StructField::nullable("numRecords", DataType::LONG)])
                         .unwrap(),
@@ -137,6 +166,8 @@ pin_project! {
 
         kernel_scan: Arc<KernelScan>,
 
+        stats_columns: Option<HashSet<String>>,
+
         pub(crate) dv_stream: ReceiverStreamBuilder<(Url, Option<Vec<bool>>)>,
 
         #[pin]
@@ -145,13 +176,23 @@ pin_project! {
 }
 
 impl<S> ScanFileStream<S> {
-    pub(crate) fn new(engine: Arc<dyn Engine>, scan: &Arc<Scan>, stream: S) -> Self {
+    /// Create a new stream over the scan files of `scan`.
+    ///
+    /// If `include_exact_bounds` is set, the exact bounds of the scanned columns are parsed from
+    /// the file statistics even if the scan predicate does not reference them.
+    pub(crate) fn new(
+        engine: Arc<dyn Engine>,
+        scan: &Arc<Scan>,
+        stream: S,
+        include_exact_bounds: bool,
+    ) -> Self {
         Self {
             metrics: ReplayStats::new(),
             dv_stream: ReceiverStreamBuilder::<(Url, Option<Vec<bool>>)>::new(100),
             engine,
             table_root: scan.table_root().clone(),
             kernel_scan: scan.inner().clone(),
+            stats_columns: extract_stats_columns(scan.inner(), include_exact_bounds),
             stream,
         }
     }
@@ -204,14 +245,12 @@ where
                 let scan_files =
                     filter_record_batch(&batch, &BooleanArray::from(selection_vector))?;
 
-                // Extract columns referenced in predicate (if any)
-                let predicate_columns = extract_predicate_columns(this.kernel_scan.as_ref());
+                // Columns referenced in predicate or answerable from statistics (if any)
+                let stats_columns = this.stats_columns.as_ref();
 
-                // Create minimal stats schema based on predicate columns
-                let stats_schema = create_minimal_stats_schema(
-                    this.kernel_scan.as_ref(),
-                    predicate_columns.as_ref(),
-                );
+                // Create minimal stats schema based on stats columns
+                let stats_schema =
+                    create_minimal_stats_schema(this.kernel_scan.as_ref(), stats_columns);
 
                 // Parse statistics (will skip parsing for unreferenced columns)
                 let parsed_stats = parse_stats_column_with_schema(
@@ -220,12 +259,10 @@ where
                     stats_schema,
                 )?;
 
-                // TODO: do we need to mnake the stats inexact if deletion vectors are present?
-                let mut file_statistics = extract_file_statistics(
-                    this.kernel_scan,
-                    parsed_stats,
-                    predicate_columns.as_ref(),
-                );
+                // NOTE: row counts include rows removed by deletion vectors, statistics of files
+                // with deletion vectors are made inexact when planning the data scan.
+                let mut file_statistics =
+                    extract_file_statistics(this.kernel_scan, parsed_stats, stats_columns);
 
                 Poll::Ready(Some(Ok(ctx
                     .files
@@ -254,13 +291,13 @@ where
 /// Converts Delta Kernel's file statistics into DataFusion's [`Statistics`] format,
 /// which is used for query optimization and predicate pushdown. This function implements
 /// an important optimization: it only extracts statistics for columns that appear in
-/// predicates, avoiding expensive parsing for unused columns.
+/// predicates or may answer aggregates, avoiding expensive parsing for unused columns.
 ///
 /// # Arguments
 ///
 /// * `scan` - The kernel scan containing schema and predicate information
 /// * `parsed_stats` - RecordBatch containing parsed statistics for all files
-/// * `predicate_columns` - Optional set of columns referenced in predicates
+/// * `stats_columns` - Optional set of columns to extract statistics for
 ///
 /// # Returns
 ///
@@ -270,7 +307,7 @@ where
 fn extract_file_statistics(
     scan: &KernelScan,
     parsed_stats: RecordBatch,
-    predicate_columns: Option<&HashSet<String>>,
+    stats_columns: Option<&HashSet<String>>,
 ) -> HashMap<Url, (Statistics, Option<StructData>)> {
     (0..parsed_stats.num_rows())
         .map(move |idx| LogicalFileView::new(parsed_stats.clone(), idx))
@@ -290,12 +327,12 @@ fn extract_file_statistics(
                 .fields()
                 .map(|f| {
                     // Check if we should extract stats for this column
-                    let should_extract_stats = predicate_columns
+                    let should_extract_stats = stats_columns
                         .map(|cols| cols.contains(f.name()))
-                        .unwrap_or(false); // No predicate = no stats needed for pruning
+                        .unwrap_or(false); // No stats columns = no stats needed for planning
 
                     if !should_extract_stats {
-                        // Return unknown statistics for non-predicate columns
+                        // Return unknown statistics for other columns
                         return ColumnStatistics {
                             null_count: Precision::Absent,
                             max_value: Precision::Absent,
@@ -306,7 +343,7 @@ fn extract_file_statistics(
                         };
                     }
 
-                    // Extract statistics for predicate columns
+                    // Extract statistics for stats columns
                     let null_count = if let Some(field_index) =
                         null_counts.as_ref().and_then(|v| v.index_of(f.name()))
                     {
@@ -355,7 +392,7 @@ fn extract_precision(data: &Option<StructData>, name: impl AsRef<str>) -> Precis
     if let Some(field_index) = data.as_ref().and_then(|v| v.index_of(name.as_ref())) {
         data.as_ref()
             .map(|v| match to_datafusion_scalar(&v.values()[field_index]) {
-                Ok(df) => Precision::Exact(df),
+                Ok(df) => stats_bound_precision(df),
                 _ => Precision::Absent,
             })
             .unwrap_or_default()
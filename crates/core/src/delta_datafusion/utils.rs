@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
 use datafusion::common::DFSchemaRef;
+use datafusion::common::scalar::ScalarValue;
+use datafusion::common::stats::Precision;
 use datafusion::common::tree_node::{Transformed, TreeNode as _};
 use datafusion::error::{DataFusionError, Result};
 use datafusion::logical_expr::simplify::SimplifyContext;
@@ -164,6 +166,26 @@ pub(crate) fn coerce_predicate_literals(expr: Expr, schema: &DFSchema) -> Result
     .map(|transformed| transformed.data)
 }
 
+/// Precision of a min / max value recorded in the file statistics of the delta log.
+///
+/// Writers truncate string and timestamp bounds, serialize decimals as floating point numbers
+/// and drop NaNs from floating point bounds, so these only bound the actual values. For all
+/// other types the statistics record the exact minimum / maximum of the column, which allows
+/// answering aggregates like `MIN` and `MAX` without reading the data files.
+pub(crate) fn stats_bound_precision(value: ScalarValue) -> Precision<ScalarValue> {
+    match value {
+        value if value.is_null() => Precision::Absent,
+        ScalarValue::Boolean(_)
+        | ScalarValue::Int8(_)
+        | ScalarValue::Int16(_)
+        | ScalarValue::Int32(_)
+        | ScalarValue::Int64(_)
+        | ScalarValue::Date32(_)
+        | ScalarValue::Date64(_) => Precision::Exact(value),
+        value => Precision::Inexact(value),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
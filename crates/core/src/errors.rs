@@ -195,6 +195,9 @@ pub enum DeltaTableError {
 
     #[error("No starting version or timestamp provided for CDC")]
     NoStartingVersionOrTimestamp,
+
+    #[error("Version checksum of version {version} does not match the table state: {details}")]
+    ChecksumMismatch { version: i64, details: String },
 }
 
 impl From<object_store::path::Error> for DeltaTableError {
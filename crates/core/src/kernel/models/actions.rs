@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::TableProperty;
 use crate::kernel::{DeltaResult, error::Error};
 use crate::kernel::{StructType, StructTypeExt};
+use crate::table::config::is_column_property;
 
 pub use delta_kernel::actions::{Metadata, Protocol};
 
@@ -406,6 +407,7 @@ impl ProtocolInner {
     pub fn apply_column_metadata_to_protocol(mut self, schema: &StructType) -> DeltaResult<Self> {
         let generated_cols = schema.get_generated_columns()?;
         let invariants = schema.get_invariants()?;
+        let column_defaults = schema.get_column_defaults()?;
         let contains_timestamp_ntz = self.contains_timestampntz(schema.fields());
 
         if contains_timestamp_ntz {
@@ -420,6 +422,20 @@ impl ProtocolInner {
             self = self.enable_invariants()
         }
 
+        if let Some(column) = column_defaults
+            .iter()
+            .find(|default| generated_cols.iter().any(|gc| gc.name == default.name))
+        {
+            return Err(Error::Schema(format!(
+                "Column {} cannot have both a default value and a generation expression",
+                column.name
+            )));
+        }
+
+        if !column_defaults.is_empty() {
+            self = self.enable_column_defaults()
+        }
+
         Ok(self)
     }
 
@@ -435,7 +451,7 @@ impl ProtocolInner {
         for (key, value) in new_properties {
             if let Ok(parsed_key) = key.parse::<TableProperty>() {
                 parsed_properties.insert(parsed_key, value.to_string());
-            } else if raise_if_not_exists {
+            } else if raise_if_not_exists && !is_column_property(key) {
                 return Err(Error::Generic(format!(
                     "Error parsing property '{key}':'{value}'",
                 )));
@@ -576,6 +592,46 @@ impl ProtocolInner {
         self
     }
 
+    /// Enable column defaults, which only exist as a table feature
+    fn enable_column_defaults(mut self) -> Self {
+        // Upgrading to table features must keep the features implied by the legacy writer version
+        let legacy_features: &[TableFeature] = match self.min_writer_version {
+            2 => &[TableFeature::AppendOnly, TableFeature::Invariants],
+            3 => &[
+                TableFeature::AppendOnly,
+                TableFeature::Invariants,
+                TableFeature::CheckConstraints,
+            ],
+            4 => &[
+                TableFeature::AppendOnly,
+                TableFeature::Invariants,
+                TableFeature::CheckConstraints,
+                TableFeature::ChangeDataFeed,
+                TableFeature::GeneratedColumns,
+            ],
+            5 => &[
+                TableFeature::AppendOnly,
+                TableFeature::Invariants,
+                TableFeature::CheckConstraints,
+                TableFeature::ChangeDataFeed,
+                TableFeature::GeneratedColumns,
+                TableFeature::ColumnMapping,
+            ],
+            6 => &[
+                TableFeature::AppendOnly,
+                TableFeature::Invariants,
+                TableFeature::CheckConstraints,
+                TableFeature::ChangeDataFeed,
+                TableFeature::GeneratedColumns,
+                TableFeature::ColumnMapping,
+                TableFeature::IdentityColumns,
+            ],
+            _ => &[],
+        };
+        self = self.append_writer_features(legacy_features.iter().cloned());
+        self.append_writer_features([allow_column_defaults_feature()])
+    }
+
     /// Enabled generated columns
     fn enable_invariants(mut self) -> Self {
         if self.min_writer_version >= 7 {
@@ -617,6 +673,8 @@ pub enum TableFeatures {
     /// Iceberg compatibility support
     IcebergCompatV1,
     MaterializePartitionColumns,
+    /// Default values for columns
+    AllowColumnDefaults,
 }
 
 impl FromStr for TableFeatures {
@@ -638,6 +696,7 @@ impl FromStr for TableFeatures {
             "domainMetadata" => Ok(TableFeatures::DomainMetadata),
             "icebergCompatV1" => Ok(TableFeatures::IcebergCompatV1),
             "materializePartitionColumns" => Ok(TableFeatures::MaterializePartitionColumns),
+            "allowColumnDefaults" => Ok(TableFeatures::AllowColumnDefaults),
             _ => Err(()),
         }
     }
@@ -660,6 +719,7 @@ impl AsRef<str> for TableFeatures {
             TableFeatures::DomainMetadata => "domainMetadata",
             TableFeatures::IcebergCompatV1 => "icebergCompatV1",
             TableFeatures::MaterializePartitionColumns => "materializePartitionColumns",
+            TableFeatures::AllowColumnDefaults => "allowColumnDefaults",
         }
     }
 }
@@ -678,6 +738,11 @@ impl TryFrom<&TableFeatures> for TableFeature {
     }
 }
 
+/// The `allowColumnDefaults` writer feature, which delta_kernel only knows as an unknown feature
+pub(crate) fn allow_column_defaults_feature() -> TableFeature {
+    TableFeature::Unknown(TableFeatures::AllowColumnDefaults.as_ref().to_string())
+}
+
 impl TableFeatures {
     /// Convert table feature to respective reader or/and write feature
     pub fn to_reader_writer_features(&self) -> (Option<TableFeature>, Option<TableFeature>) {
@@ -717,6 +782,13 @@ impl TableFeatures {
                         (Some(feature.clone()), Some(feature))
                     }
 
+                    // Writer-only features delta_kernel does not know about yet
+                    TableFeature::Unknown(ref name)
+                        if name == TableFeatures::AllowColumnDefaults.as_ref() =>
+                    {
+                        (None, Some(feature.clone()))
+                    }
+
                     // Unknown features
                     TableFeature::Unknown(_) => (None, None),
                 }
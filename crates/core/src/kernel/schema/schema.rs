@@ -11,7 +11,7 @@ use serde_json::Value;
 
 use crate::kernel::error::Error;
 use crate::schema::DataCheck;
-use crate::table::GeneratedColumn;
+use crate::table::{COLUMN_DEFAULT_KEY, ColumnDefault, GeneratedColumn};
 
 /// Type alias for a top level schema
 pub type Schema = StructType;
@@ -58,6 +58,9 @@ pub trait StructTypeExt {
 
     /// Get all generated column expressions
     fn get_generated_columns(&self) -> Result<Vec<GeneratedColumn>, Error>;
+
+    /// Get all default values of top level columns
+    fn get_column_defaults(&self) -> Result<Vec<ColumnDefault>, Error>;
 }
 
 impl StructTypeExt for StructType {
@@ -84,6 +87,30 @@ impl StructTypeExt for StructType {
         Ok(generated_cols)
     }
 
+    /// Get all default values of top level columns
+    fn get_column_defaults(&self) -> Result<Vec<ColumnDefault>, Error> {
+        let mut defaults = Vec::new();
+        for field in self.fields() {
+            match field.metadata.get(COLUMN_DEFAULT_KEY) {
+                Some(MetadataValue::String(default_expr)) => {
+                    defaults.push(ColumnDefault::new(
+                        &field.name,
+                        default_expr,
+                        field.data_type(),
+                    ));
+                }
+                Some(value) => {
+                    return Err(Error::Schema(format!(
+                        "Default value of column {} must be a SQL string, found {value:?}",
+                        field.name
+                    )));
+                }
+                None => {}
+            }
+        }
+        Ok(defaults)
+    }
+
     /// Get all invariants in the schemas
     fn get_invariants(&self) -> Result<Vec<Invariant>, Error> {
         let mut remaining_fields: Vec<(String, StructField)> = self
@@ -202,6 +229,34 @@ mod tests {
         assert_eq!(cols.len(), 2);
     }
 
+    #[test]
+    fn test_get_column_defaults() {
+        let schema: StructType = serde_json::from_value(json!(
+            {
+                "type":"struct",
+                "fields":[
+                    {"name":"id","type":"integer","nullable":true,"metadata":{}},
+                    {"name":"status","type":"string","nullable":true,"metadata":{"CURRENT_DEFAULT":"'open'"}}]
+            }
+        ))
+        .unwrap();
+        let defaults = schema.get_column_defaults().unwrap();
+        assert_eq!(
+            defaults,
+            vec![ColumnDefault::new("status", "'open'", &DataType::STRING)]
+        );
+
+        let schema: StructType = serde_json::from_value(json!(
+            {
+                "type":"struct",
+                "fields":[
+                    {"name":"id","type":"integer","nullable":true,"metadata":{"CURRENT_DEFAULT":5}}]
+            }
+        ))
+        .unwrap();
+        assert!(schema.get_column_defaults().is_err());
+    }
+
     #[test]
     fn test_get_invariants() {
         let schema: StructType = serde_json::from_value(json!({
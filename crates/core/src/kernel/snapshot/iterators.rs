@@ -189,7 +189,7 @@ impl LogicalFileView {
     }
 
     /// Converts partition values to a map of column names to serialized values.
-    fn partition_values_map(&self) -> HashMap<String, Option<String>> {
+    pub(crate) fn partition_values_map(&self) -> HashMap<String, Option<String>> {
         self.partition_values()
             .map(|data| {
                 data.fields()
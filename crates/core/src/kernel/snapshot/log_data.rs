@@ -128,6 +128,7 @@ mod datafusion {
     use itertools::Itertools;
 
     use super::*;
+    use crate::delta_datafusion::stats_bound_precision;
     use crate::kernel::arrow::engine_ext::ExpressionEvaluatorExt as _;
     use crate::kernel::arrow::extract::{extract_and_cast_opt, extract_column};
     use crate::{DeltaResult, DeltaTableError};
@@ -210,12 +211,18 @@ mod datafusion {
                 };
 
                 if let Some(mut accumulator) = accumulator {
-                    return accumulator
+                    let bound = accumulator
                         .update_batch(&[array.clone()])
                         .ok()
                         .and_then(|_| accumulator.evaluate().ok())
-                        .map(Precision::Exact)
+                        .map(stats_bound_precision)
                         .unwrap_or(Precision::Absent);
+                    // Files without statistics for the column may contain any value
+                    return if array.null_count() > 0 {
+                        bound.to_inexact()
+                    } else {
+                        bound
+                    };
                 }
 
                 return Precision::Absent;
@@ -231,10 +238,7 @@ mod datafusion {
                             fun_type.clone(),
                         )
                     })
-                    .map(|s| match s {
-                        Precision::Exact(s) => Some(s),
-                        _ => None,
-                    })
+                    .map(|s| s.get_value().cloned())
                     .collect::<Option<Vec<_>>>()
                     .map(|o| {
                         let arrays = o
@@ -242,8 +246,9 @@ mod datafusion {
                             .map(|sv| sv.to_array())
                             .collect::<Result<Vec<_>, DataFusionError>>()
                             .unwrap();
+                        // Field wise bounds do not bound the struct values as a whole
                         let sa = StructArray::new(fields.clone(), arrays, None);
-                        Precision::Exact(ScalarValue::Struct(Arc::new(sa)))
+                        Precision::Inexact(ScalarValue::Struct(Arc::new(sa)))
                     })
                     .unwrap_or(Precision::Absent),
                 _ => Precision::Absent,
@@ -269,10 +274,12 @@ mod datafusion {
 
             let min_value = self.column_bounds(COL_MIN_VALUES, name.as_ref(), AccumulatorType::Min);
             let min_value = match &min_value {
-                Precision::Exact(value) if value.is_null() => Precision::Absent,
+                Precision::Exact(value) | Precision::Inexact(value) if value.is_null() => {
+                    Precision::Absent
+                }
                 // TODO this is a hack, we should not be casting here but rather when we read the checkpoint data.
                 // it seems sometimes the min/max values are stored as nanoseconds and sometimes as microseconds?
-                Precision::Exact(ScalarValue::TimestampNanosecond(a, b)) => Precision::Exact(
+                Precision::Inexact(ScalarValue::TimestampNanosecond(a, b)) => Precision::Inexact(
                     ScalarValue::TimestampMicrosecond(a.map(|v| v / 1000), b.clone()),
                 ),
                 _ => min_value,
@@ -280,8 +287,10 @@ mod datafusion {
 
             let max_value = self.column_bounds(COL_MAX_VALUES, name.as_ref(), AccumulatorType::Max);
             let max_value = match &max_value {
-                Precision::Exact(value) if value.is_null() => Precision::Absent,
-                Precision::Exact(ScalarValue::TimestampNanosecond(a, b)) => Precision::Exact(
+                Precision::Exact(value) | Precision::Inexact(value) if value.is_null() => {
+                    Precision::Absent
+                }
+                Precision::Inexact(ScalarValue::TimestampNanosecond(a, b)) => Precision::Inexact(
                     ScalarValue::TimestampMicrosecond(a.map(|v| v / 1000), b.clone()),
                 ),
                 _ => max_value,
@@ -326,15 +335,26 @@ mod datafusion {
 
         fn num_records(&self) -> Precision<usize> {
             if let Some(accessors) = self.accessors() {
-                return accessors
+                let num_records = accessors
                     .iter()
                     .map(|a| a.num_records())
                     .reduce(|acc, num_records| acc.add(&num_records))
                     .unwrap_or(Precision::Absent);
+                // Deleted rows are still counted in the file statistics
+                return if self.has_deletion_vectors() {
+                    num_records.to_inexact()
+                } else {
+                    num_records
+                };
             }
             Precision::Absent
         }
 
+        fn has_deletion_vectors(&self) -> bool {
+            self.iter()
+                .any(|file| file.deletion_vector_descriptor().is_some())
+        }
+
         fn total_size_files(&self) -> Precision<usize> {
             if let Some(accessors) = self.accessors() {
                 return accessors
@@ -365,11 +385,20 @@ mod datafusion {
         pub(crate) fn statistics(&self) -> Option<Statistics> {
             let num_rows = self.num_records();
             let total_byte_size = self.total_size_files();
+            let has_deletion_vectors = self.has_deletion_vectors();
             let column_statistics = self
                 .config
                 .schema()
                 .fields()
-                .map(|f| self.column_stats(f.name()))
+                .map(|f| {
+                    self.column_stats(f.name()).map(|stats| {
+                        if has_deletion_vectors {
+                            stats.to_inexact()
+                        } else {
+                            stats
+                        }
+                    })
+                })
                 .collect::<Option<Vec<_>>>()?;
             Some(Statistics {
                 num_rows,
@@ -494,6 +494,8 @@ pub struct EagerSnapshot {
     snapshot: Snapshot,
     // logical files in the snapshot
     files: Vec<RecordBatch>,
+    // predicate the files were filtered with, when only some files are loaded
+    files_predicate: Option<PredicateRef>,
 }
 
 pub(crate) async fn resolve_snapshot(
@@ -541,7 +543,11 @@ impl EagerSnapshot {
             true => snapshot.files(log_store, None).try_collect().await?,
             false => vec![],
         };
-        Ok(Self { snapshot, files })
+        Ok(Self {
+            snapshot,
+            files,
+            files_predicate: None,
+        })
     }
 
     pub(crate) async fn with_files(mut self, log_store: &dyn LogStore) -> DeltaResult<Self> {
@@ -552,8 +558,47 @@ impl EagerSnapshot {
         Self::try_new_with_snapshot(log_store, self.snapshot).await
     }
 
+    /// Load only the active files the predicate cannot exclude.
+    ///
+    /// Snapshots loaded without files (see [`DeltaTableConfig::require_files`]) are kept memory
+    /// bounded for operations that only touch some of the files, by replaying the log with the
+    /// predicate pushed into the checkpoint read. Snapshots which already hold all files are
+    /// returned as is, and without a predicate all files are loaded.
+    pub(crate) async fn with_files_matching(
+        mut self,
+        log_store: &dyn LogStore,
+        predicate: Option<PredicateRef>,
+    ) -> DeltaResult<Self> {
+        let Some(predicate) = predicate else {
+            return self.with_files(log_store).await;
+        };
+        if self.snapshot.config.require_files || self.files_predicate.as_ref() == Some(&predicate) {
+            return Ok(self);
+        }
+        self.files = self
+            .snapshot
+            .files(log_store, Some(predicate.clone()))
+            .try_collect()
+            .await?;
+        self.files_predicate = Some(predicate);
+        Ok(self)
+    }
+
+    /// Drop files loaded via [`EagerSnapshot::with_files_matching`].
+    pub(crate) fn without_partial_files(mut self) -> Self {
+        if self.files_predicate.take().is_some() {
+            self.files = vec![];
+        }
+        self
+    }
+
+    /// The predicate the loaded files were filtered with, if only some files are loaded.
+    pub(crate) fn files_predicate(&self) -> Option<PredicateRef> {
+        self.files_predicate.clone()
+    }
+
     pub(crate) fn files(&self) -> DeltaResult<&[RecordBatch]> {
-        if self.snapshot.config.require_files {
+        if self.snapshot.config.require_files || self.files_predicate.is_some() {
             Ok(&self.files)
         } else {
             Err(DeltaTableError::NotInitializedWithFiles("files".into()))
@@ -573,14 +618,18 @@ impl EagerSnapshot {
 
         self.snapshot.update(log_store, target_version).await?;
 
+        // snapshots without files only keep the files matching their predicate up to date
+        if !self.snapshot.config.require_files && self.files_predicate.is_none() {
+            return Ok(());
+        }
         self.files = self
             .snapshot
             .files_from(
                 log_store,
-                None,
+                self.files_predicate.clone(),
                 current_version,
                 Box::new(std::mem::take(&mut self.files).into_iter()),
-                None,
+                self.files_predicate.clone(),
             )
             .try_collect()
             .await?;
@@ -789,7 +838,11 @@ mod tests {
                 .files(log_store.as_ref(), None)
                 .try_collect()
                 .await?;
-            Ok(Self { snapshot, files })
+            Ok(Self {
+                snapshot,
+                files,
+                files_predicate: None,
+            })
         }
     }
 
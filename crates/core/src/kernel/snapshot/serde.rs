@@ -15,7 +15,7 @@ use serde::de::{self, Deserializer, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize, ser::SerializeSeq};
 use url::Url;
 
-use crate::DeltaTableConfig;
+use crate::{DeltaResult, DeltaTableConfig, DeltaTableError};
 
 use super::{EagerSnapshot, Snapshot};
 
@@ -295,7 +295,11 @@ impl<'de> Visitor<'de> for EagerSnapshotVisitor {
                 .map_err(|e| de::Error::custom(format!("failed to read ipc record batch: {e}")))?
         };
 
-        Ok(EagerSnapshot { snapshot, files })
+        Ok(EagerSnapshot {
+            snapshot,
+            files,
+            files_predicate: None,
+        })
     }
 }
 
@@ -307,3 +311,50 @@ impl<'de> Deserialize<'de> for EagerSnapshot {
         deserializer.deserialize_seq(EagerSnapshotVisitor)
     }
 }
+
+impl EagerSnapshot {
+    /// Encode the snapshot into a compact binary representation.
+    ///
+    /// The layout is the length of the JSON encoded [`Snapshot`] as little endian `u64`, followed
+    /// by the JSON encoded snapshot and the files as an arrow IPC file.
+    pub(crate) fn encode(&self) -> DeltaResult<Vec<u8>> {
+        let snapshot = serde_json::to_vec(&self.snapshot)?;
+        let mut buffer = Vec::with_capacity(snapshot.len() + 8);
+        buffer.extend_from_slice(&(snapshot.len() as u64).to_le_bytes());
+        buffer.extend_from_slice(&snapshot);
+
+        if let Some(first) = self.files.first() {
+            let mut writer = FileWriter::try_new(&mut buffer, first.schema().as_ref())?;
+            for file in &self.files {
+                writer.write(file)?;
+            }
+            writer.finish()?;
+        }
+
+        Ok(buffer)
+    }
+
+    /// Decode a snapshot previously encoded with [`EagerSnapshot::encode`]
+    pub(crate) fn decode(data: &[u8]) -> DeltaResult<Self> {
+        let invalid = || DeltaTableError::Generic("Invalid encoded snapshot".to_string());
+        let (len, data) = data.split_first_chunk::<8>().ok_or_else(invalid)?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| invalid())?;
+        if data.len() < len {
+            return Err(invalid());
+        }
+        let (snapshot, files) = data.split_at(len);
+
+        let snapshot: Snapshot = serde_json::from_slice(snapshot)?;
+        let files = if files.is_empty() {
+            vec![]
+        } else {
+            FileReader::try_new(std::io::Cursor::new(files), None)?.try_collect()?
+        };
+
+        Ok(EagerSnapshot {
+            snapshot,
+            files,
+            files_predicate: None,
+        })
+    }
+}
@@ -0,0 +1,136 @@
+//! Auto compaction post-commit hook
+//!
+//! When `delta.autoOptimize.autoCompact` is enabled, the partitions written by a commit are
+//! checked for small files after the commit succeeded. Partitions holding at least
+//! `delta.autoOptimize.autoCompact.minNumFiles` files below
+//! `delta.autoOptimize.autoCompact.maxFileSize` are compacted with [`OptimizeBuilder`].
+use std::collections::{BTreeMap, HashMap};
+
+use itertools::Itertools as _;
+use tracing::*;
+
+use super::{CommitData, TransactionError};
+use crate::errors::{DeltaResult, DeltaTableError};
+use crate::kernel::Action;
+use crate::logstore::LogStoreRef;
+use crate::operations::optimize::OptimizeBuilder;
+use crate::table::config::{AutoCompactSettings, TablePropertiesExt as _};
+use crate::table::state::DeltaTableState;
+use crate::{PartitionFilter, PartitionValue};
+
+/// Partition values of a table partition, keyed by partition column
+type PartitionValues = BTreeMap<String, Option<String>>;
+
+/// Files rewritten by auto compaction
+#[derive(Debug, Default)]
+pub(super) struct AutoCompactMetrics {
+    /// Number of compacted files added
+    pub num_files_added: u64,
+    /// Number of small files removed
+    pub num_files_removed: u64,
+    /// Number of partitions whose compaction failed for other reasons than a conflict
+    pub num_failed_partitions: u64,
+}
+
+/// Compact the partitions written by `data` if they hold enough small files.
+///
+/// `state` is the table state after the commit, it is advanced to the state after the last
+/// compaction. The commit already succeeded, so failed compactions never fail it: compactions
+/// losing against a concurrent commit are skipped with a warning, other failures are logged and
+/// counted in the metrics.
+pub(super) async fn auto_compact(
+    log_store: &LogStoreRef,
+    state: &mut DeltaTableState,
+    data: &CommitData,
+) -> DeltaResult<AutoCompactMetrics> {
+    let mut metrics = AutoCompactMetrics::default();
+    if !data.operation.changes_data() || !state.load_config().require_files {
+        return Ok(metrics);
+    }
+    let Some(settings) = state.table_config().auto_compact() else {
+        return Ok(metrics);
+    };
+
+    for partition in partitions_to_compact(data, state, &settings) {
+        let filters = partition
+            .iter()
+            .map(|(key, value)| PartitionFilter {
+                key: key.clone(),
+                value: PartitionValue::Equal(value.clone().unwrap_or_default()),
+            })
+            .collect_vec();
+        debug!("auto compacting partition {partition:?}");
+
+        let result = OptimizeBuilder::new(log_store.clone(), Some(state.snapshot.clone()))
+            .with_filters(&filters)
+            .with_target_size(settings.max_file_size)
+            .await;
+        match result {
+            Ok((table, optimize_metrics)) => {
+                metrics.num_files_added += optimize_metrics.num_files_added;
+                metrics.num_files_removed += optimize_metrics.num_files_removed;
+                if let Some(table_state) = table.state {
+                    *state = table_state;
+                }
+            }
+            Err(err) if is_conflict(&err) => {
+                warn!("skipping auto compaction of partition {partition:?}: {err}");
+            }
+            Err(err) => {
+                warn!("auto compaction of partition {partition:?} failed: {err}");
+                metrics.num_failed_partitions += 1;
+            }
+        }
+    }
+
+    Ok(metrics)
+}
+
+/// Partitions written by `data` holding at least `min_num_files` files below `max_file_size`
+fn partitions_to_compact(
+    data: &CommitData,
+    state: &DeltaTableState,
+    settings: &AutoCompactSettings,
+) -> Vec<PartitionValues> {
+    let mut small_files: HashMap<PartitionValues, u64> = data
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Add(add) if add.data_change => {
+                Some((add.partition_values.clone().into_iter().collect(), 0))
+            }
+            _ => None,
+        })
+        .collect();
+    if small_files.is_empty() {
+        return Vec::new();
+    }
+
+    for file in state.snapshot.log_data().iter() {
+        if file.size() as u64 >= settings.max_file_size {
+            continue;
+        }
+        let partition_values: PartitionValues = file.partition_values_map().into_iter().collect();
+        if let Some(count) = small_files.get_mut(&partition_values) {
+            *count += 1;
+        }
+    }
+
+    small_files
+        .into_iter()
+        .filter(|(_, count)| *count >= settings.min_num_files)
+        .map(|(partition, _)| partition)
+        .sorted()
+        .collect()
+}
+
+fn is_conflict(err: &DeltaTableError) -> bool {
+    matches!(
+        err,
+        DeltaTableError::VersionAlreadyExists(_)
+            | DeltaTableError::Transaction {
+                source: TransactionError::CommitConflict(_)
+                    | TransactionError::MaxCommitAttempts(_)
+            }
+    )
+}
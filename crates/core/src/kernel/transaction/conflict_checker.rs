@@ -555,7 +555,8 @@ impl<'a> ConflictChecker<'a> {
     fn check_for_deleted_files_against_current_txn_deleted_files(
         &self,
     ) -> Result<(), CommitConflictError> {
-        // Fail if a file is deleted twice.
+        // Fail if a file is deleted twice. Files re-added without a data change, e.g. to
+        // replace their statistics, must not have been deleted concurrently either.
         let txn_deleted_files: HashSet<String> = self
             .txn_info
             .actions
@@ -563,6 +564,7 @@ impl<'a> ConflictChecker<'a> {
             .cloned()
             .filter_map(|action| match action {
                 Action::Remove(remove) => Some(remove.path),
+                Action::Add(add) if !add.data_change => Some(add.path),
                 _ => None,
             })
             .collect();
@@ -1048,6 +1050,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[cfg(feature = "datafusion")]
+    async fn test_readd_conflicts_with_concurrent_delete() {
+        // re-adding a file with fresh statistics while a concurrent transaction removes it
+        let file = simple_add(true, "1", "10");
+        let mut setup_actions = init_table_actions();
+        setup_actions.push(file.clone().into());
+
+        let mut readd = file.clone();
+        readd.data_change = false;
+        let result = execute_test(
+            Some(setup_actions),
+            None,
+            vec![ActionFactory::remove(&file, false).into()],
+            vec![readd.into()],
+            false,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(CommitConflictError::ConcurrentDeleteDelete)
+        ));
+    }
+
     #[tokio::test]
     #[cfg(feature = "datafusion")]
     async fn test_concurrent_compaction_double_delete_still_conflicts() {
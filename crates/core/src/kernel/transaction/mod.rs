@@ -97,7 +97,10 @@ use crate::logstore::ObjectStoreRef;
 use crate::logstore::{CommitOrBytes, LogStoreRef};
 use crate::operations::CustomExecuteHandler;
 use crate::protocol::DeltaOperation;
-use crate::protocol::{cleanup_expired_logs_for, create_checkpoint_for};
+use crate::protocol::{
+    cleanup_expired_logs_for, create_checkpoint_for, create_checksum_for,
+    create_log_compaction_for, create_partition_stats_for,
+};
 use crate::table::config::TablePropertiesExt as _;
 use crate::table::state::DeltaTableState;
 use crate::{DeltaResult, crate_version};
@@ -107,6 +110,8 @@ pub use self::protocol::INSTANCE as PROTOCOL;
 
 #[cfg(test)]
 pub(crate) mod application;
+#[cfg(feature = "datafusion")]
+mod auto_compact;
 mod conflict_checker;
 mod protocol;
 #[cfg(feature = "datafusion")]
@@ -128,8 +133,26 @@ pub struct PostCommitMetrics {
     /// Whether a new checkpoint was created as part of this commit
     pub new_checkpoint_created: bool,
 
+    /// Whether a new log compaction file was created as part of this commit
+    pub new_log_compaction_created: bool,
+
+    /// Whether a version checksum file was created as part of this commit
+    pub new_checksum_created: bool,
+
+    /// Whether a partition statistics summary was created as part of this commit
+    pub new_partition_stats_created: bool,
+
     /// Number of log files cleaned up
     pub num_log_files_cleaned_up: u64,
+
+    /// Number of compacted files added by auto compaction
+    pub num_auto_compact_files_added: u64,
+
+    /// Number of small files removed by auto compaction
+    pub num_auto_compact_files_removed: u64,
+
+    /// Number of partitions auto compaction failed for
+    pub num_auto_compact_failures: u64,
 }
 
 #[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -141,8 +164,26 @@ pub struct Metrics {
     /// Whether a new checkpoint was created as part of this commit
     pub new_checkpoint_created: bool,
 
+    /// Whether a new log compaction file was created as part of this commit
+    pub new_log_compaction_created: bool,
+
+    /// Whether a version checksum file was created as part of this commit
+    pub new_checksum_created: bool,
+
+    /// Whether a partition statistics summary was created as part of this commit
+    pub new_partition_stats_created: bool,
+
     /// Number of log files cleaned up
     pub num_log_files_cleaned_up: u64,
+
+    /// Number of compacted files added by auto compaction
+    pub num_auto_compact_files_added: u64,
+
+    /// Number of small files removed by auto compaction
+    pub num_auto_compact_files_removed: u64,
+
+    /// Number of partitions auto compaction failed for
+    pub num_auto_compact_failures: u64,
 }
 
 /// Error raised while commititng transaction
@@ -338,8 +379,12 @@ impl CommitData {
 /// Properties for post commit hook.
 pub struct PostCommitHookProperties {
     create_checkpoint: bool,
+    /// Compact the log every given number of commits, if None no log compaction files are written
+    log_compaction_interval: Option<u64>,
     /// Override the EnableExpiredLogCleanUp setting, if None config setting is used
     cleanup_expired_logs: Option<bool>,
+    /// Write a version checksum file when creating the table
+    create_checksum: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -350,7 +395,9 @@ pub struct CommitProperties {
     pub(crate) app_transaction: Vec<Transaction>,
     max_retries: usize,
     create_checkpoint: bool,
+    log_compaction_interval: Option<u64>,
     cleanup_expired_logs: Option<bool>,
+    create_checksum: bool,
 }
 
 impl Default for CommitProperties {
@@ -360,7 +407,9 @@ impl Default for CommitProperties {
             app_transaction: Vec::new(),
             max_retries: DEFAULT_RETRIES,
             create_checkpoint: true,
+            log_compaction_interval: None,
             cleanup_expired_logs: None,
+            create_checksum: false,
         }
     }
 }
@@ -387,6 +436,22 @@ impl CommitProperties {
         self
     }
 
+    /// Specify the number of commits after which the preceding commits are compacted into a
+    /// log compaction file, if None no log compaction files are written. An interval needs
+    /// to span at least two commits.
+    pub fn with_log_compaction_interval(mut self, log_compaction_interval: Option<u64>) -> Self {
+        self.log_compaction_interval = log_compaction_interval.filter(|interval| *interval > 1);
+        self
+    }
+
+    /// Specify if a version checksum file should be written when the commit creates the table.
+    /// Subsequent commits write their checksum incrementally as long as the previous version
+    /// has one.
+    pub fn with_create_checksum(mut self, create_checksum: bool) -> Self {
+        self.create_checksum = create_checksum;
+        self
+    }
+
     /// Add an additional application transaction to the commit
     pub fn with_application_transaction(mut self, txn: Transaction) -> Self {
         self.app_transaction.push(txn);
@@ -413,7 +478,9 @@ impl From<CommitProperties> for CommitBuilder {
             app_metadata: value.app_metadata,
             post_commit_hook: Some(PostCommitHookProperties {
                 create_checkpoint: value.create_checkpoint,
+                log_compaction_interval: value.log_compaction_interval,
                 cleanup_expired_logs: value.cleanup_expired_logs,
+                create_checksum: value.create_checksum,
             }),
             app_transaction: value.app_transaction,
             ..Default::default()
@@ -554,10 +621,16 @@ impl<'a> PreCommit<'a> {
             }
             let log_entry = this.data.get_bytes()?;
 
-            // With the DefaultLogStore & LakeFSLogstore, we just pass the bytes around, since we use conditionalPuts
-            // Other stores will use tmp_commits
-            let commit_or_bytes = if ["LakeFSLogStore", "DefaultLogStore"]
-                .contains(&this.log_store.name().as_str())
+            // With the DefaultLogStore, LakeFSLogstore and the S3, Azure & GCS conditional put stores, we just
+            // pass the bytes around, since we use conditionalPuts. Other stores will use tmp_commits
+            let commit_or_bytes = if [
+                "LakeFSLogStore",
+                "DefaultLogStore",
+                "S3ConditionalPutLogStore",
+                "AzureLogStore",
+                "GcsLogStore",
+            ]
+            .contains(&this.log_store.name().as_str())
             {
                 CommitOrBytes::LogBytes(log_entry)
             } else {
@@ -626,7 +699,12 @@ impl<'a> std::future::IntoFuture for PreparedCommit<'a> {
                             version: 0,
                             data: this.data,
                             create_checkpoint: false,
+                            log_compaction_interval: None,
                             cleanup_expired_logs: None,
+                            create_checksum: this
+                                .post_commit
+                                .map(|v| v.create_checksum)
+                                .unwrap_or_default(),
                             log_store: this.log_store,
                             table_data: None,
                             custom_execute_handler: this.post_commit_hook_handler,
@@ -761,10 +839,17 @@ impl<'a> std::future::IntoFuture for PreparedCommit<'a> {
                                     .post_commit
                                     .map(|v| v.create_checkpoint)
                                     .unwrap_or_default(),
+                                log_compaction_interval: this
+                                    .post_commit
+                                    .and_then(|v| v.log_compaction_interval),
                                 cleanup_expired_logs: this
                                     .post_commit
                                     .map(|v| v.cleanup_expired_logs)
                                     .unwrap_or_default(),
+                                create_checksum: this
+                                    .post_commit
+                                    .map(|v| v.create_checksum)
+                                    .unwrap_or_default(),
                                 log_store: this.log_store,
                                 table_data: Some(Box::new(read_snapshot)),
                                 custom_execute_handler: this.post_commit_hook_handler,
@@ -816,7 +901,9 @@ pub struct PostCommit {
     /// The data that was committed to the log store
     pub data: CommitData,
     create_checkpoint: bool,
+    log_compaction_interval: Option<u64>,
     cleanup_expired_logs: Option<bool>,
+    create_checksum: bool,
     log_store: LogStoreRef,
     table_data: Option<Box<dyn TableReference>>,
     custom_execute_handler: Option<Arc<dyn CustomExecuteHandler>>,
@@ -848,7 +935,9 @@ impl PostCommit {
                 custom_execute_handler
                     .before_post_commit_hook(
                         &self.log_store,
-                        cleanup_logs || self.create_checkpoint,
+                        cleanup_logs
+                            || self.create_checkpoint
+                            || self.log_compaction_interval.is_some(),
                         post_commit_operation_id,
                     )
                     .await?
@@ -867,6 +956,31 @@ impl PostCommit {
                     .await?;
             }
 
+            let mut new_log_compaction_created = false;
+            if let Some(interval) = self.log_compaction_interval
+                && !new_checkpoint_created
+            {
+                // Execute log compaction hook
+                new_log_compaction_created = self
+                    .create_log_compaction(
+                        &self.log_store,
+                        self.version,
+                        interval,
+                        post_commit_operation_id,
+                    )
+                    .await?;
+            }
+
+            // Execute version checksum hook
+            let new_checksum_created = self
+                .create_checksum(&self.log_store, post_commit_operation_id)
+                .await?;
+
+            // Execute partition statistics hook
+            let new_partition_stats_created = self
+                .create_partition_stats(&state, &self.log_store, post_commit_operation_id)
+                .await?;
+
             let mut num_log_files_cleaned_up: u64 = 0;
             if cleanup_logs {
                 // Execute clean up logs hook
@@ -888,12 +1002,36 @@ impl PostCommit {
                 }
             }
 
+            // Execute auto compaction hook, compaction requires datafusion to rewrite files
+            #[cfg(feature = "datafusion")]
+            let (
+                num_auto_compact_files_added,
+                num_auto_compact_files_removed,
+                num_auto_compact_failures,
+            ) = {
+                let metrics =
+                    auto_compact::auto_compact(&self.log_store, &mut state, &self.data).await?;
+                (
+                    metrics.num_files_added,
+                    metrics.num_files_removed,
+                    metrics.num_failed_partitions,
+                )
+            };
+            #[cfg(not(feature = "datafusion"))]
+            let (
+                num_auto_compact_files_added,
+                num_auto_compact_files_removed,
+                num_auto_compact_failures,
+            ) = (0, 0, 0);
+
             // Run arbitrary after_post_commit_hook code
             if let Some(custom_execute_handler) = &self.custom_execute_handler {
                 custom_execute_handler
                     .after_post_commit_hook(
                         &self.log_store,
-                        cleanup_logs || self.create_checkpoint,
+                        cleanup_logs
+                            || self.create_checkpoint
+                            || self.log_compaction_interval.is_some(),
                         post_commit_operation_id,
                     )
                     .await?
@@ -902,18 +1040,30 @@ impl PostCommit {
                 state,
                 PostCommitMetrics {
                     new_checkpoint_created,
+                    new_log_compaction_created,
+                    new_checksum_created,
+                    new_partition_stats_created,
                     num_log_files_cleaned_up,
+                    num_auto_compact_files_added,
+                    num_auto_compact_files_removed,
+                    num_auto_compact_failures,
                 },
             ))
         } else {
+            let operation_id = Uuid::new_v4();
+            let new_checksum_created = self.create_checksum(&self.log_store, operation_id).await?;
             let state =
                 DeltaTableState::try_new(&self.log_store, Default::default(), Some(self.version))
                     .await?;
+            let new_partition_stats_created = self
+                .create_partition_stats(&state, &self.log_store, operation_id)
+                .await?;
             Ok((
                 state,
                 PostCommitMetrics {
-                    new_checkpoint_created: false,
-                    num_log_files_cleaned_up: 0,
+                    new_checksum_created,
+                    new_partition_stats_created,
+                    ..Default::default()
                 },
             ))
         }
@@ -940,6 +1090,84 @@ impl PostCommit {
             Ok(false)
         }
     }
+
+    async fn create_log_compaction(
+        &self,
+        log_store: &LogStoreRef,
+        version: i64,
+        interval: u64,
+        operation_id: Uuid,
+    ) -> DeltaResult<bool> {
+        let interval = interval as i64;
+        if version > 0 && (version + 1) % interval == 0 {
+            create_log_compaction_for(
+                version + 1 - interval,
+                version,
+                log_store.as_ref(),
+                Some(operation_id),
+            )
+            .await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn create_checksum(
+        &self,
+        log_store: &LogStoreRef,
+        operation_id: Uuid,
+    ) -> DeltaResult<bool> {
+        // The checksum of the table creation can only be written when requested, later versions
+        // are derived from the checksum of their previous version
+        if self.version == 0 && !self.create_checksum {
+            return Ok(false);
+        }
+        match create_checksum_for(
+            self.version,
+            &self.data.actions,
+            log_store.as_ref(),
+            Some(operation_id),
+        )
+        .await
+        {
+            Ok(created) => Ok(created),
+            Err(err) => {
+                // The commit already succeeded, a missing checksum only slows down validation
+                warn!(
+                    "failed to write checksum for version {}: {err}",
+                    self.version
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    async fn create_partition_stats(
+        &self,
+        table_state: &DeltaTableState,
+        log_store: &LogStoreRef,
+        operation_id: Uuid,
+    ) -> DeltaResult<bool> {
+        match create_partition_stats_for(
+            &table_state.snapshot,
+            &self.data.actions,
+            log_store.as_ref(),
+            Some(operation_id),
+        )
+        .await
+        {
+            Ok(created) => Ok(created),
+            Err(err) => {
+                // The commit already succeeded, scans fall back to pruning individual files
+                warn!(
+                    "failed to write partition statistics for version {}: {err}",
+                    self.version
+                );
+                Ok(false)
+            }
+        }
+    }
 }
 
 /// A commit that successfully completed
@@ -989,7 +1217,16 @@ impl std::future::IntoFuture for PostCommit {
                     metrics: Metrics {
                         num_retries: this.metrics.num_retries,
                         new_checkpoint_created: post_commit_metrics.new_checkpoint_created,
+                        new_log_compaction_created: post_commit_metrics.new_log_compaction_created,
+                        new_checksum_created: post_commit_metrics.new_checksum_created,
+                        new_partition_stats_created: post_commit_metrics
+                            .new_partition_stats_created,
                         num_log_files_cleaned_up: post_commit_metrics.num_log_files_cleaned_up,
+                        num_auto_compact_files_added: post_commit_metrics
+                            .num_auto_compact_files_added,
+                        num_auto_compact_files_removed: post_commit_metrics
+                            .num_auto_compact_files_removed,
+                        num_auto_compact_failures: post_commit_metrics.num_auto_compact_failures,
                     },
                 }),
                 Err(err) => Err(err),
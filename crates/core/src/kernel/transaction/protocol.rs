@@ -248,6 +248,7 @@ pub static INSTANCE: LazyLock<ProtocolChecker> = LazyLock::new(|| {
         writer_features.insert(TableFeature::Invariants);
         writer_features.insert(TableFeature::CheckConstraints);
         writer_features.insert(TableFeature::GeneratedColumns);
+        writer_features.insert(crate::kernel::allow_column_defaults_feature());
     }
     writer_features.insert(TableFeature::DeletionVectors);
     // writer_features.insert(TableFeature::ColumnMapping);
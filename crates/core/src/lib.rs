@@ -112,6 +112,10 @@ pub use object_store::{Error as ObjectStoreError, ObjectMeta, ObjectStore, path:
 pub use operations::DeltaOps;
 
 pub use protocol::checkpoints;
+pub use protocol::checksum;
+pub use protocol::leases;
+pub use protocol::log_compaction;
+pub use protocol::partition_stats;
 
 // convenience exports for consumers to avoid aligning crate versions
 pub use arrow;
@@ -84,7 +84,9 @@ impl LogStore for DefaultLogStore {
                 .await
                 .map_err(|err| -> TransactionError {
                     match err {
-                        ObjectStoreError::AlreadyExists { .. } => {
+                        // Azure and GCS report a failed precondition instead
+                        ObjectStoreError::AlreadyExists { .. }
+                        | ObjectStoreError::Precondition { .. } => {
                             TransactionError::VersionAlreadyExists(version)
                         }
                         _ => TransactionError::from(err),
@@ -159,7 +159,7 @@ pub type LogStoreRef = Arc<dyn LogStore>;
 static DELTA_LOG_PATH: LazyLock<Path> = LazyLock::new(|| Path::from("_delta_log"));
 
 pub(crate) static DELTA_LOG_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(\d{20})\.(json|checkpoint(\.\d+)?\.parquet)$").unwrap());
+    LazyLock::new(|| Regex::new(r"(\d{20})\.(json|checkpoint(\.\d+){0,2}\.parquet)$").unwrap());
 
 /// Return the [LogStoreRef] for the provided [Url] location
 ///
@@ -0,0 +1,367 @@
+//! Backfill file statistics for files that were added to the table without them.
+//!
+//! Tables created by [`convert_to_delta`](super::convert_to_delta) or by older writers often
+//! carry no statistics at all, so no files can be skipped when scanning them. This operation
+//! collects statistics for such files, as well as for files lacking statistics for a column listed
+//! in `delta.dataSkippingStatsColumns`, and re-adds them with `dataChange = false`.
+//!
+//! Statistics are read from the Parquet footer of a file where available. Files written without
+//! column statistics are scanned instead.
+//!
+//! # Example
+//! ```rust ignore
+//! let table = open_table("../path/to/table")?;
+//! let (table, metrics) = table.compute_stats().await?;
+//! ````
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use delta_kernel::expressions::Scalar;
+use futures::future::BoxFuture;
+use futures::{StreamExt, TryStreamExt};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use object_store::ObjectStore;
+use object_store::path::Path;
+use parquet::arrow::async_reader::{ParquetObjectReader, ParquetRecordBatchStreamBuilder};
+use serde::Serialize;
+use tracing::*;
+
+use super::{CustomExecuteHandler, Operation};
+use crate::DeltaTable;
+use crate::errors::DeltaResult;
+use crate::kernel::transaction::{CommitBuilder, CommitProperties};
+use crate::kernel::{Action, Add, EagerSnapshot, resolve_snapshot};
+use crate::logstore::LogStoreRef;
+use crate::protocol::{ColumnCountStat, DeltaOperation, Stats};
+use crate::table::config::TablePropertiesExt as _;
+use crate::table::state::DeltaTableState;
+use crate::writer::stats::{RecordBatchStats, parse_stats_columns, stats_from_parquet_metadata};
+
+/// Collect missing statistics for the active files of a table.
+/// See this module's documentation for more information
+pub struct ComputeStatsBuilder {
+    /// A snapshot of the table's state
+    snapshot: Option<EagerSnapshot>,
+    /// Delta object store for handling data files
+    log_store: LogStoreRef,
+    /// Recompute statistics for all files, not only for files with incomplete statistics
+    recompute: bool,
+    /// Max number of files processed concurrently
+    max_concurrent_tasks: usize,
+    /// Commit properties and configuration
+    commit_properties: CommitProperties,
+    custom_execute_handler: Option<Arc<dyn CustomExecuteHandler>>,
+}
+
+/// Metrics of the compute stats operation
+#[derive(Default, Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeStatsMetrics {
+    /// Number of files re-added with fresh statistics
+    pub num_files_updated: u64,
+    /// Number of files whose statistics were read from the Parquet footer
+    pub num_files_from_footer: u64,
+    /// Number of files whose statistics were computed by scanning the data
+    pub num_files_scanned: u64,
+    /// Number of candidate files left unchanged, e.g. because they have a deletion vector
+    pub num_files_skipped: u64,
+    /// Time taken by the operation in milliseconds
+    pub execution_time_ms: u64,
+}
+
+/// An active file whose statistics need to be collected
+struct Candidate {
+    /// The add action of the file
+    add: Add,
+    /// Location of the file in the table's object store
+    location: Path,
+    /// Partition values of the file, partition columns are excluded from statistics
+    partition_values: IndexMap<String, Scalar>,
+    /// The current statistics of the file, if any
+    stats: Option<Stats>,
+}
+
+/// How the statistics of a file were collected
+enum StatsSource {
+    Footer,
+    Scan,
+}
+
+impl super::Operation for ComputeStatsBuilder {
+    fn log_store(&self) -> &LogStoreRef {
+        &self.log_store
+    }
+    fn get_custom_execute_handler(&self) -> Option<Arc<dyn CustomExecuteHandler>> {
+        self.custom_execute_handler.clone()
+    }
+}
+
+impl ComputeStatsBuilder {
+    /// Create a new [`ComputeStatsBuilder`]
+    pub(crate) fn new(log_store: LogStoreRef, snapshot: Option<EagerSnapshot>) -> Self {
+        ComputeStatsBuilder {
+            snapshot,
+            log_store,
+            recompute: false,
+            max_concurrent_tasks: num_cpus::get(),
+            commit_properties: CommitProperties::default(),
+            custom_execute_handler: None,
+        }
+    }
+
+    /// Recompute the statistics of all files, including those that already have statistics
+    pub fn with_recompute(mut self, recompute: bool) -> Self {
+        self.recompute = recompute;
+        self
+    }
+
+    /// Max number of files processed concurrently
+    pub fn with_max_concurrent_tasks(mut self, max_concurrent_tasks: usize) -> Self {
+        self.max_concurrent_tasks = max_concurrent_tasks;
+        self
+    }
+
+    /// Additional information to write to the commit
+    pub fn with_commit_properties(mut self, commit_properties: CommitProperties) -> Self {
+        self.commit_properties = commit_properties;
+        self
+    }
+
+    /// Set a custom execute handler, for pre and post execution
+    pub fn with_custom_execute_handler(mut self, handler: Arc<dyn CustomExecuteHandler>) -> Self {
+        self.custom_execute_handler = Some(handler);
+        self
+    }
+
+    /// Active files whose statistics need to be collected
+    async fn files_to_update(
+        &self,
+        snapshot: &EagerSnapshot,
+        metrics: &mut ComputeStatsMetrics,
+    ) -> DeltaResult<Vec<Candidate>> {
+        let partition_columns = snapshot.metadata().partition_columns().to_vec();
+        let stats_columns = snapshot
+            .table_properties()
+            .data_skipping_stats_columns
+            .as_ref()
+            .map(|cols| parse_stats_columns(&cols.iter().map(|c| c.to_string()).collect_vec()))
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|path| !partition_columns.contains(&path[0]))
+            .collect_vec();
+
+        let mut files = Vec::new();
+        let mut file_stream = snapshot.file_views(&self.log_store, None);
+        while let Some(file) = file_stream.next().await {
+            let file = file?;
+            let add = file.add_action();
+            let stats = add.get_stats().ok().flatten();
+            let complete = !self.recompute
+                && stats.as_ref().is_some_and(|stats| {
+                    stats_columns
+                        .iter()
+                        .all(|path| has_null_count(&stats.null_count, path))
+                });
+            if complete {
+                continue;
+            }
+            if file.deletion_vector_descriptor().is_some() {
+                // the number of records in the footer does not account for deleted rows
+                metrics.num_files_skipped += 1;
+                continue;
+            }
+            let partition_values = file
+                .partition_values()
+                .map(|data| {
+                    data.fields()
+                        .iter()
+                        .zip(data.values().iter())
+                        .map(|(field, value)| (field.name().to_string(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            files.push(Candidate {
+                add,
+                location: file.object_store_path(),
+                partition_values,
+                stats,
+            });
+        }
+        Ok(files)
+    }
+}
+
+/// Whether the null counts contain a value for the column at `path`
+fn has_null_count(null_count: &HashMap<String, ColumnCountStat>, path: &[String]) -> bool {
+    let Some((name, rest)) = path.split_first() else {
+        return true;
+    };
+    match null_count.get(name) {
+        Some(ColumnCountStat::Column(children)) => {
+            !children.is_empty() && has_null_count(children, rest)
+        }
+        Some(ColumnCountStat::Value(_)) => true,
+        None => false,
+    }
+}
+
+/// Collect the statistics of a single file from its footer, or by scanning it
+async fn compute_file_stats(
+    object_store: Arc<dyn ObjectStore>,
+    file: &Candidate,
+    snapshot: &EagerSnapshot,
+) -> DeltaResult<(Stats, StatsSource)> {
+    let reader = ParquetObjectReader::new(object_store, file.location.clone())
+        .with_file_size(file.add.size as u64);
+    let builder = ParquetRecordBatchStreamBuilder::new(reader).await?;
+
+    let has_footer_stats = builder.metadata().row_groups().iter().all(|row_group| {
+        row_group
+            .columns()
+            .iter()
+            .all(|column| column.statistics().is_some())
+    });
+    let props = snapshot.table_properties();
+    let stats_columns = props
+        .data_skipping_stats_columns
+        .as_ref()
+        .map(|cols| cols.iter().map(|c| c.to_string()).collect_vec());
+    if has_footer_stats {
+        let stats = stats_from_parquet_metadata(
+            &file.partition_values,
+            builder.metadata(),
+            props.num_indexed_cols(),
+            &stats_columns,
+        )?;
+        return Ok((stats, StatsSource::Footer));
+    }
+
+    debug!("scanning {} for statistics", file.add.path);
+    let mut stats = RecordBatchStats::new(builder.metadata().file_metadata().schema_descr_ptr());
+    let mut stream = builder.build()?;
+    while let Some(batch) = stream.try_next().await? {
+        stats.update(&batch)?;
+    }
+    let stats = stats.finish(
+        &file.partition_values,
+        props.num_indexed_cols(),
+        &stats_columns,
+    )?;
+    Ok((stats, StatsSource::Scan))
+}
+
+impl std::future::IntoFuture for ComputeStatsBuilder {
+    type Output = DeltaResult<(DeltaTable, ComputeStatsMetrics)>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let this = self;
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let snapshot =
+                resolve_snapshot(&this.log_store, this.snapshot.clone(), true, None).await?;
+
+            let mut metrics = ComputeStatsMetrics::default();
+            let files = this.files_to_update(&snapshot, &mut metrics).await?;
+
+            let object_store = this.log_store.object_store(None);
+            let results: Vec<_> = futures::stream::iter(files)
+                .map(|file| {
+                    let object_store = object_store.clone();
+                    let snapshot = &snapshot;
+                    async move {
+                        let result = compute_file_stats(object_store, &file, snapshot).await;
+                        result.map(|(stats, source)| (file, stats, source))
+                    }
+                })
+                .buffer_unordered(this.max_concurrent_tasks.max(1))
+                .try_collect()
+                .await?;
+
+            let mut actions = Vec::with_capacity(results.len());
+            for (file, stats, source) in results {
+                if file.stats.as_ref() == Some(&stats) {
+                    metrics.num_files_skipped += 1;
+                    continue;
+                }
+                match source {
+                    StatsSource::Footer => metrics.num_files_from_footer += 1,
+                    StatsSource::Scan => metrics.num_files_scanned += 1,
+                }
+                metrics.num_files_updated += 1;
+                actions.push(Action::Add(Add {
+                    data_change: false,
+                    stats: Some(serde_json::to_string(&stats)?),
+                    ..file.add
+                }));
+            }
+
+            if actions.is_empty() {
+                metrics.execution_time_ms = start.elapsed().as_millis() as u64;
+                return Ok((
+                    DeltaTable::new_with_state(this.log_store, DeltaTableState::new(snapshot)),
+                    metrics,
+                ));
+            }
+
+            let operation_id = this.get_operation_id();
+            this.pre_execute(operation_id).await?;
+
+            metrics.execution_time_ms = start.elapsed().as_millis() as u64;
+            let mut commit_properties = this.commit_properties.clone();
+            commit_properties
+                .app_metadata
+                .insert("readVersion".to_owned(), snapshot.version().into());
+            commit_properties.app_metadata.insert(
+                "operationMetrics".to_owned(),
+                serde_json::to_value(&metrics)?,
+            );
+
+            let commit = CommitBuilder::from(commit_properties)
+                .with_operation_id(operation_id)
+                .with_post_commit_hook_handler(this.get_custom_execute_handler())
+                .with_actions(actions)
+                .build(
+                    Some(&snapshot),
+                    this.log_store.clone(),
+                    DeltaOperation::ComputeStats {
+                        recompute: this.recompute,
+                    },
+                )
+                .await?;
+
+            this.post_execute(operation_id).await?;
+
+            Ok((
+                DeltaTable::new_with_state(this.log_store, commit.snapshot()),
+                metrics,
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn null_counts(value: serde_json::Value) -> HashMap<String, ColumnCountStat> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_has_null_count() {
+        let counts = null_counts(serde_json::json!({"id": 0, "meta": {"kafka": {"offset": 1}}}));
+        let path = |p: &str| p.split('.').map(String::from).collect_vec();
+
+        assert!(has_null_count(&counts, &path("id")));
+        assert!(has_null_count(&counts, &path("meta")));
+        assert!(has_null_count(&counts, &path("meta.kafka.offset")));
+        assert!(!has_null_count(&counts, &path("meta.kafka.partition")));
+        assert!(!has_null_count(&counts, &path("value")));
+    }
+}
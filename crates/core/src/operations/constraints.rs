@@ -184,8 +184,8 @@ impl std::future::IntoFuture for ConstraintBuilder {
                 );
             }
             let constraints_checker: Vec<Constraint> = constraints_sql_mapper
-                .values()
-                .map(|sql| Constraint::new("*", sql))
+                .iter()
+                .map(|(name, sql)| Constraint::new(name, sql))
                 .collect();
 
             let plan = DataValidationExec::try_new_with_predicates(
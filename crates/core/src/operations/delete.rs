@@ -59,7 +59,7 @@ use crate::delta_datafusion::logical::{
 };
 use crate::delta_datafusion::physical::{MetricObserverExec, find_metric_node, get_metric};
 use crate::delta_datafusion::{Expression, update_datafusion_session};
-use crate::delta_datafusion::{create_session, scan_files_where_matches};
+use crate::delta_datafusion::{create_session, file_skipping_predicate, scan_files_where_matches};
 use crate::errors::DeltaResult;
 use crate::kernel::transaction::{CommitBuilder, CommitProperties, PROTOCOL};
 use crate::kernel::{Action, EagerSnapshot, resolve_snapshot};
@@ -183,8 +183,11 @@ impl std::future::IntoFuture for DeleteBuilder {
         let mut this = self;
 
         Box::pin(async move {
+            // snapshots loaded without files only load the files matching the predicate
+            let require_files = this.snapshot.is_none();
             let snapshot =
-                resolve_snapshot(&this.log_store, this.snapshot.clone(), true, None).await?;
+                resolve_snapshot(&this.log_store, this.snapshot.clone(), require_files, None)
+                    .await?;
             PROTOCOL.check_append_only(&snapshot)?;
             PROTOCOL.can_write_to(&snapshot)?;
 
@@ -202,6 +205,13 @@ impl std::future::IntoFuture for DeleteBuilder {
                 .predicate
                 .map(|p| p.resolve(session.as_ref(), snapshot.arrow_schema().to_dfschema_ref()?))
                 .transpose()?;
+            let skipping_predicate = predicate
+                .as_ref()
+                .map(file_skipping_predicate)
+                .transpose()?;
+            let snapshot = snapshot
+                .with_files_matching(&this.log_store, skipping_predicate)
+                .await?;
 
             let operation = DeltaOperation::Delete {
                 predicate: predicate.as_ref().map(|p| fmt_expr_to_sql(p)).transpose()?,
@@ -212,6 +222,7 @@ impl std::future::IntoFuture for DeleteBuilder {
                 this.log_store.clone(),
                 snapshot.clone(),
                 session.as_ref(),
+                this.writer_properties,
                 operation_id,
             )
             .await?;
@@ -303,6 +314,7 @@ async fn execute(
     log_store: LogStoreRef,
     snapshot: EagerSnapshot,
     session: &dyn Session,
+    writer_properties: Option<WriterProperties>,
     operation_id: Uuid,
 ) -> DeltaResult<(Vec<Action>, DeleteMetrics)> {
     let exec_start = Instant::now();
@@ -402,6 +414,7 @@ async fn execute(
         log_store.as_ref(),
         snapshot.table_configuration(),
         exec.clone(),
+        writer_properties,
         Some(operation_id),
         write_cdc,
     )
@@ -434,7 +447,7 @@ mod tests {
     use crate::writer::test_utils::{
         get_arrow_schema, get_delta_schema, get_record_batch, setup_table_with_configuration,
     };
-    use crate::{DeltaResult, DeltaTable, TableProperty};
+    use crate::{DeltaResult, DeltaTable, DeltaTableConfig, TableProperty};
     use arrow::array::Int32Array;
     use arrow::datatypes::TimestampMicrosecondType;
     use arrow::datatypes::{Field, Schema};
@@ -796,6 +809,69 @@ mod tests {
         assert_batches_sorted_eq!(&expected, &actual);
     }
 
+    #[tokio::test]
+    async fn test_delete_without_files() -> DeltaResult<()> {
+        // Perform a delete on a snapshot loaded without files
+        let schema = get_arrow_schema(&None);
+        let table = setup_table(Some(["modified"].to_vec())).await;
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(arrow::array::StringArray::from(vec!["A", "B", "A", "A"])),
+                Arc::new(arrow::array::Int32Array::from(vec![0, 20, 10, 100])),
+                Arc::new(arrow::array::StringArray::from(vec![
+                    "2021-02-02",
+                    "2021-02-03",
+                    "2021-02-02",
+                    "2021-02-03",
+                ])),
+            ],
+        )
+        .unwrap();
+        let table = table
+            .write(vec![batch])
+            .with_save_mode(SaveMode::Append)
+            .await?;
+
+        let log_store = table.log_store();
+        let mut config = DeltaTableConfig::default();
+        config.require_files = false;
+        let state = DeltaTableState::try_new(log_store.as_ref(), config, None).await?;
+        let table = DeltaTable::new_with_state(log_store.clone(), state);
+
+        let (table, metrics) = table
+            .delete()
+            .with_predicate(
+                col("modified")
+                    .eq(lit("2021-02-03"))
+                    .and(col("value").gt(lit(50))),
+            )
+            .await?;
+        assert_eq!(table.version(), Some(2));
+        assert!(!table.snapshot()?.load_config().require_files);
+        assert_eq!(metrics.num_added_files, 1);
+        assert_eq!(metrics.num_removed_files, 1);
+        assert_eq!(metrics.num_deleted_rows, 1);
+        assert_eq!(metrics.num_copied_rows, 1);
+
+        let state =
+            DeltaTableState::try_new(log_store.as_ref(), DeltaTableConfig::default(), None).await?;
+        let table = DeltaTable::new_with_state(log_store, state);
+        let expected = [
+            "+----+-------+------------+",
+            "| id | value | modified   |",
+            "+----+-------+------------+",
+            "| A  | 0     | 2021-02-02 |",
+            "| A  | 10    | 2021-02-02 |",
+            "| B  | 20    | 2021-02-03 |",
+            "+----+-------+------------+",
+        ];
+        let actual = get_data(&table).await;
+        assert_batches_sorted_eq!(&expected, &actual);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_delete_on_mixed_columns() {
         // Test predicates that contain non-partition and partition column
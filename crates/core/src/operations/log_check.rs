@@ -0,0 +1,890 @@
+//! Audit the transaction log of a Delta Table and repair what can be repaired.
+//!
+//! Where [`FileSystemCheckBuilder`](super::filesystem_check::FileSystemCheckBuilder) audits the
+//! data files referenced by the log, this operation audits the log itself. It reports
+//! - checkpoints that cannot be read, and multi-part checkpoints with missing parts
+//! - gaps in the sequence of commits
+//! - a `_last_checkpoint` file that cannot be parsed or points at a checkpoint that does not exist
+//! - commits that cannot be parsed
+//! - commits that add the same file more than once
+//!
+//! Unless run as a dry run, bad checkpoints are regenerated from the log, moving their files to
+//! the `_delta_quarantine/<operation id>` directory of the table, and `_last_checkpoint` is
+//! rewritten to point at the latest valid checkpoint. Bad checkpoints are only replaced if the
+//! commits needed to write them can be read, and stay in place if writing them fails. Missing or
+//! unparseable commits cannot be repaired and are only reported.
+//!
+//! Only files that cannot be decoded are reported as corrupt, errors reading from the object
+//! store fail the operation.
+//!
+//! The table does not need to be loadable, the operation only lists and reads the log.
+//!
+//! # Example
+//! ```rust ignore
+//! let table = DeltaTableBuilder::from_url(table_url)?.build()?;
+//! let (table, report) = table.log_check().await?;
+//! ````
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, LazyLock};
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use futures::future::BoxFuture;
+use object_store::path::Path;
+use object_store::{Error as ObjectStoreError, ObjectMeta, ObjectStore};
+use parquet::arrow::async_reader::{ParquetObjectReader, ParquetRecordBatchStreamBuilder};
+use parquet::errors::ParquetError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::*;
+use uuid::Uuid;
+
+use super::CustomExecuteHandler;
+use super::Operation;
+use crate::errors::DeltaResult;
+use crate::kernel::Action;
+use crate::logstore::{LogStore, LogStoreRef, get_actions};
+use crate::protocol::checkpoints::create_checkpoint_for;
+use crate::{DeltaTable, DeltaTableConfig};
+
+static COMMIT_FILE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{20})\.json$").unwrap());
+
+/// Matches classic, multi-part and V2 checkpoint file names, capturing the version and, for
+/// multi-part checkpoints, the part and number of parts.
+static CHECKPOINT_FILE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{20})\.checkpoint(?:\.(\d{10})\.(\d{10})|\.[^.]+)?\.(?:parquet|json)$")
+        .unwrap()
+});
+
+const LAST_CHECKPOINT_FILE_NAME: &str = "_last_checkpoint";
+
+/// Directory in the table root bad checkpoints are moved to, in a subdirectory per run
+const QUARANTINE_DIR: &str = "_delta_quarantine";
+
+/// Audit the transaction log and repair bad checkpoints and `_last_checkpoint`.
+/// See this module's documentation for more information
+pub struct LogCheckBuilder {
+    /// Delta object store for handling data files
+    log_store: LogStoreRef,
+    /// Configuration used to load the table once the log was checked
+    config: DeltaTableConfig,
+    /// Only report problems, don't repair them
+    dry_run: bool,
+    custom_execute_handler: Option<Arc<dyn CustomExecuteHandler>>,
+}
+
+/// Report of the log check operation
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCheckReport {
+    /// Was this a dry run
+    pub dry_run: bool,
+    /// Problems found in the log
+    pub issues: Vec<LogIssue>,
+    /// Repairs applied to the log, empty for a dry run
+    pub repairs: Vec<LogRepair>,
+}
+
+impl LogCheckReport {
+    /// Whether no problems were found in the log
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A problem found in the transaction log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LogIssue {
+    /// A checkpoint file cannot be read
+    #[serde(rename_all = "camelCase")]
+    CorruptCheckpoint {
+        /// Version of the checkpoint
+        version: i64,
+        /// Path of the unreadable file
+        path: String,
+        /// Why the file cannot be read
+        error: String,
+    },
+    /// Parts of a multi-part checkpoint are missing
+    #[serde(rename_all = "camelCase")]
+    PartialCheckpoint {
+        /// Version of the checkpoint
+        version: i64,
+        /// Number of parts found
+        parts_found: u32,
+        /// Number of parts the checkpoint consists of
+        parts_expected: u32,
+    },
+    /// Commits are missing from the log
+    #[serde(rename_all = "camelCase")]
+    MissingCommits {
+        /// First missing version
+        from_version: i64,
+        /// Last missing version
+        to_version: i64,
+    },
+    /// A commit cannot be parsed
+    #[serde(rename_all = "camelCase")]
+    UnparseableCommit {
+        /// Version of the commit
+        version: i64,
+        /// Why the commit cannot be parsed
+        error: String,
+    },
+    /// A commit adds the same file more than once
+    #[serde(rename_all = "camelCase")]
+    DuplicateAdd {
+        /// Version of the commit
+        version: i64,
+        /// Path of the file
+        path: String,
+    },
+    /// The `_last_checkpoint` file cannot be parsed
+    #[serde(rename_all = "camelCase")]
+    InvalidLastCheckpoint {
+        /// Why the file cannot be parsed
+        error: String,
+    },
+    /// The `_last_checkpoint` file points at a checkpoint that does not exist or is not valid
+    #[serde(rename_all = "camelCase")]
+    MissingLastCheckpointTarget {
+        /// Version recorded in `_last_checkpoint`
+        version: i64,
+    },
+}
+
+/// A repair applied to the transaction log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LogRepair {
+    /// The files of a bad checkpoint were moved to the quarantine directory
+    #[serde(rename_all = "camelCase")]
+    QuarantinedCheckpoint {
+        /// Version of the checkpoint
+        version: i64,
+        /// Paths the files were moved to
+        paths: Vec<String>,
+    },
+    /// A checkpoint was written from the log
+    #[serde(rename_all = "camelCase")]
+    RegeneratedCheckpoint {
+        /// Version of the checkpoint
+        version: i64,
+    },
+    /// A bad checkpoint could not be written from the log and was kept in place
+    #[serde(rename_all = "camelCase")]
+    RegenerationFailed {
+        /// Version of the checkpoint
+        version: i64,
+        /// Why the checkpoint could not be written
+        error: String,
+    },
+    /// `_last_checkpoint` was rewritten, or removed if no valid checkpoint exists
+    #[serde(rename_all = "camelCase")]
+    RewroteLastCheckpoint {
+        /// Version `_last_checkpoint` points at now
+        version: Option<i64>,
+    },
+}
+
+/// The files of a single checkpoint
+struct CheckpointFiles {
+    version: i64,
+    /// Number of parts of a multi-part checkpoint
+    num_parts: Option<u32>,
+    files: Vec<ObjectMeta>,
+}
+
+/// A checkpoint all files of which could be read
+struct ValidCheckpoint {
+    version: i64,
+    num_parts: Option<u32>,
+    num_actions: u64,
+    size_in_bytes: u64,
+}
+
+#[derive(Deserialize)]
+struct LastCheckpointHint {
+    version: i64,
+    parts: Option<u32>,
+}
+
+/// The top level files of the `_delta_log` directory
+#[derive(Default)]
+struct LogListing {
+    commits: BTreeMap<i64, ObjectMeta>,
+    checkpoints: Vec<CheckpointFiles>,
+}
+
+impl super::Operation for LogCheckBuilder {
+    fn log_store(&self) -> &LogStoreRef {
+        &self.log_store
+    }
+    fn get_custom_execute_handler(&self) -> Option<Arc<dyn CustomExecuteHandler>> {
+        self.custom_execute_handler.clone()
+    }
+}
+
+impl LogCheckBuilder {
+    /// Create a new [`LogCheckBuilder`]
+    pub(crate) fn new(log_store: LogStoreRef, config: DeltaTableConfig) -> Self {
+        LogCheckBuilder {
+            log_store,
+            config,
+            dry_run: false,
+            custom_execute_handler: None,
+        }
+    }
+
+    /// Only report problems. A dry run will not modify the Delta log
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set a custom execute handler, for pre and post execution
+    pub fn with_custom_execute_handler(mut self, handler: Arc<dyn CustomExecuteHandler>) -> Self {
+        self.custom_execute_handler = Some(handler);
+        self
+    }
+}
+
+impl std::future::IntoFuture for LogCheckBuilder {
+    type Output = DeltaResult<(DeltaTable, LogCheckReport)>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let this = self;
+
+        Box::pin(async move {
+            let operation_id = this.get_operation_id();
+            this.pre_execute(operation_id).await?;
+
+            let log_store = this.log_store.as_ref();
+            let mut report = LogCheckReport {
+                dry_run: this.dry_run,
+                ..Default::default()
+            };
+
+            let listing = list_log(log_store).await?;
+            check_commits(log_store, &listing, &mut report.issues).await?;
+            let (valid, bad) = check_checkpoints(log_store, &listing, &mut report.issues).await?;
+            report.issues.extend(check_commit_gaps(&listing, &valid));
+            let hint_is_bad = check_last_checkpoint(log_store, &valid, &mut report.issues).await?;
+            info!(
+                commits = listing.commits.len(),
+                checkpoints = listing.checkpoints.len(),
+                issues = report.issues.len(),
+                "log check completed"
+            );
+
+            if !this.dry_run {
+                report.repairs = repair(
+                    log_store,
+                    &listing,
+                    &valid,
+                    bad,
+                    &report.issues,
+                    hint_is_bad,
+                    operation_id,
+                )
+                .await?;
+            }
+
+            this.post_execute(operation_id).await?;
+
+            let mut table = DeltaTable::new(this.log_store, this.config);
+            if let Err(err) = table.load().await {
+                warn!("table cannot be loaded after checking the log: {err}");
+            }
+            Ok((table, report))
+        })
+    }
+}
+
+/// List the commits and checkpoints in the `_delta_log` directory, ignoring its subdirectories
+/// and files whose version does not fit the supported range.
+async fn list_log(log_store: &dyn LogStore) -> DeltaResult<LogListing> {
+    let log_path = log_store.log_path();
+    let object_store = log_store.object_store(None);
+    let list_span = info_span!("list_log", operation = "log_check");
+    let files: Vec<ObjectMeta> = list_span
+        .in_scope(|| object_store.list(Some(log_path)))
+        .try_collect()
+        .await?;
+
+    let mut listing = LogListing::default();
+    for file in files {
+        let Some(name) = file.location.filename().map(str::to_string) else {
+            continue;
+        };
+        if file.location != log_path.child(name.as_str()) {
+            continue;
+        }
+        if let Some(captures) = COMMIT_FILE_REGEX.captures(&name) {
+            let Ok(version) = captures[1].parse::<i64>() else {
+                debug!("ignoring log file {name} with an out of range version");
+                continue;
+            };
+            listing.commits.insert(version, file);
+        } else if let Some(captures) = CHECKPOINT_FILE_REGEX.captures(&name) {
+            let version = captures[1].parse::<i64>();
+            let num_parts = captures
+                .get(3)
+                .map(|n| n.as_str().parse::<u32>())
+                .transpose();
+            let (Ok(version), Ok(num_parts)) = (version, num_parts) else {
+                debug!("ignoring log file {name} with an out of range version or part");
+                continue;
+            };
+            // parts of a multi-part checkpoint are grouped by the number of parts
+            match listing.checkpoints.iter_mut().find(|cp| {
+                num_parts.is_some() && cp.version == version && cp.num_parts == num_parts
+            }) {
+                Some(checkpoint) => checkpoint.files.push(file),
+                None => listing.checkpoints.push(CheckpointFiles {
+                    version,
+                    num_parts,
+                    files: vec![file],
+                }),
+            }
+        }
+    }
+    listing.checkpoints.sort_by_key(|cp| cp.version);
+    Ok(listing)
+}
+
+/// Parse every commit and check it adds each file at most once.
+async fn check_commits(
+    log_store: &dyn LogStore,
+    listing: &LogListing,
+    issues: &mut Vec<LogIssue>,
+) -> DeltaResult<()> {
+    let store = log_store.object_store(None);
+    for (version, file) in &listing.commits {
+        let bytes = match store.get(&file.location).await {
+            Ok(data) => data.bytes().await?,
+            // the commit was cleaned up while checking
+            Err(ObjectStoreError::NotFound { .. }) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let actions = match get_actions(*version, &bytes) {
+            Ok(actions) => actions,
+            Err(err) => {
+                issues.push(LogIssue::UnparseableCommit {
+                    version: *version,
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+
+        // files are identified by their path and deletion vector
+        let mut adds: HashMap<_, usize> = HashMap::new();
+        for action in actions {
+            if let Action::Add(add) = action {
+                let dv = add
+                    .deletion_vector
+                    .map(|dv| (dv.path_or_inline_dv, dv.offset));
+                *adds.entry((add.path, dv)).or_default() += 1;
+            }
+        }
+        let mut duplicates: Vec<_> = adds
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|((path, _), _)| path)
+            .collect();
+        duplicates.sort();
+        duplicates.dedup();
+        issues.extend(duplicates.into_iter().map(|path| LogIssue::DuplicateAdd {
+            version: *version,
+            path,
+        }));
+    }
+    Ok(())
+}
+
+/// Read every checkpoint, returning the valid checkpoints and the bad ones.
+async fn check_checkpoints<'a>(
+    log_store: &dyn LogStore,
+    listing: &'a LogListing,
+    issues: &mut Vec<LogIssue>,
+) -> DeltaResult<(Vec<ValidCheckpoint>, Vec<&'a CheckpointFiles>)> {
+    let store = log_store.object_store(None);
+    let mut valid = Vec::new();
+    let mut bad = Vec::new();
+    for checkpoint in &listing.checkpoints {
+        if let Some(num_parts) = checkpoint.num_parts
+            && checkpoint.files.len() as u32 != num_parts
+        {
+            issues.push(LogIssue::PartialCheckpoint {
+                version: checkpoint.version,
+                parts_found: checkpoint.files.len() as u32,
+                parts_expected: num_parts,
+            });
+            bad.push(checkpoint);
+            continue;
+        }
+
+        let mut num_actions = 0;
+        let mut corrupt = false;
+        for file in &checkpoint.files {
+            match count_checkpoint_actions(store.clone(), file).await? {
+                Ok(count) => num_actions += count,
+                Err(error) => {
+                    issues.push(LogIssue::CorruptCheckpoint {
+                        version: checkpoint.version,
+                        path: file.location.to_string(),
+                        error,
+                    });
+                    corrupt = true;
+                }
+            }
+        }
+        if !corrupt && num_actions == 0 {
+            issues.push(LogIssue::CorruptCheckpoint {
+                version: checkpoint.version,
+                path: checkpoint.files[0].location.to_string(),
+                error: "checkpoint contains no actions".to_string(),
+            });
+            corrupt = true;
+        }
+
+        if corrupt {
+            bad.push(checkpoint);
+        } else {
+            valid.push(ValidCheckpoint {
+                version: checkpoint.version,
+                num_parts: checkpoint.num_parts,
+                num_actions,
+                size_in_bytes: checkpoint.files.iter().map(|file| file.size).sum(),
+            });
+        }
+    }
+    Ok((valid, bad))
+}
+
+/// Count the actions of a checkpoint file, reading only the footer of parquet files.
+///
+/// Returns the reason the file cannot be decoded as the inner error. Errors reading the file
+/// from the object store are returned as the outer error.
+async fn count_checkpoint_actions(
+    store: Arc<dyn ObjectStore>,
+    file: &ObjectMeta,
+) -> DeltaResult<Result<u64, String>> {
+    if file.location.as_ref().ends_with(".json") {
+        let bytes = store.get(&file.location).await?.bytes().await?;
+        let mut count = 0;
+        for line in bytes.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+            if let Err(err) = serde_json::from_slice::<serde_json::Value>(line) {
+                return Ok(Err(err.to_string()));
+            }
+            count += 1;
+        }
+        return Ok(Ok(count));
+    }
+
+    let reader = ParquetObjectReader::new(store, file.location.clone()).with_file_size(file.size);
+    match ParquetRecordBatchStreamBuilder::new(reader).await {
+        Ok(builder) => Ok(Ok(builder.metadata().file_metadata().num_rows() as u64)),
+        // the reader surfaces object store errors as external errors
+        Err(ParquetError::External(err)) => match err.downcast::<ObjectStoreError>() {
+            Ok(err) => Err((*err).into()),
+            Err(err) => Ok(Err(err.to_string())),
+        },
+        Err(err) => Ok(Err(err.to_string())),
+    }
+}
+
+/// Whether the commits needed to write a checkpoint for `version` exist and can be parsed.
+///
+/// These are the commits following the latest valid checkpoint before the version, or all
+/// commits up to the version if there is none.
+fn is_replayable(
+    version: i64,
+    listing: &LogListing,
+    valid: &[ValidCheckpoint],
+    unparseable: &HashSet<i64>,
+) -> bool {
+    let first = valid
+        .iter()
+        .map(|cp| cp.version)
+        .filter(|cp_version| *cp_version < version)
+        .max()
+        .map_or(0, |cp_version| cp_version + 1);
+    (first..=version).all(|v| listing.commits.contains_key(&v) && !unparseable.contains(&v))
+}
+
+/// Move the files of a checkpoint to the quarantine directory, returning their new paths.
+async fn quarantine(
+    store: &dyn ObjectStore,
+    quarantine_dir: &Path,
+    checkpoint: &CheckpointFiles,
+) -> DeltaResult<Vec<Path>> {
+    let mut paths = Vec::with_capacity(checkpoint.files.len());
+    for file in &checkpoint.files {
+        let target = quarantine_dir.child(file.location.filename().unwrap());
+        debug!("quarantining checkpoint file {} to {target}", file.location);
+        store.rename(&file.location, &target).await?;
+        paths.push(target);
+    }
+    Ok(paths)
+}
+
+/// Find the versions missing from the log.
+///
+/// Commits must be contiguous, and readers need every commit after the latest valid checkpoint,
+/// or every commit from version 0 if there is none.
+fn check_commit_gaps(listing: &LogListing, valid: &[ValidCheckpoint]) -> Vec<LogIssue> {
+    let Some(first) = listing.commits.keys().next() else {
+        return Vec::new();
+    };
+    let required = valid
+        .iter()
+        .map(|cp| cp.version + 1)
+        .max()
+        .unwrap_or_default();
+
+    let mut issues = Vec::new();
+    let mut expected = required.min(*first);
+    for version in listing.commits.keys() {
+        if *version > expected {
+            issues.push(LogIssue::MissingCommits {
+                from_version: expected,
+                to_version: version - 1,
+            });
+        }
+        expected = expected.max(version + 1);
+    }
+    issues
+}
+
+/// Check `_last_checkpoint` points at a valid checkpoint, returns whether it needs to be rewritten.
+async fn check_last_checkpoint(
+    log_store: &dyn LogStore,
+    valid: &[ValidCheckpoint],
+    issues: &mut Vec<LogIssue>,
+) -> DeltaResult<bool> {
+    let path = log_store.log_path().child(LAST_CHECKPOINT_FILE_NAME);
+    let bytes = match log_store.object_store(None).get(&path).await {
+        Ok(data) => data.bytes().await?,
+        Err(ObjectStoreError::NotFound { .. }) => return Ok(false),
+        Err(err) => return Err(err.into()),
+    };
+    let hint: LastCheckpointHint = match serde_json::from_slice(&bytes) {
+        Ok(hint) => hint,
+        Err(err) => {
+            issues.push(LogIssue::InvalidLastCheckpoint {
+                error: err.to_string(),
+            });
+            return Ok(true);
+        }
+    };
+    let exists = valid.iter().any(|cp| {
+        cp.version == hint.version && (hint.parts.is_none() || cp.num_parts == hint.parts)
+    });
+    if !exists {
+        issues.push(LogIssue::MissingLastCheckpointTarget {
+            version: hint.version,
+        });
+    }
+    Ok(!exists)
+}
+
+/// Quarantine and regenerate bad checkpoints, and point `_last_checkpoint` at the latest valid
+/// checkpoint if needed.
+///
+/// Bad checkpoints are only quarantined if a valid checkpoint of the same version exists, or
+/// the commits needed to regenerate them can be read. If regenerating a checkpoint fails, its
+/// files are moved back.
+async fn repair(
+    log_store: &dyn LogStore,
+    listing: &LogListing,
+    valid: &[ValidCheckpoint],
+    bad: Vec<&CheckpointFiles>,
+    issues: &[LogIssue],
+    mut rewrite_hint: bool,
+    operation_id: Uuid,
+) -> DeltaResult<Vec<LogRepair>> {
+    let store = log_store.object_store(None);
+    let mut repairs = Vec::new();
+
+    let unparseable: HashSet<i64> = issues
+        .iter()
+        .filter_map(|issue| match issue {
+            LogIssue::UnparseableCommit { version, .. } => Some(*version),
+            _ => None,
+        })
+        .collect();
+    let mut bad_by_version: BTreeMap<i64, Vec<&CheckpointFiles>> = BTreeMap::new();
+    for checkpoint in bad {
+        bad_by_version
+            .entry(checkpoint.version)
+            .or_default()
+            .push(checkpoint);
+    }
+
+    let quarantine_dir = Path::from(QUARANTINE_DIR).child(operation_id.to_string());
+    for (version, checkpoints) in bad_by_version {
+        let regenerate = !valid.iter().any(|cp| cp.version == version);
+        if regenerate && !is_replayable(version, listing, valid, &unparseable) {
+            warn!("cannot regenerate checkpoint for version {version}, keeping it in place");
+            repairs.push(LogRepair::RegenerationFailed {
+                version,
+                error: "commits needed to write the checkpoint are missing or cannot be parsed"
+                    .to_string(),
+            });
+            continue;
+        }
+
+        let mut quarantined = Vec::with_capacity(checkpoints.len());
+        for checkpoint in &checkpoints {
+            quarantined.push(quarantine(store.as_ref(), &quarantine_dir, checkpoint).await?);
+        }
+        if regenerate
+            && let Err(err) =
+                create_checkpoint_for(version as u64, log_store, Some(operation_id)).await
+        {
+            warn!("cannot regenerate checkpoint for version {version}: {err}");
+            for (checkpoint, paths) in checkpoints.iter().zip(&quarantined) {
+                for (file, path) in checkpoint.files.iter().zip(paths) {
+                    store.rename(path, &file.location).await?;
+                }
+            }
+            repairs.push(LogRepair::RegenerationFailed {
+                version,
+                error: err.to_string(),
+            });
+            continue;
+        }
+
+        for paths in quarantined {
+            repairs.push(LogRepair::QuarantinedCheckpoint {
+                version,
+                paths: paths.iter().map(|path| path.to_string()).collect(),
+            });
+        }
+        if regenerate {
+            repairs.push(LogRepair::RegeneratedCheckpoint { version });
+            // regenerating writes `_last_checkpoint`, which may not be the latest checkpoint
+            rewrite_hint = true;
+        }
+    }
+
+    if rewrite_hint {
+        let path = log_store.log_path().child(LAST_CHECKPOINT_FILE_NAME);
+        let listing = list_log(log_store).await?;
+        let (valid, _) = check_checkpoints(log_store, &listing, &mut Vec::new()).await?;
+        let latest = valid.iter().max_by_key(|cp| cp.version);
+        match latest {
+            Some(checkpoint) => {
+                let mut hint = serde_json::json!({
+                    "version": checkpoint.version,
+                    "size": checkpoint.num_actions,
+                    "sizeInBytes": checkpoint.size_in_bytes,
+                });
+                if let Some(num_parts) = checkpoint.num_parts {
+                    hint["parts"] = num_parts.into();
+                }
+                store
+                    .put(&path, Bytes::from(hint.to_string()).into())
+                    .await?;
+            }
+            None => match store.delete(&path).await {
+                Ok(()) | Err(ObjectStoreError::NotFound { .. }) => (),
+                Err(err) => return Err(err.into()),
+            },
+        }
+        repairs.push(LogRepair::RewroteLastCheckpoint {
+            version: latest.map(|cp| cp.version),
+        });
+    }
+
+    Ok(repairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TableProperty;
+    use crate::protocol::checkpoints::create_checkpoint;
+    use crate::writer::test_utils::{get_delta_schema, setup_table_with_configuration};
+
+    async fn setup_table() -> DeltaResult<DeltaTable> {
+        let mut table =
+            setup_table_with_configuration(TableProperty::AppendOnly, Some("false")).await;
+        for append_only in ["true", "false"] {
+            table = table
+                .set_tbl_properties()
+                .with_properties([("delta.appendOnly".to_string(), append_only.to_string())].into())
+                .await?;
+        }
+        create_checkpoint(&table, None).await?;
+        Ok(table)
+    }
+
+    fn checkpoint_path(table: &DeltaTable, version: i64) -> Path {
+        table
+            .log_store()
+            .log_path()
+            .child(format!("{version:020}.checkpoint.parquet"))
+    }
+
+    #[tokio::test]
+    async fn test_log_check_healthy() -> DeltaResult<()> {
+        let table = setup_table().await?;
+        let (_, report) = table.log_check().await?;
+        assert!(report.is_healthy());
+        assert!(report.repairs.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_check_corrupt_checkpoint() -> DeltaResult<()> {
+        let table = setup_table().await?;
+        let log_store = table.log_store();
+        let store = log_store.object_store(None);
+        store
+            .put(
+                &checkpoint_path(&table, 2),
+                Bytes::from_static(b"not parquet").into(),
+            )
+            .await?;
+
+        let (_, report) = table.clone().log_check().with_dry_run(true).await?;
+        assert!(matches!(
+            report.issues.as_slice(),
+            [
+                LogIssue::CorruptCheckpoint { version: 2, .. },
+                LogIssue::MissingLastCheckpointTarget { version: 2 }
+            ]
+        ));
+        assert!(report.repairs.is_empty());
+
+        let (table, report) = table.log_check().await?;
+        assert_eq!(report.repairs.len(), 3);
+        let LogRepair::QuarantinedCheckpoint { version: 2, paths } = &report.repairs[0] else {
+            panic!(
+                "expected a quarantined checkpoint, got {:?}",
+                report.repairs[0]
+            );
+        };
+        assert_eq!(
+            report.repairs[1],
+            LogRepair::RegeneratedCheckpoint { version: 2 }
+        );
+        assert_eq!(
+            report.repairs[2],
+            LogRepair::RewroteLastCheckpoint { version: Some(2) }
+        );
+        assert_eq!(paths.len(), 1);
+        let quarantined = Path::from(paths[0].as_str());
+        let parts: Vec<_> = quarantined.parts().collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].as_ref(), QUARANTINE_DIR);
+        assert_eq!(parts[2].as_ref(), "00000000000000000002.checkpoint.parquet");
+        assert!(store.head(&quarantined).await.is_ok());
+        assert_eq!(table.version(), Some(2));
+
+        let (_, report) = table.log_check().await?;
+        assert!(report.is_healthy());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_check_regeneration_failed() -> DeltaResult<()> {
+        let table = setup_table().await?;
+        let log_store = table.log_store();
+        let store = log_store.object_store(None);
+        store
+            .put(
+                &checkpoint_path(&table, 2),
+                Bytes::from_static(b"not parquet").into(),
+            )
+            .await?;
+        // without the checkpoint and the first commits the version cannot be reconstructed
+        for version in 0..2 {
+            store
+                .delete(&log_store.log_path().child(format!("{version:020}.json")))
+                .await?;
+        }
+
+        let (_, report) = table.log_check().await?;
+        assert_eq!(report.repairs.len(), 2);
+        assert!(matches!(
+            report.repairs[0],
+            LogRepair::RegenerationFailed { version: 2, .. }
+        ));
+        assert_eq!(
+            report.repairs[1],
+            LogRepair::RewroteLastCheckpoint { version: None }
+        );
+        // the checkpoint is not quarantined, it is the only record of the first versions
+        assert!(store.head(&checkpoint_path(&table, 2)).await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_check_commits() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let log_store = table.log_store();
+        let store = log_store.object_store(None);
+        let add = r#"{"add":{"path":"part-1.parquet","partitionValues":{},"size":1,"modificationTime":1,"dataChange":true}}"#;
+        store
+            .put(
+                &log_store.log_path().child(format!("{:020}.json", 1)),
+                Bytes::from(format!("{add}\n{add}\n")).into(),
+            )
+            .await?;
+        store
+            .put(
+                &log_store.log_path().child(format!("{:020}.json", 3)),
+                Bytes::from_static(b"{not json").into(),
+            )
+            .await?;
+        // versions beyond the supported range are ignored
+        store
+            .put(
+                &log_store.log_path().child("99999999999999999999.json"),
+                Bytes::from_static(b"{not json").into(),
+            )
+            .await?;
+        store
+            .put(
+                &log_store
+                    .log_path()
+                    .child("00000000000000000001.checkpoint.0000000001.9999999999.parquet"),
+                Bytes::from_static(b"not parquet").into(),
+            )
+            .await?;
+
+        let (_, report) = table.log_check().await?;
+        assert_eq!(
+            report.issues[0],
+            LogIssue::DuplicateAdd {
+                version: 1,
+                path: "part-1.parquet".to_string()
+            }
+        );
+        assert!(matches!(
+            report.issues[1],
+            LogIssue::UnparseableCommit { version: 3, .. }
+        ));
+        assert_eq!(
+            report.issues[2],
+            LogIssue::MissingCommits {
+                from_version: 2,
+                to_version: 2
+            }
+        );
+        assert_eq!(report.issues.len(), 3);
+        assert!(report.repairs.is_empty());
+        Ok(())
+    }
+}
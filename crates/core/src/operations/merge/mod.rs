@@ -85,11 +85,12 @@ use crate::kernel::{Action, EagerSnapshot, StructTypeExt, new_metadata, resolve_
 use crate::logstore::LogStoreRef;
 use crate::operations::cdc::*;
 use crate::operations::merge::barrier::find_node;
-use crate::operations::write::WriterStatsConfig;
-use crate::operations::write::execution::write_execution_plan_v2;
+use crate::operations::write::column_defaults::{cd_is_enabled, column_default_exprs};
+use crate::operations::write::execution::{optimize_write_plan, write_execution_plan_v2};
 use crate::operations::write::generated_columns::{
     add_generated_columns, add_missing_generated_columns, gc_is_enabled,
 };
+use crate::operations::write::{OptimizedWrite, WriterStatsConfig};
 use crate::protocol::{DeltaOperation, MergePredicate};
 use crate::table::config::TablePropertiesExt as _;
 use crate::table::state::DeltaTableState;
@@ -147,6 +148,8 @@ pub struct MergeBuilder {
     state: Option<Arc<dyn Session>>,
     /// Properties passed to underlying parquet writer for when files are rewritten
     writer_properties: Option<WriterProperties>,
+    /// Shuffle the merge output by partition values before writing, defaults to the table configuration
+    optimized_write: Option<OptimizedWrite>,
     /// Additional information to add to the commit
     commit_properties: CommitProperties,
     /// safe_cast determines how data types that do not match the underlying table are handled
@@ -183,6 +186,7 @@ impl MergeBuilder {
             state: None,
             commit_properties: CommitProperties::default(),
             writer_properties: None,
+            optimized_write: None,
             merge_schema: false,
             match_operations: Vec::new(),
             not_match_operations: Vec::new(),
@@ -410,6 +414,13 @@ impl MergeBuilder {
         self
     }
 
+    /// Shuffle the merge output by the table's partition columns before writing, see
+    /// [`OptimizedWrite`]. Overrides `delta.autoOptimize.optimizeWrite` for this merge.
+    pub fn with_optimized_write(mut self, optimized_write: OptimizedWrite) -> Self {
+        self.optimized_write = Some(optimized_write);
+        self
+    }
+
     /// Specify the cast options to use when casting columns that do not match
     /// the table's schema.  When `cast_options.safe` is set true then any
     /// failures to cast a datatype will use null instead of returning an error
@@ -483,7 +494,7 @@ impl InsertBuilder {
     }
 
     /// Which values to insert into the target tables. If a target column is not
-    /// specified then null is inserted.
+    /// specified then its default value is inserted, or null if it has none.
     pub fn set<C: Into<DeltaColumn>, E: Into<Expression>>(
         mut self,
         column: C,
@@ -750,6 +761,7 @@ async fn execute(
     snapshot: EagerSnapshot,
     state: SessionState,
     writer_properties: Option<WriterProperties>,
+    optimized_write: Option<OptimizedWrite>,
     mut commit_properties: CommitProperties,
     _safe_cast: bool,
     streaming: bool,
@@ -819,6 +831,15 @@ async fn execute(
         generated_col_exp = Some(generated_col_expressions);
         missing_generated_col = Some(missing_generated_columns);
     }
+
+    // Columns left out of an insert clause are filled with their default value
+    let column_defaults: HashMap<String, Expr> = if cd_is_enabled(&snapshot) {
+        column_default_exprs(&state, &snapshot.schema().get_column_defaults()?)?
+            .into_iter()
+            .collect()
+    } else {
+        HashMap::new()
+    };
     // This is only done to provide the source columns with a correct table reference. Just renaming the columns does not work
     let source = LogicalPlanBuilder::scan(
         source_name.clone(),
@@ -1156,11 +1177,13 @@ async fn execute(
             Column::new(source_qualifier.clone(), name)
         };
 
-        for (idx, (operations, _)) in ops.iter().enumerate() {
-            let op = operations
-                .get(&column)
-                .map(|expr| expr.to_owned())
-                .unwrap_or_else(|| col(column.clone()));
+        let column_default = column_defaults.get(delta_field.name());
+        for (idx, (operations, r#type)) in ops.iter().enumerate() {
+            let op = match (operations.get(&column), r#type, column_default) {
+                (Some(expr), _, _) => expr.to_owned(),
+                (None, OperationType::Insert, Some(default)) => default.to_owned(),
+                (None, _, _) => col(column.clone()),
+            };
 
             when_expr.push(lit(idx as i32));
             then_expr.push(op);
@@ -1396,7 +1419,23 @@ async fn execute(
     let scan_count = find_node::<DeltaScan>(&write).ok_or_else(err)?;
 
     let table_partition_cols = current_metadata.partition_columns().clone();
+    let writer_properties = writer_properties.or_else(|| {
+        snapshot
+            .table_properties()
+            .parquet_writer_settings()
+            .writer_properties()
+    });
     let writer_stats_config = WriterStatsConfig::from_config(snapshot.table_configuration());
+    let target_file_size = Some(snapshot.table_properties().target_file_size().get() as usize);
+    let optimized_write =
+        optimized_write.unwrap_or_else(|| snapshot.table_properties().optimize_write().into());
+    let write = optimize_write_plan(
+        &state,
+        write,
+        &table_partition_cols,
+        optimized_write,
+        target_file_size,
+    )?;
 
     let (mut actions, write_plan_metrics) = write_execution_plan_v2(
         Some(&snapshot),
@@ -1404,7 +1443,7 @@ async fn execute(
         write,
         table_partition_cols.clone(),
         log_store.object_store(Some(operation_id)),
-        Some(snapshot.table_properties().target_file_size().get() as usize),
+        target_file_size,
         None,
         writer_properties.clone(),
         writer_stats_config.clone(),
@@ -1567,6 +1606,7 @@ impl std::future::IntoFuture for MergeBuilder {
                 snapshot,
                 state,
                 this.writer_properties,
+                this.optimized_write,
                 this.commit_properties,
                 this.safe_cast,
                 this.streaming,
@@ -3479,6 +3519,73 @@ mod tests {
         assert_batches_sorted_eq!(&expected, &actual);
     }
 
+    #[tokio::test]
+    async fn test_merge_insert_column_defaults() {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_column("id", DataType::Primitive(PrimitiveType::String), true, None)
+            .with_column(
+                "value",
+                DataType::Primitive(PrimitiveType::Integer),
+                true,
+                Some(std::collections::HashMap::from([(
+                    "CURRENT_DEFAULT".to_string(),
+                    json!("-1"),
+                )])),
+            )
+            .await
+            .unwrap();
+
+        let batch = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![
+                Field::new("id", ArrowDataType::Utf8, true),
+                Field::new("value", ArrowDataType::Int32, true),
+            ])),
+            vec![
+                Arc::new(arrow::array::StringArray::from(vec!["A"])),
+                Arc::new(arrow::array::Int32Array::from(vec![1])),
+            ],
+        )
+        .unwrap();
+        let table = table.write(vec![batch]).await.unwrap();
+
+        let ctx = SessionContext::new();
+        let source = ctx
+            .read_batch(
+                RecordBatch::try_new(
+                    Arc::new(ArrowSchema::new(vec![Field::new(
+                        "id",
+                        ArrowDataType::Utf8,
+                        true,
+                    )])),
+                    vec![Arc::new(arrow::array::StringArray::from(vec!["A", "B"]))],
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let (table, metrics) = table
+            .merge(source, col("target.id").eq(col("source.id")))
+            .with_source_alias("source")
+            .with_target_alias("target")
+            .when_not_matched_insert(|insert| insert.set("id", col("source.id")))
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(metrics.num_target_rows_inserted, 1);
+
+        let expected = vec![
+            "+----+-------+",
+            "| id | value |",
+            "+----+-------+",
+            "| A  | 1     |",
+            "| B  | -1    |",
+            "+----+-------+",
+        ];
+        let actual = get_data(&table).await;
+        assert_batches_sorted_eq!(&expected, &actual);
+    }
+
     #[tokio::test]
     async fn test_empty_table_with_schema_merge() {
         let schema = Arc::new(ArrowSchema::new(vec![
@@ -15,15 +15,19 @@ use async_trait::async_trait;
 #[cfg(feature = "datafusion")]
 pub use datafusion::physical_plan::common::collect as collect_sendable_stream;
 use delta_kernel::table_properties::{DataSkippingNumIndexedCols, TableProperties};
+use parquet::file::properties::WriterProperties;
 use url::Url;
 use uuid::Uuid;
 
 use self::{
-    add_column::AddColumnBuilder, add_feature::AddTableFeatureBuilder, create::CreateBuilder,
-    filesystem_check::FileSystemCheckBuilder, restore::RestoreBuilder,
+    add_column::AddColumnBuilder, add_feature::AddTableFeatureBuilder,
+    compute_stats::ComputeStatsBuilder, create::CreateBuilder,
+    filesystem_check::FileSystemCheckBuilder, log_check::LogCheckBuilder,
+    rebuild_partition_stats::RebuildPartitionStatsBuilder, restore::RestoreBuilder,
     set_tbl_properties::SetTablePropertiesBuilder,
     update_field_metadata::UpdateFieldMetadataBuilder,
     update_table_metadata::UpdateTableMetadataBuilder, vacuum::VacuumBuilder,
+    validate_checksum::ValidateChecksumBuilder,
 };
 #[cfg(feature = "datafusion")]
 use self::{
@@ -38,19 +42,26 @@ use crate::errors::{DeltaResult, DeltaTableError};
 use crate::logstore::LogStoreRef;
 use crate::operations::generate::GenerateBuilder;
 use crate::table::builder::DeltaTableBuilder;
-use crate::table::config::{DEFAULT_NUM_INDEX_COLS, TablePropertiesExt as _};
+use crate::table::config::{
+    BloomFilterColumn, DEFAULT_NUM_INDEX_COLS, TablePropertiesExt as _, TableProperty,
+    parse_bloom_filter_columns, parse_bool, parse_parquet_writer_settings,
+};
 
 pub mod add_column;
 pub mod add_feature;
+pub mod compute_stats;
 pub mod convert_to_delta;
 pub mod create;
 pub mod drop_constraints;
 pub mod filesystem_check;
 pub mod generate;
+pub mod log_check;
+pub mod rebuild_partition_stats;
 pub mod restore;
 pub mod update_field_metadata;
 pub mod update_table_metadata;
 pub mod vacuum;
+pub mod validate_checksum;
 
 #[cfg(feature = "datafusion")]
 mod cdc;
@@ -138,6 +149,30 @@ impl DeltaTable {
         FileSystemCheckBuilder::new(self.log_store(), self.state.clone().map(|s| s.snapshot))
     }
 
+    /// Audit the transaction log and repair bad checkpoints
+    #[must_use]
+    pub fn log_check(self) -> LogCheckBuilder {
+        LogCheckBuilder::new(self.log_store(), self.config.clone())
+    }
+
+    /// Validate the table state against its version checksum file
+    #[must_use]
+    pub fn validate_checksum(self) -> ValidateChecksumBuilder {
+        ValidateChecksumBuilder::new(self.log_store(), self.state.clone().map(|s| s.snapshot))
+    }
+
+    /// Collect missing file statistics and re-add the affected files
+    #[must_use]
+    pub fn compute_stats(self) -> ComputeStatsBuilder {
+        ComputeStatsBuilder::new(self.log_store(), self.state.clone().map(|s| s.snapshot))
+    }
+
+    /// Recompute the partition statistics summary from the active files of the table
+    #[must_use]
+    pub fn rebuild_partition_stats(self) -> RebuildPartitionStatsBuilder {
+        RebuildPartitionStatsBuilder::new(self.log_store(), self.state.clone().map(|s| s.snapshot))
+    }
+
     /// Enable a table feature for a table
     #[must_use]
     pub fn add_feature(self) -> AddTableFeatureBuilder {
@@ -577,6 +612,57 @@ pub fn get_num_idx_cols_and_stats_columns(
     )
 }
 
+/// Get the columns to write parquet bloom filters for from the table configuration in the state
+/// If table_config does not exist (only can occur in the first write action) it takes
+/// the configuration that was passed to the writerBuilder.
+pub fn get_bloom_filter_columns(
+    config: Option<&TableProperties>,
+    configuration: &HashMap<String, Option<String>>,
+) -> Vec<BloomFilterColumn> {
+    match config {
+        Some(conf) => conf.bloom_filter_columns(),
+        None => parse_bloom_filter_columns(
+            configuration
+                .iter()
+                .filter_map(|(key, value)| Some((key.as_str(), value.as_deref()?))),
+        ),
+    }
+}
+
+/// Get the default parquet writer properties from the table configuration in the state
+/// If table_config does not exist (only can occur in the first write action) it takes
+/// the configuration that was passed to the writerBuilder.
+pub fn get_writer_properties(
+    config: Option<&TableProperties>,
+    configuration: &HashMap<String, Option<String>>,
+) -> Option<WriterProperties> {
+    let settings = match config {
+        Some(conf) => conf.parquet_writer_settings(),
+        None => parse_parquet_writer_settings(
+            configuration
+                .iter()
+                .filter_map(|(key, value)| Some((key.as_str(), value.as_deref()?))),
+        ),
+    };
+    settings.writer_properties()
+}
+
+/// Get whether optimized writes are enabled from the table configuration in the state
+/// If table_config does not exist (only can occur in the first write action) it takes
+/// the configuration that was passed to the writerBuilder.
+pub fn get_optimize_write(
+    config: Option<&TableProperties>,
+    configuration: &HashMap<String, Option<String>>,
+) -> bool {
+    match config {
+        Some(conf) => conf.optimize_write(),
+        None => configuration
+            .get(TableProperty::AutoOptimizeOptimizeWrite.as_ref())
+            .and_then(|v| v.as_deref())
+            .is_some_and(parse_bool),
+    }
+}
+
 /// Get the target_file_size from the table configuration in the sates
 /// If table_config does not exist (only can occur in the first write action) it takes
 /// the configuration that was passed to the writerBuilder.
@@ -46,7 +46,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeErr
 use tracing::*;
 use uuid::Uuid;
 
-use super::write::writer::{PartitionWriter, PartitionWriterConfig};
+use super::write::writer::{PartitionWriter, PartitionWriterConfig, with_bloom_filters};
 use super::{CustomExecuteHandler, Operation};
 use crate::delta_datafusion::{DeltaRuntimeEnvBuilder, DeltaSessionContext, DeltaTableProvider};
 use crate::errors::{DeltaResult, DeltaTableError};
@@ -343,17 +343,35 @@ impl<'a> std::future::IntoFuture for OptimizeBuilder<'a> {
         let this = self;
 
         Box::pin(async move {
+            // snapshots loaded without files only load the files matching the filters
+            let require_files = this.snapshot.is_none();
             let snapshot =
-                resolve_snapshot(&this.log_store, this.snapshot.clone(), true, None).await?;
+                resolve_snapshot(&this.log_store, this.snapshot.clone(), require_files, None)
+                    .await?;
             PROTOCOL.can_write_to(&snapshot)?;
+            let predicate = if this.filters.is_empty() {
+                None
+            } else {
+                Some(Arc::new(to_kernel_predicate(
+                    this.filters,
+                    snapshot.schema().as_ref(),
+                )?))
+            };
+            let snapshot = snapshot
+                .with_files_matching(&this.log_store, predicate)
+                .await?;
 
             let operation_id = this.get_operation_id();
             this.pre_execute(operation_id).await?;
 
             let writer_properties = this.writer_properties.unwrap_or_else(|| {
-                WriterProperties::builder()
+                let builder = WriterProperties::builder()
                     .set_compression(Compression::ZSTD(ZstdLevel::try_new(4).unwrap()))
-                    .set_created_by(format!("delta-rs version {}", crate_version()))
+                    .set_created_by(format!("delta-rs version {}", crate_version()));
+                snapshot
+                    .table_properties()
+                    .parquet_writer_settings()
+                    .apply(builder)
                     .build()
             });
             let session = this
@@ -880,7 +898,10 @@ pub async fn create_merge_plan(
         task_parameters: Arc::new(MergeTaskParameters {
             input_parameters,
             file_schema,
-            writer_properties,
+            writer_properties: with_bloom_filters(
+                writer_properties,
+                &snapshot.table_properties().bloom_filter_columns(),
+            ),
             num_indexed_cols: snapshot.table_properties().num_indexed_cols(),
             stats_columns: snapshot
                 .table_properties()
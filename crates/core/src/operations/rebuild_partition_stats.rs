@@ -0,0 +1,193 @@
+//! Recompute the partition statistics summary of a Delta Table.
+//!
+//! Writers of tables with `delta-rs.partitionStats.enabled` maintain the summary incrementally.
+//! This operation recomputes it from the active files of the table, e.g. after the summary of
+//! a version went missing or was written by a faulty writer.
+//!
+//! # Example
+//! ```rust ignore
+//! let table = open_table("../path/to/table")?;
+//! let (table, metrics) = table.rebuild_partition_stats().await?;
+//! ````
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+
+use super::CustomExecuteHandler;
+use super::Operation;
+use crate::DeltaTable;
+use crate::errors::{DeltaResult, DeltaTableError};
+use crate::kernel::EagerSnapshot;
+use crate::kernel::resolve_snapshot;
+use crate::logstore::LogStoreRef;
+use crate::protocol::partition_stats::{
+    PartitionStatsSummary, partition_stats_enabled, write_partition_stats,
+};
+use crate::table::state::DeltaTableState;
+
+/// Recompute the partition statistics summary from the active files of the table.
+/// See this module's documentation for more information
+pub struct RebuildPartitionStatsBuilder {
+    /// A snapshot of the table's state
+    snapshot: Option<EagerSnapshot>,
+    /// Delta object store for handling data files
+    log_store: LogStoreRef,
+    custom_execute_handler: Option<Arc<dyn CustomExecuteHandler>>,
+}
+
+/// Details of the rebuild partition statistics operation
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildPartitionStatsMetrics {
+    /// The version of the table the summary was written for
+    pub version: i64,
+    /// Number of partitions with active files
+    pub num_partitions: u64,
+    /// Number of active files in the table
+    pub num_files: u64,
+}
+
+impl super::Operation for RebuildPartitionStatsBuilder {
+    fn log_store(&self) -> &LogStoreRef {
+        &self.log_store
+    }
+    fn get_custom_execute_handler(&self) -> Option<Arc<dyn CustomExecuteHandler>> {
+        self.custom_execute_handler.clone()
+    }
+}
+
+impl RebuildPartitionStatsBuilder {
+    /// Create a new [`RebuildPartitionStatsBuilder`]
+    pub(crate) fn new(log_store: LogStoreRef, snapshot: Option<EagerSnapshot>) -> Self {
+        RebuildPartitionStatsBuilder {
+            snapshot,
+            log_store,
+            custom_execute_handler: None,
+        }
+    }
+
+    /// Set a custom execute handler, for pre and post execution
+    pub fn with_custom_execute_handler(mut self, handler: Arc<dyn CustomExecuteHandler>) -> Self {
+        self.custom_execute_handler = Some(handler);
+        self
+    }
+}
+
+impl std::future::IntoFuture for RebuildPartitionStatsBuilder {
+    type Output = DeltaResult<(DeltaTable, RebuildPartitionStatsMetrics)>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let this = self;
+
+        Box::pin(async move {
+            let snapshot =
+                resolve_snapshot(&this.log_store, this.snapshot.clone(), true, None).await?;
+            if !partition_stats_enabled(&snapshot) {
+                return Err(DeltaTableError::generic(
+                    "Partition statistics require 'delta-rs.partitionStats.enabled' and are not supported with column mapping",
+                ));
+            }
+
+            let operation_id = this.get_operation_id();
+            this.pre_execute(operation_id).await?;
+
+            let summary =
+                PartitionStatsSummary::try_from_snapshot(&snapshot, this.log_store.as_ref())
+                    .await?;
+            write_partition_stats(
+                this.log_store.as_ref(),
+                snapshot.version(),
+                &summary,
+                Some(operation_id),
+            )
+            .await?;
+
+            this.post_execute(operation_id).await?;
+
+            let metrics = RebuildPartitionStatsMetrics {
+                version: snapshot.version(),
+                num_partitions: summary.partitions.len() as u64,
+                num_files: summary
+                    .partitions
+                    .iter()
+                    .map(|partition| partition.num_files as u64)
+                    .sum(),
+            };
+            Ok((
+                DeltaTable::new_with_state(this.log_store, DeltaTableState::new(snapshot)),
+                metrics,
+            ))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use arrow_array::RecordBatch;
+    use object_store::ObjectStore;
+
+    use super::*;
+    use crate::TableProperty;
+    use crate::protocol::partition_stats::read_partition_stats;
+    use crate::writer::test_utils::{get_arrow_schema, get_delta_schema};
+
+    #[tokio::test]
+    async fn test_rebuild_partition_stats() -> DeltaResult<()> {
+        let batch = RecordBatch::try_new(
+            get_arrow_schema(&None),
+            vec![
+                Arc::new(arrow::array::StringArray::from(vec!["A", "B"])),
+                Arc::new(arrow::array::Int32Array::from(vec![1, 2])),
+                Arc::new(arrow::array::StringArray::from(vec![
+                    "2021-02-01",
+                    "2021-02-02",
+                ])),
+            ],
+        )
+        .unwrap();
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_partition_columns(["modified"])
+            .with_configuration_property(TableProperty::PartitionStatsEnabled, Some("true"))
+            .await?;
+        let table = table.write(vec![batch]).await?;
+
+        let log_store = table.log_store();
+        let written = read_partition_stats(log_store.as_ref(), 1, None)
+            .await?
+            .expect("partition statistics");
+        log_store
+            .object_store(None)
+            .delete(
+                &log_store
+                    .log_path()
+                    .child("_partition_stats")
+                    .child(format!("{:020}.partition_stats.json", 1)),
+            )
+            .await?;
+
+        let (_, metrics) = table.rebuild_partition_stats().await?;
+        assert_eq!(metrics.version, 1);
+        assert_eq!(metrics.num_partitions, 2);
+        assert_eq!(metrics.num_files, 2);
+        let rebuilt = read_partition_stats(log_store.as_ref(), 1, None)
+            .await?
+            .expect("rebuilt partition statistics");
+        assert_eq!(rebuilt.partitions.len(), written.partitions.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_partition_stats_disabled() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        assert!(table.rebuild_partition_stats().await.is_err());
+        Ok(())
+    }
+}
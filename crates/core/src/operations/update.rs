@@ -46,7 +46,9 @@ use super::{
     CustomExecuteHandler, Operation,
     write::execution::{write_execution_plan, write_execution_plan_cdc},
 };
-use crate::delta_datafusion::{Expression, scan_files_where_matches, update_datafusion_session};
+use crate::delta_datafusion::{
+    Expression, file_skipping_predicate, scan_files_where_matches, update_datafusion_session,
+};
 use crate::kernel::resolve_snapshot;
 use crate::logstore::LogStoreRef;
 use crate::operations::cdc::*;
@@ -336,6 +338,12 @@ async fn execute(
     let physical_plan = session.create_physical_plan(&plan_updated).await?;
     let tracker = CDCTracker::new(files_scan.scan().clone(), plan_updated);
 
+    let writer_properties = writer_properties.or_else(|| {
+        snapshot
+            .table_properties()
+            .parquet_writer_settings()
+            .writer_properties()
+    });
     let writer_stats_config = WriterStatsConfig::from_config(snapshot.table_configuration());
     let mut actions = write_execution_plan(
         Some(snapshot),
@@ -418,8 +426,11 @@ impl std::future::IntoFuture for UpdateBuilder {
         let mut this = self;
 
         Box::pin(async move {
+            // snapshots loaded without files only load the files matching the predicate
+            let require_files = this.snapshot.is_none();
             let snapshot =
-                resolve_snapshot(&this.log_store, this.snapshot.clone(), true, None).await?;
+                resolve_snapshot(&this.log_store, this.snapshot.clone(), require_files, None)
+                    .await?;
             PROTOCOL.check_append_only(&snapshot)?;
             PROTOCOL.can_write_to(&snapshot)?;
 
@@ -444,6 +455,13 @@ impl std::future::IntoFuture for UpdateBuilder {
                 .predicate
                 .map(|p| p.resolve(session.as_ref(), snapshot.arrow_schema().to_dfschema_ref()?))
                 .transpose()?;
+            let skipping_predicate = predicate
+                .as_ref()
+                .map(file_skipping_predicate)
+                .transpose()?;
+            let snapshot = snapshot
+                .with_files_matching(&this.log_store, skipping_predicate)
+                .await?;
 
             let predicate = predicate.unwrap_or(lit(true));
             let operation = DeltaOperation::Update {
@@ -2,10 +2,12 @@ use super::*;
 
 use crate::kernel::{Action, PrimitiveType, StructField, StructType};
 use crate::kernel::{DataType as DeltaDataType, ProtocolInner};
+use crate::table::state::DeltaTableState;
 use crate::writer::test_utils::datafusion::get_data;
 use crate::writer::test_utils::datafusion::write_batch;
 use crate::writer::test_utils::{
-    get_arrow_schema, get_delta_schema, get_record_batch, setup_table_with_configuration,
+    get_arrow_schema, get_delta_schema, get_record_batch, get_record_batch_from_rows,
+    setup_table_with_configuration,
 };
 use crate::{DeltaTable, TableProperty};
 use arrow::array::{Int32Array, ListArray, StringArray};
@@ -327,6 +329,62 @@ async fn test_update_partitions() {
     assert_batches_sorted_eq!(&expected, &actual);
 }
 
+#[tokio::test]
+async fn test_update_without_files() {
+    // Perform an update on a snapshot loaded without files
+    let table = setup_table(Some(vec!["modified"])).await;
+    let batch = get_record_batch_from_rows(&[
+        ("A", 1, "2021-02-02"),
+        ("B", 10, "2021-02-02"),
+        ("A", 10, "2021-02-03"),
+        ("A", 100, "2021-02-03"),
+    ]);
+    let table = write_batch(table, batch).await;
+
+    let log_store = table.log_store();
+    let mut config = crate::DeltaTableConfig::default();
+    config.require_files = false;
+    let state = DeltaTableState::try_new(log_store.as_ref(), config, None)
+        .await
+        .unwrap();
+    let table = DeltaTable::new_with_state(log_store.clone(), state);
+
+    let (table, metrics) = table
+        .update()
+        .with_predicate(
+            col("modified")
+                .eq(lit("2021-02-03"))
+                .and(col("value").eq(lit(100))),
+        )
+        .with_update("id", lit("C"))
+        .await
+        .unwrap();
+    assert_eq!(table.version(), Some(2));
+    assert!(!table.snapshot().unwrap().load_config().require_files);
+    assert_eq!(metrics.num_added_files, 1);
+    assert_eq!(metrics.num_removed_files, 1);
+    assert_eq!(metrics.num_updated_rows, 1);
+    assert_eq!(metrics.num_copied_rows, 1);
+
+    let state =
+        DeltaTableState::try_new(log_store.as_ref(), crate::DeltaTableConfig::default(), None)
+            .await
+            .unwrap();
+    let table = DeltaTable::new_with_state(log_store, state);
+    let expected = vec![
+        "+----+-------+------------+",
+        "| id | value | modified   |",
+        "+----+-------+------------+",
+        "| A  | 1     | 2021-02-02 |",
+        "| A  | 10    | 2021-02-03 |",
+        "| B  | 10    | 2021-02-02 |",
+        "| C  | 100   | 2021-02-03 |",
+        "+----+-------+------------+",
+    ];
+    let actual = get_data(&table).await;
+    assert_batches_sorted_eq!(&expected, &actual);
+}
+
 #[tokio::test]
 async fn test_update_case_sensitive() {
     let schema = StructType::try_new(vec![
@@ -38,6 +38,7 @@ use crate::kernel::transaction::{CommitBuilder, CommitProperties};
 use crate::kernel::{EagerSnapshot, resolve_snapshot};
 use crate::logstore::{LogStore, LogStoreRef};
 use crate::protocol::DeltaOperation;
+use crate::protocol::leases::{delete_expired_leases, live_lease_versions};
 use crate::table::config::TablePropertiesExt as _;
 use crate::table::state::DeltaTableState;
 use crate::{DeltaTable, DeltaTableConfig};
@@ -56,6 +57,17 @@ enum VacuumError {
         min: i64,
     },
 
+    /// Error returned when a version referenced by a live reader lease can no longer be loaded
+    #[error(
+        "Version {version} referenced by a live reader lease can no longer be loaded, release or let the lease expire to vacuum the table: {source}"
+    )]
+    LeasedVersionNotLoadable {
+        /// The leased version
+        version: i64,
+        /// The error loading the version
+        source: DeltaTableError,
+    },
+
     /// Error returned
     #[error(transparent)]
     DeltaTable(#[from] DeltaTableError),
@@ -177,6 +189,10 @@ impl VacuumBuilder {
 
     /// Specify table versions that we want to keep for time travel.
     /// This will prevent deletion of files required by these versions.
+    ///
+    /// Versions referenced by live reader leases (see [`crate::protocol::leases`]) are always
+    /// kept in the same way. Vacuum fails if a leased version can no longer be loaded, rather
+    /// than removing files the reader may still need.
     pub fn with_keep_versions(mut self, versions: &[i64]) -> Self {
         warn!("Using experimental API VacuumBuilder::with_keep_versions");
         self.keep_versions = Some(versions.to_vec());
@@ -252,32 +268,51 @@ impl VacuumBuilder {
             None => Utc::now().timestamp_millis(),
         };
 
-        let keep_files = match &self.keep_versions {
-            Some(versions) if !versions.is_empty() => {
-                let mut sorted_versions = versions.clone();
-                sorted_versions.sort();
-                let mut keep_files: HashSet<String> = HashSet::new();
-                let mut state = DeltaTableState::try_new(
-                    &self.log_store,
-                    DeltaTableConfig::default(),
-                    Some(versions[0]),
-                )
-                .await?;
-                for version in sorted_versions {
-                    state.update(&self.log_store, Some(version)).await?;
-                    let files: Vec<String> = state
-                        .log_data()
-                        .into_iter()
-                        .map(|add| add.object_store_path())
-                        .map(|path| path.to_string())
-                        .collect();
-                    debug!("keep version:{version}\n, {files:#?}");
-                    keep_files.extend(files);
-                }
+        // versions referenced by live reader leases are kept like explicitly kept versions
+        let mut keep_versions = self.keep_versions.clone().unwrap_or_default();
+        let lease_versions = live_lease_versions(self.log_store.as_ref(), now_millis).await?;
+        if !lease_versions.is_empty() {
+            debug!("keeping versions {lease_versions:?} referenced by live reader leases");
+        }
+        keep_versions.extend(lease_versions.iter().copied());
+        let load_error = |version: i64, source: DeltaTableError| {
+            if lease_versions.contains(&version) {
+                VacuumError::LeasedVersionNotLoadable { version, source }
+            } else {
+                source.into()
+            }
+        };
 
-                keep_files
+        let keep_files = if keep_versions.is_empty() {
+            HashSet::new()
+        } else {
+            let mut sorted_versions = keep_versions;
+            sorted_versions.sort();
+            sorted_versions.dedup();
+            let mut keep_files: HashSet<String> = HashSet::new();
+            let mut state = DeltaTableState::try_new(
+                &self.log_store,
+                DeltaTableConfig::default(),
+                Some(sorted_versions[0]),
+            )
+            .await
+            .map_err(|err| load_error(sorted_versions[0], err))?;
+            for version in sorted_versions {
+                state
+                    .update(&self.log_store, Some(version))
+                    .await
+                    .map_err(|err| load_error(version, err))?;
+                let files: Vec<String> = state
+                    .log_data()
+                    .into_iter()
+                    .map(|add| add.object_store_path())
+                    .map(|path| path.to_string())
+                    .collect();
+                debug!("keep version:{version}\n, {files:#?}");
+                keep_files.extend(files);
             }
-            _ => HashSet::new(),
+
+            keep_files
         };
 
         let expired_tombstones =
@@ -361,6 +396,7 @@ impl VacuumBuilder {
             retention_check_enabled: enforce_retention_duration,
             default_retention_millis: min_retention.num_milliseconds(),
             specified_retention_millis: Some(retention_period.num_milliseconds()),
+            now_millis,
         })
     }
 }
@@ -389,6 +425,9 @@ impl std::future::IntoFuture for VacuumBuilder {
             let operation_id = this.get_operation_id();
             this.pre_execute(operation_id).await?;
 
+            // expired leases no longer keep any files, so they can be removed
+            delete_expired_leases(this.log_store.as_ref(), plan.now_millis).await?;
+
             let result = plan
                 .execute(
                     this.log_store.clone(),
@@ -427,6 +466,8 @@ struct VacuumPlan {
     pub default_retention_millis: i64,
     /// Overridden retention in milliseconds
     pub specified_retention_millis: Option<i64>,
+    /// Time the plan was created at in milliseconds since epoch
+    pub now_millis: i64,
 }
 
 impl VacuumPlan {
@@ -922,4 +963,138 @@ mod tests {
 
         Ok(())
     }
+
+    /// An in-memory copy of the simple table, which is not loaded yet
+    async fn simple_table_in_memory() -> DeltaTable {
+        use object_store::GetResultPayload;
+
+        let store = InMemory::new();
+        let source = LocalFileSystem::new_with_prefix("../test/tests/data/simple_table").unwrap();
+        let mut stream = source.list(None);
+        while let Some(Ok(entity)) = stream.next().await {
+            let mut contents = vec![];
+            match source.get(&entity.location).await.unwrap().payload {
+                GetResultPayload::File(mut fd, _path) => {
+                    fd.read_to_end(&mut contents).unwrap();
+                }
+                _ => panic!("We should only be dealing in files!"),
+            }
+            store
+                .put(&entity.location, PutPayload::from(contents))
+                .await
+                .unwrap();
+        }
+
+        let table_url = url::Url::parse("memory:///").unwrap();
+        crate::DeltaTableBuilder::from_url(table_url.clone())
+            .unwrap()
+            .with_storage_backend(Arc::new(store), table_url)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_keeps_leased_versions() -> DeltaResult<()> {
+        let mut table = simple_table_in_memory().await;
+        table.load_version(3).await?;
+        let lease = table
+            .acquire_lease(std::time::Duration::from_secs(3600))
+            .await?;
+        table.load().await?;
+
+        let vacuum = |clock: Option<i64>| {
+            let builder = VacuumBuilder::new(
+                table.log_store(),
+                Some(table.snapshot().unwrap().snapshot.clone()),
+            )
+            .with_retention_period(Duration::hours(0))
+            .with_dry_run(true)
+            .with_mode(VacuumMode::Full)
+            .with_enforce_retention_duration(false);
+            match clock {
+                Some(timestamp) => builder.with_clock(Arc::new(MockClock::new(timestamp))),
+                None => builder,
+            }
+        };
+
+        let (_table, leased) = vacuum(None).await?;
+        let (_table, kept) = vacuum(None).with_keep_versions(&[3]).await?;
+        let mut leased = leased.files_deleted;
+        let mut kept = kept.files_deleted;
+        leased.sort();
+        kept.sort();
+        assert_ne!(32, leased.len());
+        assert_eq!(leased, kept);
+
+        // expired leases do not keep any files
+        let (_table, expired) = vacuum(Some(lease.expires_at + 1)).await?;
+        assert_eq!(32, expired.files_deleted.len());
+
+        table.release_lease(lease).await?;
+        let (_table, released) = vacuum(None).await?;
+        assert_eq!(32, released.files_deleted.len());
+
+        // dry runs keep expired leases, vacuuming the table removes them
+        let lease = table
+            .acquire_lease(std::time::Duration::from_secs(3600))
+            .await?;
+        vacuum(Some(lease.expires_at + 1)).await?;
+        let leases = crate::protocol::leases::read_leases(table.log_store().as_ref()).await?;
+        assert_eq!(1, leases.len());
+        let (_table, vacuumed) = vacuum(Some(lease.expires_at + 1))
+            .with_dry_run(false)
+            .await?;
+        assert_eq!(32, vacuumed.files_deleted.len());
+        let leases = crate::protocol::leases::read_leases(table.log_store().as_ref()).await?;
+        assert!(leases.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_fails_for_leases_on_unloadable_versions() -> DeltaResult<()> {
+        use crate::protocol::checkpoints::create_checkpoint;
+        use crate::protocol::leases::{ReaderLease, write_lease};
+
+        let mut table = simple_table_in_memory().await;
+        table.load_version(0).await?;
+        let mut latest = table.clone();
+        latest.load().await?;
+        create_checkpoint(&latest, None).await?;
+        latest
+            .log_store()
+            .object_store(None)
+            .delete(&object_store::path::Path::from(
+                "_delta_log/00000000000000000000.json",
+            ))
+            .await?;
+
+        // version 0 can no longer be loaded after its commit was cleaned up
+        let err = table
+            .acquire_lease(std::time::Duration::from_secs(3600))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Cannot lease version 0"));
+
+        // a lease recorded before the commit was cleaned up
+        let lease = ReaderLease {
+            id: "stale".to_string(),
+            version: 0,
+            expires_at: i64::MAX,
+        };
+        write_lease(latest.log_store().as_ref(), &lease).await?;
+        let err = VacuumBuilder::new(
+            latest.log_store(),
+            Some(latest.snapshot().unwrap().snapshot.clone()),
+        )
+        .with_retention_period(Duration::hours(0))
+        .with_dry_run(true)
+        .with_enforce_retention_duration(false)
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Version 0 referenced by a live reader lease")
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,220 @@
+//! Validate the state of a Delta Table against its version checksum file.
+//!
+//! The table state at a version is reconstructed from the log and compared with the table size,
+//! number of files, protocol, metadata, application transactions and domain metadata recorded in
+//! the `<version>.crc` file. A mismatch indicates a corrupted log or checksum and is returned as
+//! [`DeltaTableError::ChecksumMismatch`].
+//!
+//! # Example
+//! ```rust ignore
+//! let table = open_table("../path/to/table")?;
+//! let (table, metrics) = table.validate_checksum().await?;
+//! ````
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+use tracing::*;
+
+use super::CustomExecuteHandler;
+use super::Operation;
+use crate::DeltaTable;
+use crate::errors::{DeltaResult, DeltaTableError};
+use crate::kernel::EagerSnapshot;
+use crate::kernel::resolve_snapshot;
+use crate::logstore::LogStoreRef;
+use crate::protocol::checksum::{VersionChecksum, read_checksum, write_checksum};
+use crate::table::state::DeltaTableState;
+
+/// Validate the table state against its version checksum file.
+/// See this module's documentation for more information
+pub struct ValidateChecksumBuilder {
+    /// A snapshot of the to-be-validated table's state
+    snapshot: Option<EagerSnapshot>,
+    /// Delta object store for handling data files
+    log_store: LogStoreRef,
+    /// The version to validate, defaults to the version of the snapshot
+    version: Option<i64>,
+    /// Write the checksum file from the table state if the version has none
+    create_missing: bool,
+    custom_execute_handler: Option<Arc<dyn CustomExecuteHandler>>,
+}
+
+/// Details of the validate checksum operation
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateChecksumMetrics {
+    /// The validated version of the table
+    pub version: i64,
+    /// Whether a checksum file existed for the version
+    pub checksum_found: bool,
+    /// Whether a missing checksum file was created
+    pub checksum_created: bool,
+}
+
+impl super::Operation for ValidateChecksumBuilder {
+    fn log_store(&self) -> &LogStoreRef {
+        &self.log_store
+    }
+    fn get_custom_execute_handler(&self) -> Option<Arc<dyn CustomExecuteHandler>> {
+        self.custom_execute_handler.clone()
+    }
+}
+
+impl ValidateChecksumBuilder {
+    /// Create a new [`ValidateChecksumBuilder`]
+    pub(crate) fn new(log_store: LogStoreRef, snapshot: Option<EagerSnapshot>) -> Self {
+        ValidateChecksumBuilder {
+            snapshot,
+            log_store,
+            version: None,
+            create_missing: false,
+            custom_execute_handler: None,
+        }
+    }
+
+    /// Validate the given version instead of the version of the loaded table
+    pub fn with_version(mut self, version: i64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Write the checksum file computed from the table state if the version has none.
+    /// Following commits then keep their checksums up to date.
+    pub fn with_create_missing(mut self, create_missing: bool) -> Self {
+        self.create_missing = create_missing;
+        self
+    }
+
+    /// Set a custom execute handler, for pre and post execution
+    pub fn with_custom_execute_handler(mut self, handler: Arc<dyn CustomExecuteHandler>) -> Self {
+        self.custom_execute_handler = Some(handler);
+        self
+    }
+}
+
+impl std::future::IntoFuture for ValidateChecksumBuilder {
+    type Output = DeltaResult<(DeltaTable, ValidateChecksumMetrics)>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let this = self;
+
+        Box::pin(async move {
+            let snapshot = this
+                .snapshot
+                .clone()
+                .filter(|snapshot| this.version.is_none_or(|v| snapshot.version() == v));
+            let snapshot = resolve_snapshot(
+                &this.log_store,
+                snapshot,
+                true,
+                this.version.map(|v| v as u64),
+            )
+            .await?;
+            let version = snapshot.version();
+            let operation_id = this.get_operation_id();
+
+            let mut metrics = ValidateChecksumMetrics {
+                version,
+                ..Default::default()
+            };
+            match read_checksum(this.log_store.as_ref(), version, Some(operation_id)).await? {
+                Some(checksum) => {
+                    metrics.checksum_found = true;
+                    let mismatches = checksum
+                        .mismatches(&snapshot, this.log_store.as_ref())
+                        .await?;
+                    if !mismatches.is_empty() {
+                        error!(version, ?mismatches, "version checksum validation failed");
+                        return Err(DeltaTableError::ChecksumMismatch {
+                            version,
+                            details: mismatches.join(", "),
+                        });
+                    }
+                }
+                None if this.create_missing => {
+                    this.pre_execute(operation_id).await?;
+                    let checksum =
+                        VersionChecksum::try_from_snapshot(&snapshot, this.log_store.as_ref())
+                            .await?;
+                    write_checksum(
+                        this.log_store.as_ref(),
+                        version,
+                        &checksum,
+                        Some(operation_id),
+                    )
+                    .await?;
+                    this.post_execute(operation_id).await?;
+                    metrics.checksum_created = true;
+                }
+                None => debug!(version, "no version checksum to validate"),
+            }
+
+            Ok((
+                DeltaTable::new_with_state(this.log_store, DeltaTableState::new(snapshot)),
+                metrics,
+            ))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use bytes::Bytes;
+    use object_store::ObjectStore;
+
+    use super::*;
+    use crate::writer::test_utils::{get_delta_schema, get_record_batch_from_rows};
+
+    #[tokio::test]
+    async fn test_validate_checksum() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-02")])])
+            .await?;
+
+        let (table, metrics) = table.validate_checksum().await?;
+        assert!(!metrics.checksum_found);
+        assert!(!metrics.checksum_created);
+
+        let (table, metrics) = table.validate_checksum().with_create_missing(true).await?;
+        assert_eq!(metrics.version, 1);
+        assert!(metrics.checksum_created);
+
+        // The checksum of the next commit is derived from the created one
+        let table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 2, "2021-02-02")])])
+            .await?;
+        let (table, metrics) = table.validate_checksum().await?;
+        assert_eq!(metrics.version, 2);
+        assert!(metrics.checksum_found);
+
+        let (_, metrics) = table.clone().validate_checksum().with_version(1).await?;
+        assert!(metrics.checksum_found);
+
+        // Corrupt the checksum of the latest version
+        let log_store = table.log_store();
+        let mut checksum = read_checksum(log_store.as_ref(), 2, None)
+            .await?
+            .expect("checksum");
+        checksum.num_files += 1;
+        log_store
+            .object_store(None)
+            .put(
+                &log_store.log_path().child(format!("{:020}.crc", 2)),
+                Bytes::from(serde_json::to_vec(&checksum)?).into(),
+            )
+            .await?;
+        let result = table.validate_checksum().await;
+        assert!(matches!(
+            result,
+            Err(DeltaTableError::ChecksumMismatch { version: 2, .. })
+        ));
+        Ok(())
+    }
+}
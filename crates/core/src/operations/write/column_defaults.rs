@@ -0,0 +1,142 @@
+use datafusion::catalog::Session;
+use datafusion::common::DFSchema;
+use datafusion::logical_expr::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder};
+use delta_kernel::engine::arrow_conversion::TryIntoArrow as _;
+use tracing::debug;
+
+use crate::{
+    DeltaResult,
+    delta_datafusion::expr::parse_predicate_expression,
+    kernel::{EagerSnapshot, allow_column_defaults_feature},
+    table::ColumnDefault,
+};
+
+/// check if the table allows columns to declare default values
+#[inline]
+pub fn cd_is_enabled(snapshot: &EagerSnapshot) -> bool {
+    snapshot
+        .protocol()
+        .writer_features()
+        .is_some_and(|features| features.contains(&allow_column_defaults_feature()))
+}
+
+/// Evaluate the default value expressions of the given columns, casted to the column types
+pub fn column_default_exprs(
+    session: &dyn Session,
+    column_defaults: &[ColumnDefault],
+) -> DeltaResult<Vec<(String, Expr)>> {
+    let schema = DFSchema::empty();
+    let mut exprs = Vec::with_capacity(column_defaults.len());
+    for column_default in column_defaults {
+        let data_type = (&column_default.data_type).try_into_arrow()?;
+        let expr =
+            parse_predicate_expression(&schema, column_default.get_default_expression(), session)?
+                .cast_to(&data_type, &schema)?;
+        exprs.push((column_default.name.clone(), expr));
+    }
+    Ok(exprs)
+}
+
+/// Add the columns with default values that are missing from the plan
+pub fn with_column_defaults(
+    session: &dyn Session,
+    plan: LogicalPlan,
+    column_defaults: &[ColumnDefault],
+) -> DeltaResult<LogicalPlan> {
+    let missing: Vec<_> = column_defaults
+        .iter()
+        .filter(|cd| {
+            plan.schema()
+                .field_with_unqualified_name(cd.get_name())
+                .is_err()
+        })
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(plan);
+    }
+
+    let mut projection: Vec<_> = plan
+        .schema()
+        .columns()
+        .into_iter()
+        .map(Expr::Column)
+        .collect();
+    for (name, expr) in column_default_exprs(session, &missing)? {
+        debug!("Adding missing column {name} with its default value.");
+        projection.push(expr.alias(name));
+    }
+
+    Ok(LogicalPlanBuilder::new(plan).project(projection)?.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, RecordBatch};
+    use arrow::datatypes::{DataType as ArrowDataType, Field as ArrowField, Schema};
+    use datafusion::catalog::MemTable;
+    use datafusion::datasource::provider_as_source;
+    use datafusion::prelude::SessionContext;
+    use delta_kernel::schema::DataType as KernelDataType;
+    use std::sync::Arc;
+
+    fn create_test_plan() -> LogicalPlan {
+        let schema = Arc::new(Schema::new(vec![ArrowField::new(
+            "id",
+            ArrowDataType::Int32,
+            false,
+        )]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+
+        let source = provider_as_source(Arc::new(
+            MemTable::try_new(batch.schema(), vec![vec![batch]]).unwrap(),
+        ));
+        LogicalPlanBuilder::scan("test", source, None)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_add_missing_column_default() {
+        let session = SessionContext::new().state();
+        let column_defaults = vec![
+            ColumnDefault::new("id", "0", &KernelDataType::INTEGER),
+            ColumnDefault::new("status", "'open'", &KernelDataType::STRING),
+            ColumnDefault::new("score", "1 + 1", &KernelDataType::LONG),
+        ];
+
+        let plan = with_column_defaults(&session, create_test_plan(), &column_defaults).unwrap();
+
+        let schema = plan.schema();
+        assert_eq!(schema.fields().len(), 3);
+        assert_eq!(
+            schema
+                .field_with_unqualified_name("status")
+                .unwrap()
+                .data_type(),
+            &ArrowDataType::Utf8
+        );
+        assert_eq!(
+            schema
+                .field_with_unqualified_name("score")
+                .unwrap()
+                .data_type(),
+            &ArrowDataType::Int64
+        );
+    }
+
+    #[test]
+    fn test_invalid_column_default() {
+        let session = SessionContext::new().state();
+        let column_defaults = vec![ColumnDefault::new(
+            "status",
+            "missing_column",
+            &KernelDataType::STRING,
+        )];
+
+        assert!(with_column_defaults(&session, create_test_plan(), &column_defaults).is_err());
+    }
+}
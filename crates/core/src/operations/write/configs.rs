@@ -2,7 +2,7 @@ use delta_kernel::{
     table_configuration::TableConfiguration, table_properties::DataSkippingNumIndexedCols,
 };
 
-use crate::table::config::TablePropertiesExt as _;
+use crate::table::config::{BloomFilterColumn, TablePropertiesExt as _};
 
 /// Configuration for the writer on how to collect stats
 #[derive(Clone)]
@@ -11,6 +11,8 @@ pub struct WriterStatsConfig {
     pub num_indexed_cols: DataSkippingNumIndexedCols,
     /// Optional list of columns which to collect stats for, takes precedende over num_index_cols
     pub stats_columns: Option<Vec<String>>,
+    /// Columns to write parquet bloom filters for
+    pub bloom_filter_columns: Vec<BloomFilterColumn>,
 }
 
 impl WriterStatsConfig {
@@ -22,9 +24,19 @@ impl WriterStatsConfig {
         Self {
             num_indexed_cols,
             stats_columns,
+            bloom_filter_columns: Vec::new(),
         }
     }
 
+    /// Write parquet bloom filters for the given columns
+    pub fn with_bloom_filter_columns(
+        mut self,
+        bloom_filter_columns: Vec<BloomFilterColumn>,
+    ) -> Self {
+        self.bloom_filter_columns = bloom_filter_columns;
+        self
+    }
+
     pub fn from_config(config: &TableConfiguration) -> Self {
         Self {
             num_indexed_cols: config.table_properties().num_indexed_cols(),
@@ -33,6 +45,35 @@ impl WriterStatsConfig {
                 .data_skipping_stats_columns
                 .as_ref()
                 .map(|v| v.iter().map(|v| v.to_string()).collect::<Vec<String>>()),
+            bloom_filter_columns: config.table_properties().bloom_filter_columns(),
+        }
+    }
+}
+
+/// How the input of a write is shuffled before it is handed to the parquet writers.
+///
+/// Optimized writes repartition the input by the table's partition columns, so that all rows
+/// of a table partition are written by the same task instead of being spread over every
+/// partition of the input plan. Unpartitioned writes are left as they are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptimizedWrite {
+    /// Write the input as it is partitioned by the query plan
+    #[default]
+    Disabled,
+    /// Hash partition the input on the partition columns into the session's target partitions
+    Enabled,
+    /// Like [`OptimizedWrite::Enabled`], but derive the number of bins from the estimated
+    /// input size and the target file size, capped at the session's target partitions
+    Adaptive,
+}
+
+impl From<bool> for OptimizedWrite {
+    /// Optimized write mode for the value of `delta.autoOptimize.optimizeWrite`
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Self::Enabled
+        } else {
+            Self::Disabled
         }
     }
 }
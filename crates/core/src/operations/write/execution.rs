@@ -8,7 +8,9 @@ use datafusion::common::ToDFSchema;
 use datafusion::datasource::{MemTable, provider_as_source};
 use datafusion::execution::context::SessionContext;
 use datafusion::logical_expr::{Expr, LogicalPlan, LogicalPlanBuilder, col, lit, when};
-use datafusion::physical_plan::{ExecutionPlan, execute_stream_partitioned};
+use datafusion::physical_expr::expressions::col as physical_col;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::{ExecutionPlan, Partitioning, execute_stream_partitioned};
 use delta_kernel::engine::arrow_conversion::TryIntoKernel as _;
 use delta_kernel::table_configuration::TableConfiguration;
 use futures::{StreamExt as _, TryStreamExt as _};
@@ -31,7 +33,8 @@ use crate::kernel::{Action, Add, AddCDCFile, EagerSnapshot, Remove, StructType,
 use crate::logstore::{LogStore, LogStoreRef, ObjectStoreRef};
 use crate::operations::cdc::{CDC_COLUMN_NAME, should_write_cdc};
 use crate::operations::write::WriterStatsConfig;
-use crate::table::config::TablePropertiesExt as _;
+use crate::operations::write::configs::OptimizedWrite;
+use crate::table::config::{DEFAULT_TARGET_FILE_SIZE, TablePropertiesExt as _};
 
 const DEFAULT_WRITER_BATCH_CHANNEL_SIZE: usize = 10;
 
@@ -52,6 +55,50 @@ pub(crate) struct WriteExecutionPlanMetrics {
     pub write_time_ms: u64,
 }
 
+/// Shuffle the input of a write by the table partition columns, see [`OptimizedWrite`].
+///
+/// All rows of a table partition end up in the same output partition of the returned plan,
+/// rather than being interleaved from every partition of the input plan.
+pub(crate) fn optimize_write_plan(
+    session: &dyn Session,
+    plan: Arc<dyn ExecutionPlan>,
+    partition_columns: &[String],
+    optimized_write: OptimizedWrite,
+    target_file_size: Option<usize>,
+) -> DeltaResult<Arc<dyn ExecutionPlan>> {
+    if partition_columns.is_empty() {
+        return Ok(plan);
+    }
+    let target_partitions = session.config().target_partitions().max(1);
+    let num_bins = match optimized_write {
+        OptimizedWrite::Disabled => return Ok(plan),
+        OptimizedWrite::Enabled => target_partitions,
+        OptimizedWrite::Adaptive => {
+            let target_file_size = target_file_size
+                .unwrap_or(DEFAULT_TARGET_FILE_SIZE as usize)
+                .max(1);
+            match plan.partition_statistics(None)?.total_byte_size.get_value() {
+                Some(bytes) => bytes.div_ceil(target_file_size).clamp(1, target_partitions),
+                None => target_partitions,
+            }
+        }
+    };
+
+    let schema = plan.schema();
+    let exprs = partition_columns
+        .iter()
+        .map(|name| physical_col(name, &schema))
+        .collect::<datafusion::common::Result<Vec<_>>>()?;
+    debug!(
+        "optimized write: hash partitioning input on {partition_columns:?} into {num_bins} bins"
+    );
+
+    Ok(Arc::new(RepartitionExec::try_new(
+        plan,
+        Partitioning::Hash(exprs, num_bins),
+    )?))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn write_execution_plan_cdc(
     snapshot: Option<&EagerSnapshot>,
@@ -327,15 +374,25 @@ pub(crate) async fn write_exec_plan(
     log_store: &dyn LogStore,
     table_config: &TableConfiguration,
     exec: Arc<dyn ExecutionPlan>,
+    writer_properties: Option<WriterProperties>,
     operation_id: Option<Uuid>,
     write_as_cdc: bool,
 ) -> DeltaResult<(Vec<Action>, WriteExecutionPlanMetrics)> {
-    let writer_properties = session
-        .config_options()
-        .execution
-        .parquet
-        .into_writer_properties_builder()?
-        .build();
+    let writer_properties = match writer_properties {
+        Some(writer_properties) => writer_properties,
+        None => {
+            let builder = session
+                .config_options()
+                .execution
+                .parquet
+                .into_writer_properties_builder()?;
+            table_config
+                .table_properties()
+                .parquet_writer_settings()
+                .apply(builder)
+                .build()
+        }
+    };
     let stats_config = WriterStatsConfig::from_config(table_config);
     let object_store = log_store.object_store(operation_id);
     let target_file_size = table_config
@@ -390,7 +447,8 @@ async fn write_data_plan(
         write_batch_size,
         writer_stats_config.num_indexed_cols,
         writer_stats_config.stats_columns.clone(),
-    );
+    )
+    .with_bloom_filter_columns(&writer_stats_config.bloom_filter_columns);
 
     // sync channel for batches produced by partition stream
     let (tx, mut rx) = mpsc::channel::<RecordBatch>(channel_size());
@@ -488,7 +546,8 @@ async fn write_cdc_plan(
         write_batch_size,
         writer_stats_config.num_indexed_cols,
         writer_stats_config.stats_columns.clone(),
-    );
+    )
+    .with_bloom_filter_columns(&writer_stats_config.bloom_filter_columns);
 
     let cdf_config = WriterConfig::new(
         cdf_schema.clone(),
@@ -498,7 +557,8 @@ async fn write_cdc_plan(
         write_batch_size,
         writer_stats_config.num_indexed_cols,
         writer_stats_config.stats_columns.clone(),
-    );
+    )
+    .with_bloom_filter_columns(&writer_stats_config.bloom_filter_columns);
 
     // sync channel for batches produced by partition stream for normal and cdf batches
     let (tx_normal, mut rx_normal) = mpsc::channel::<RecordBatch>(channel_size());
@@ -46,8 +46,9 @@ use serde::{Deserialize, Serialize};
 use tracing::Instrument;
 use url::Url;
 
-pub use self::configs::WriterStatsConfig;
-use self::execution::{prepare_predicate_actions, write_execution_plan_v2};
+use self::column_defaults::{cd_is_enabled, with_column_defaults};
+pub use self::configs::{OptimizedWrite, WriterStatsConfig};
+use self::execution::{optimize_write_plan, prepare_predicate_actions, write_execution_plan_v2};
 use self::generated_columns::{gc_is_enabled, with_generated_columns};
 use self::metrics::{SOURCE_COUNT_ID, SOURCE_COUNT_METRIC};
 use self::schema_evolution::try_cast_schema;
@@ -70,6 +71,7 @@ use crate::kernel::{
 use crate::logstore::LogStoreRef;
 use crate::protocol::{DeltaOperation, SaveMode};
 
+pub(crate) mod column_defaults;
 pub mod configs;
 pub(crate) mod execution;
 pub(crate) mod generated_columns;
@@ -151,6 +153,8 @@ pub struct WriteBuilder {
     safe_cast: bool,
     /// Parquet writer properties
     writer_properties: Option<WriterProperties>,
+    /// Shuffle the input by partition values before writing, defaults to the table configuration
+    optimized_write: Option<OptimizedWrite>,
     /// Additional information to add to the commit
     commit_properties: CommitProperties,
     /// Name of the table, only used when table doesn't exist yet
@@ -202,6 +206,7 @@ impl WriteBuilder {
             safe_cast: false,
             schema_mode: None,
             writer_properties: None,
+            optimized_write: None,
             commit_properties: CommitProperties::default(),
             name: None,
             description: None,
@@ -281,6 +286,14 @@ impl WriteBuilder {
         self
     }
 
+    /// Shuffle the input by the table's partition columns before writing, see [`OptimizedWrite`].
+    ///
+    /// Overrides `delta.autoOptimize.optimizeWrite` for this write.
+    pub fn with_optimized_write(mut self, optimized_write: OptimizedWrite) -> Self {
+        self.optimized_write = Some(optimized_write);
+        self
+    }
+
     /// Additional metadata to be added to commit info
     pub fn with_commit_properties(mut self, commit_properties: CommitProperties) -> Self {
         self.commit_properties = commit_properties;
@@ -458,6 +471,16 @@ impl std::future::IntoFuture for WriteBuilder {
                     source.schema().inner().clone()
                 };
 
+                if let Some(snapshot) = &this.snapshot
+                    && cd_is_enabled(snapshot)
+                {
+                    source = with_column_defaults(
+                        session.as_ref(),
+                        source,
+                        &snapshot.schema().get_column_defaults()?,
+                    )?;
+                }
+
                 if let Some(snapshot) = &this.snapshot
                     && gc_is_enabled(snapshot)
                 {
@@ -613,12 +636,21 @@ impl std::future::IntoFuture for WriteBuilder {
                 let target_file_size = this.target_file_size.or_else(|| {
                     Some(super::get_target_file_size(config, &this.configuration) as usize)
                 });
+                let writer_properties = this
+                    .writer_properties
+                    .or_else(|| super::get_writer_properties(config, &this.configuration));
+                let bloom_filter_columns =
+                    super::get_bloom_filter_columns(config, &this.configuration);
+                let optimized_write = this.optimized_write.unwrap_or_else(|| {
+                    super::get_optimize_write(config, &this.configuration).into()
+                });
                 let (num_indexed_cols, stats_columns) =
                     super::get_num_idx_cols_and_stats_columns(config, this.configuration);
 
                 let writer_stats_config = WriterStatsConfig {
                     num_indexed_cols,
                     stats_columns,
+                    bloom_filter_columns,
                 };
 
                 let mut contains_cdc = false;
@@ -639,7 +671,7 @@ impl std::future::IntoFuture for WriteBuilder {
                                     snapshot,
                                     session.as_ref(),
                                     partition_columns.clone(),
-                                    this.writer_properties.clone(),
+                                    writer_properties.clone(),
                                     deletion_timestamp,
                                     writer_stats_config.clone(),
                                     operation_id,
@@ -681,17 +713,24 @@ impl std::future::IntoFuture for WriteBuilder {
                 }
 
                 let source_plan = session.create_physical_plan(&source).await?;
+                let write_plan = optimize_write_plan(
+                    session.as_ref(),
+                    source_plan.clone(),
+                    &partition_columns,
+                    optimized_write,
+                    target_file_size,
+                )?;
 
                 // Here we need to validate if the new data conforms to a predicate if one is provided
                 let (add_actions, _) = write_execution_plan_v2(
                     this.snapshot.as_ref(),
                     session.as_ref(),
-                    source_plan.clone(),
+                    write_plan,
                     partition_columns.clone(),
                     this.log_store.object_store(Some(operation_id)).clone(),
                     target_file_size,
                     this.write_batch_size,
-                    this.writer_properties,
+                    writer_properties,
                     writer_stats_config.clone(),
                     predicate.clone(),
                     contains_cdc,
@@ -814,6 +853,45 @@ mod tests {
             .expect_err("Remove action is included when Delta table is append-only. Should error");
     }
 
+    #[tokio::test]
+    async fn test_write_with_table_writer_settings() -> TestResult {
+        use parquet::basic::Compression;
+
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_configuration_property(TableProperty::ParquetCompression, Some("zstd"))
+            .with_configuration_property(TableProperty::ParquetCompressionLevel, Some("3"))
+            .await?;
+        let batch = get_record_batch(None, false);
+
+        let table = table.write(vec![batch.clone()]).await?;
+        let files = table.get_file_uris()?.collect_vec();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with(".zstd.parquet"), "{files:?}");
+
+        // explicit writer properties win over the table settings
+        let table = table
+            .write(vec![batch])
+            .with_writer_properties(
+                WriterProperties::builder()
+                    .set_compression(Compression::SNAPPY)
+                    .build(),
+            )
+            .await?;
+        let files = table.get_file_uris()?.collect_vec();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            files
+                .iter()
+                .filter(|f| f.ends_with(".snappy.parquet"))
+                .count(),
+            1
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_write() {
         let table_schema = get_delta_schema();
@@ -1077,6 +1155,93 @@ mod tests {
         assert_common_write_metrics(write_metrics);
     }
 
+    #[tokio::test]
+    async fn test_write_optimized_write() -> TestResult {
+        let batch = get_record_batch(None, false);
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_partition_columns(["modified"])
+            .with_configuration_property(TableProperty::AutoOptimizeOptimizeWrite, Some("true"))
+            .await?;
+
+        let session =
+            SessionContext::new_with_config(SessionConfig::new().with_target_partitions(4));
+        let source = session
+            .read_batches(vec![batch.clone(); 4])?
+            .repartition(datafusion::logical_expr::Partitioning::RoundRobinBatch(4))?;
+        let table = table
+            .write(vec![])
+            .with_input_plan(source.logical_plan().clone())
+            .with_session_state(Arc::new(session.state()))
+            .await?;
+
+        assert_eq!(table.snapshot()?.log_data().num_files(), 2);
+        let write_metrics: WriteMetrics = get_write_metrics(&table).await;
+        assert_eq!(write_metrics.num_added_rows, batch.num_rows() * 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_optimize_write_plan() -> TestResult {
+        use datafusion::physical_plan::Partitioning;
+
+        let batch = get_record_batch(None, false);
+        let session =
+            SessionContext::new_with_config(SessionConfig::new().with_target_partitions(4));
+        let plan = session
+            .read_batches(vec![batch.clone(); 4])?
+            .repartition(datafusion::logical_expr::Partitioning::RoundRobinBatch(3))?
+            .create_physical_plan()
+            .await?;
+        let state = session.state();
+        let partition_columns = vec!["modified".to_string()];
+
+        let optimized = execution::optimize_write_plan(
+            &state,
+            plan.clone(),
+            &partition_columns,
+            OptimizedWrite::Disabled,
+            None,
+        )?;
+        assert!(Arc::ptr_eq(&optimized, &plan));
+
+        let optimized = execution::optimize_write_plan(
+            &state,
+            plan.clone(),
+            &[],
+            OptimizedWrite::Enabled,
+            None,
+        )?;
+        assert!(Arc::ptr_eq(&optimized, &plan));
+
+        let optimized = execution::optimize_write_plan(
+            &state,
+            plan.clone(),
+            &partition_columns,
+            OptimizedWrite::Enabled,
+            None,
+        )?;
+        assert!(matches!(
+            optimized.output_partitioning(),
+            Partitioning::Hash(exprs, 4) if exprs.len() == 1
+        ));
+
+        // the input is far below the target file size, so a single bin suffices
+        let optimized = execution::optimize_write_plan(
+            &state,
+            plan,
+            &partition_columns,
+            OptimizedWrite::Adaptive,
+            Some(1024 * 1024),
+        )?;
+        assert!(matches!(
+            optimized.output_partitioning(),
+            Partitioning::Hash(_, 1)
+        ));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merge_schema() {
         let batch = get_record_batch(None, false);
@@ -1370,6 +1535,49 @@ mod tests {
         assert!(table.is_err());
     }
 
+    #[tokio::test]
+    async fn test_write_column_defaults() -> TestResult {
+        let schema: StructType = serde_json::from_value(json!({
+            "type": "struct",
+            "fields": [
+                {"name": "id", "type": "string", "nullable": true, "metadata": {}},
+                {"name": "value", "type": "integer", "nullable": true, "metadata": {}},
+                {"name": "modified", "type": "string", "nullable": true, "metadata": {}},
+                {"name": "status", "type": "string", "nullable": false, "metadata": {
+                    "CURRENT_DEFAULT": "'open'"
+                }},
+            ]
+        }))?;
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(schema.fields().cloned())
+            .await?;
+        assert!(
+            table
+                .snapshot()?
+                .protocol()
+                .writer_features()
+                .unwrap_or_default()
+                .contains(&crate::kernel::allow_column_defaults_feature())
+        );
+
+        let batch = get_record_batch(None, false);
+        let table = table.write(vec![batch.clone()]).await?;
+        assert_eq!(table.version(), Some(1));
+
+        let mut num_rows = 0;
+        for batch in get_data(&table).await {
+            let status =
+                arrow::compute::cast(batch.column_by_name("status").unwrap(), &DataType::Utf8)?;
+            let status = status.as_any().downcast_ref::<StringArray>().unwrap();
+            assert!(status.iter().all(|value| value == Some("open")));
+            num_rows += batch.num_rows();
+        }
+        assert_eq!(num_rows, batch.num_rows());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_nested_struct() {
         let table_schema = get_delta_schema_with_nested_struct();
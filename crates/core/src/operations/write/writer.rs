@@ -14,7 +14,7 @@ use object_store::path::Path;
 use parquet::arrow::AsyncArrowWriter;
 use parquet::arrow::async_writer::ParquetObjectWriter;
 use parquet::basic::Compression;
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
 use tokio::task::JoinSet;
 use tracing::*;
 
@@ -23,6 +23,7 @@ use crate::crate_version;
 use crate::errors::{DeltaResult, DeltaTableError};
 use crate::kernel::{Add, PartitionsExt};
 use crate::logstore::ObjectStoreRef;
+use crate::table::config::BloomFilterColumn;
 use crate::writer::record_batch::{PartitionResult, divide_by_partition_values};
 use crate::writer::stats::create_add;
 use crate::writer::utils::{
@@ -172,6 +173,13 @@ impl WriterConfig {
         }
     }
 
+    /// Write parquet bloom filters for the given columns, typically the table's
+    /// [`bloom_filter_columns`](crate::table::config::TablePropertiesExt::bloom_filter_columns).
+    pub fn with_bloom_filter_columns(mut self, columns: &[BloomFilterColumn]) -> Self {
+        self.writer_properties = with_bloom_filters(self.writer_properties, columns);
+        self
+    }
+
     /// Schema of files written to disk
     pub fn file_schema(&self) -> ArrowSchemaRef {
         arrow_schema_without_partitions(&self.table_schema, &self.partition_columns)
@@ -340,6 +348,40 @@ impl PartitionWriterConfig {
             max_concurrency_tasks: max_concurrency_tasks.unwrap_or_else(get_max_concurrency_tasks),
         })
     }
+
+    /// Write parquet bloom filters for the given columns, typically the table's
+    /// [`bloom_filter_columns`](crate::table::config::TablePropertiesExt::bloom_filter_columns).
+    pub fn with_bloom_filter_columns(mut self, columns: &[BloomFilterColumn]) -> Self {
+        self.writer_properties = with_bloom_filters(self.writer_properties, columns);
+        self
+    }
+}
+
+/// Enable bloom filters for `columns` on top of `writer_properties`.
+///
+/// Bloom filtered columns are used for point lookups, so they also get page level statistics,
+/// which makes the writer emit column and offset indexes for them.
+pub(crate) fn with_bloom_filters(
+    writer_properties: WriterProperties,
+    columns: &[BloomFilterColumn],
+) -> WriterProperties {
+    if columns.is_empty() {
+        return writer_properties;
+    }
+    let mut builder = writer_properties.into_builder();
+    for column in columns {
+        let path = column.column_path();
+        builder = builder
+            .set_column_bloom_filter_enabled(path.clone(), true)
+            .set_column_statistics_enabled(path.clone(), EnabledStatistics::Page);
+        if let Some(fpp) = column.fpp {
+            builder = builder.set_column_bloom_filter_fpp(path.clone(), fpp);
+        }
+        if let Some(ndv) = column.ndv {
+            builder = builder.set_column_bloom_filter_ndv(path, ndv);
+        }
+    }
+    builder.build()
 }
 
 enum LazyArrowWriter {
@@ -747,4 +789,58 @@ mod tests {
             }
         };
     }
+
+    #[tokio::test]
+    async fn test_write_bloom_filters() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let object_store = DeltaTableBuilder::from_url(url::Url::parse("memory:///").unwrap())
+            .unwrap()
+            .build_storage()
+            .unwrap()
+            .object_store(None);
+        let batch = get_record_batch(None, false);
+
+        let columns = vec![BloomFilterColumn {
+            column: "id".to_string(),
+            fpp: Some(0.01),
+            ndv: Some(1000),
+        }];
+        let config = WriterConfig::new(
+            batch.schema(),
+            vec![],
+            None,
+            None,
+            None,
+            DataSkippingNumIndexedCols::NumColumns(DEFAULT_NUM_INDEX_COLS),
+            None,
+        )
+        .with_bloom_filter_columns(&columns);
+        let mut writer = DeltaWriter::new(object_store.clone(), config);
+        writer.write(&batch).await.unwrap();
+        let adds = writer.close().await.unwrap();
+        assert_eq!(adds.len(), 1);
+
+        let data = object_store
+            .get(&Path::from(adds[0].path.clone()))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let reader = SerializedFileReader::new(data).unwrap();
+        let row_group = reader.metadata().row_group(0);
+        let column_index = |name: &str| {
+            row_group
+                .columns()
+                .iter()
+                .position(|c| c.column_path().string() == name)
+                .unwrap()
+        };
+        let id = row_group.column(column_index("id"));
+        assert!(id.bloom_filter_offset().is_some());
+        assert!(id.column_index_offset().is_some());
+        let value = row_group.column(column_index("value"));
+        assert!(value.bloom_filter_offset().is_none());
+    }
 }
@@ -11,6 +11,7 @@ use delta_kernel::FileMeta;
 use delta_kernel::engine::arrow_data::ArrowEngineData;
 use delta_kernel::engine_data::FilteredEngineData;
 use delta_kernel::snapshot::Snapshot;
+use delta_kernel::table_features::TableFeature;
 use futures::{StreamExt, TryStreamExt};
 use object_store::ObjectStore;
 use object_store::path::Path;
@@ -21,7 +22,7 @@ use tracing::{debug, error};
 use uuid::Uuid;
 
 use crate::kernel::spawn_blocking_with_span;
-use crate::logstore::{DELTA_LOG_REGEX, LogStore};
+use crate::logstore::{DELTA_LOG_REGEX, LogStore, ObjectStoreRef};
 use crate::table::config::TablePropertiesExt as _;
 use crate::{DeltaResult, DeltaTableError};
 use crate::{DeltaTable, open_table_with_version};
@@ -29,6 +30,14 @@ use crate::{DeltaTable, open_table_with_version};
 static CHECKPOINT_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"_delta_log/(\d{20})\.(checkpoint).*$").unwrap());
 
+static COMPACTED_LOG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d{20}\.(\d{20})\.compacted\.json$").unwrap());
+
+static CHECKSUM_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d{20})\.crc$").unwrap());
+
+static PARTITION_STATS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"_partition_stats/(\d{20})\.partition_stats\.json$").unwrap());
+
 /// Creates checkpoint for a given table version, table state and object store
 #[tracing::instrument(skip(log_store), fields(operation = "checkpoint", version = version, table_uri = %log_store.root_url()))]
 pub(crate) async fn create_checkpoint_for(
@@ -48,40 +57,142 @@ pub(crate) async fn create_checkpoint_for(
     .await
     .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
 
+    // Multi-part checkpoints only exist for classic checkpoints
+    let part_size = snapshot
+        .table_properties()
+        .checkpoint_part_size()
+        .filter(|_| {
+            !snapshot
+                .table_configuration()
+                .is_feature_enabled(&TableFeature::V2Checkpoint)
+        });
+
     let cp_writer = snapshot.checkpoint()?;
 
     let cp_url = cp_writer.checkpoint_path()?;
     let cp_path = Path::from_url_path(cp_url.path())?;
     let mut cp_data = cp_writer.checkpoint_data(engine.as_ref())?;
+    let root_store = log_store.root_object_store(operation_id);
 
-    let (first_batch, mut cp_data) = spawn_blocking_with_span(move || {
-        let Some(first_batch) = cp_data.next() else {
+    let cp_data = if let Some(part_size) = part_size {
+        let (parts, cp_data) =
+            write_checkpoint_parts(&cp_url, root_store.clone(), cp_data, part_size.get()).await?;
+        if parts.files.len() > 1 {
+            // The kernel only knows how to finalize single file checkpoints,
+            // so the multi-part writer also takes care of `_last_checkpoint`.
+            return finalize_multi_part_checkpoint(version, &cp_url, root_store, parts).await;
+        }
+        let Some((temp_path, _)) = parts.files.first() else {
             return Err(DeltaTableError::Generic("No data".to_string()));
         };
-        Ok((to_rb(first_batch?)?, cp_data))
-    })
-    .await
-    .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
+        root_store.rename(temp_path, &cp_path).await?;
+        cp_data
+    } else {
+        let (first_batch, mut cp_data) = spawn_blocking_with_span(move || {
+            let Some(first_batch) = cp_data.next() else {
+                return Err(DeltaTableError::Generic("No data".to_string()));
+            };
+            Ok((to_rb(first_batch?)?, cp_data))
+        })
+        .await
+        .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
 
-    let root_store = log_store.root_object_store(operation_id);
-    let object_store_writer = ParquetObjectWriter::new(root_store.clone(), cp_path.clone());
-    let mut writer = AsyncArrowWriter::try_new(object_store_writer, first_batch.schema(), None)?;
-    writer.write(&first_batch).await?;
+        let object_store_writer = ParquetObjectWriter::new(root_store.clone(), cp_path.clone());
+        let mut writer =
+            AsyncArrowWriter::try_new(object_store_writer, first_batch.schema(), None)?;
+        writer.write(&first_batch).await?;
 
-    // Hold onto the schema used for future batches.
-    // This ensures that each batch is consistent since the kernel will yeet back the data that it
-    // read from prior checkpoints regardless of whether they are identical in schema.
-    //
-    // See: <https://github.com/delta-io/delta-rs/issues/3527>!
-    let checkpoint_schema = first_batch.schema();
+        // Hold onto the schema used for future batches.
+        // This ensures that each batch is consistent since the kernel will yeet back the data that it
+        // read from prior checkpoints regardless of whether they are identical in schema.
+        //
+        // See: <https://github.com/delta-io/delta-rs/issues/3527>!
+        let checkpoint_schema = first_batch.schema();
+
+        let mut current_batch;
+        loop {
+            (current_batch, cp_data) = spawn_blocking_with_span(move || {
+                let Some(first_batch) = cp_data.next() else {
+                    return Ok::<_, DeltaTableError>((None, cp_data));
+                };
+                Ok((Some(to_rb(first_batch?)?), cp_data))
+            })
+            .await
+            .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
+
+            let Some(batch) = current_batch else {
+                break;
+            };
+
+            // If the subsequently yielded batches do not match the first batch written for whatever
+            // reason, attempt to safely cast the batches to ensure a coherent checkpoint parquet file
+            //
+            // See also: <https://github.com/delta-io/delta-rs/issues/3527>
+            let batch = if batch.schema() != checkpoint_schema {
+                crate::cast_record_batch(&batch, checkpoint_schema.clone(), true, true)?
+            } else {
+                batch
+            };
+
+            writer.write(&batch).await?;
+        }
+
+        let _pq_meta = writer.close().await?;
+        cp_data
+    };
+
+    let file_meta = root_store.head(&cp_path).await?;
+    let file_meta = FileMeta {
+        location: cp_url,
+        size: file_meta.size,
+        last_modified: file_meta.last_modified.timestamp_millis(),
+    };
+
+    spawn_blocking_with_span(move || cp_writer.finalize(engine.as_ref(), &file_meta, cp_data))
+        .await
+        .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
+
+    Ok(())
+}
+
+/// Checkpoint parts written under temporary names by [`write_checkpoint_parts`]
+struct CheckpointParts {
+    /// Temporary path and size of each part, in order
+    files: Vec<(Path, u64)>,
+    num_actions: usize,
+    num_add_files: usize,
+}
+
+/// Write the checkpoint actions into parts of at most `part_size` actions each.
+///
+/// The number of parts is part of the final file names but only known once all actions have
+/// been read, so the parts are streamed into temporary files which the caller renames.
+async fn write_checkpoint_parts<I>(
+    cp_url: &Url,
+    root_store: ObjectStoreRef,
+    mut cp_data: I,
+    part_size: u64,
+) -> DeltaResult<(CheckpointParts, I)>
+where
+    I: Iterator<Item = delta_kernel::DeltaResult<FilteredEngineData>> + Send + 'static,
+{
+    let token = Uuid::new_v4();
+    let mut parts = CheckpointParts {
+        files: Vec::new(),
+        num_actions: 0,
+        num_add_files: 0,
+    };
+    let mut checkpoint_schema = None;
+    let mut writer: Option<(Path, AsyncArrowWriter<ParquetObjectWriter>)> = None;
+    let mut rows_in_part = 0;
 
     let mut current_batch;
     loop {
         (current_batch, cp_data) = spawn_blocking_with_span(move || {
-            let Some(first_batch) = cp_data.next() else {
+            let Some(batch) = cp_data.next() else {
                 return Ok::<_, DeltaTableError>((None, cp_data));
             };
-            Ok((Some(to_rb(first_batch?)?), cp_data))
+            Ok((Some(to_rb(batch?)?), cp_data))
         })
         .await
         .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
@@ -90,30 +201,117 @@ pub(crate) async fn create_checkpoint_for(
             break;
         };
 
-        // If the subsequently yielded batches do not match the first batch written for whatever
-        // reason, attempt to safely cast the batches to ensure a coherent checkpoint parquet file
-        //
-        // See also: <https://github.com/delta-io/delta-rs/issues/3527>
-        let batch = if batch.schema() != checkpoint_schema {
-            crate::cast_record_batch(&batch, checkpoint_schema.clone(), true, true)?
+        // See `create_checkpoint_for` for why the kernel may yield batches with differing schemas
+        let schema = checkpoint_schema
+            .get_or_insert_with(|| batch.schema())
+            .clone();
+        let batch = if batch.schema() != schema {
+            crate::cast_record_batch(&batch, schema.clone(), true, true)?
         } else {
             batch
         };
+        parts.num_actions += batch.num_rows();
+        parts.num_add_files += batch
+            .column_by_name("add")
+            .map(|add| add.len() - add.null_count())
+            .unwrap_or_default();
+
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            if rows_in_part == part_size
+                && let Some((path, part_writer)) = writer.take()
+            {
+                parts
+                    .files
+                    .push(close_checkpoint_part(&root_store, path, part_writer).await?);
+                rows_in_part = 0;
+            }
+            let (_, part_writer) = match &mut writer {
+                Some(writer) => writer,
+                None => {
+                    let path = checkpoint_file_path(
+                        cp_url,
+                        &format!("_checkpoint_{token}.{}.parquet.tmp", parts.files.len() + 1),
+                    )?;
+                    debug!("writing checkpoint part {path}");
+                    let object_store_writer =
+                        ParquetObjectWriter::new(root_store.clone(), path.clone());
+                    let part_writer =
+                        AsyncArrowWriter::try_new(object_store_writer, schema.clone(), None)?;
+                    writer.insert((path, part_writer))
+                }
+            };
+            let length = ((part_size - rows_in_part) as usize).min(batch.num_rows() - offset);
+            part_writer.write(&batch.slice(offset, length)).await?;
+            offset += length;
+            rows_in_part += length as u64;
+        }
+    }
 
-        writer.write(&batch).await?;
+    if let Some((path, part_writer)) = writer {
+        parts
+            .files
+            .push(close_checkpoint_part(&root_store, path, part_writer).await?);
     }
+    Ok((parts, cp_data))
+}
 
-    let _pq_meta = writer.close().await?;
-    let file_meta = root_store.head(&cp_path).await?;
-    let file_meta = FileMeta {
-        location: cp_url,
-        size: file_meta.size,
-        last_modified: file_meta.last_modified.timestamp_millis(),
-    };
+/// Close the writer of a checkpoint part, returns the path and size of the file.
+async fn close_checkpoint_part(
+    store: &ObjectStoreRef,
+    path: Path,
+    writer: AsyncArrowWriter<ParquetObjectWriter>,
+) -> DeltaResult<(Path, u64)> {
+    writer.close().await?;
+    let size = store.head(&path).await?.size;
+    Ok((path, size))
+}
 
-    spawn_blocking_with_span(move || cp_writer.finalize(engine.as_ref(), &file_meta, cp_data))
-        .await
-        .map_err(|e| DeltaTableError::Generic(e.to_string()))??;
+/// Resolve the path of a file next to the checkpoint at `cp_url`
+fn checkpoint_file_path(cp_url: &Url, name: &str) -> DeltaResult<Path> {
+    let url = cp_url
+        .join(name)
+        .map_err(|e| DeltaTableError::Generic(e.to_string()))?;
+    Ok(Path::from_url_path(url.path())?)
+}
+
+/// Move the parts of a classic multi-part checkpoint to their final names.
+///
+/// The `_last_checkpoint` hint is only written once all parts exist, since readers ignore
+/// incomplete multi-part checkpoints.
+async fn finalize_multi_part_checkpoint(
+    version: u64,
+    cp_url: &Url,
+    root_store: ObjectStoreRef,
+    parts: CheckpointParts,
+) -> DeltaResult<()> {
+    let num_parts = parts.files.len();
+    let mut size_in_bytes = 0;
+    for (idx, (temp_path, size)) in parts.files.iter().enumerate() {
+        let path = checkpoint_file_path(
+            cp_url,
+            &format!(
+                "{version:020}.checkpoint.{:010}.{num_parts:010}.parquet",
+                idx + 1
+            ),
+        )?;
+        root_store.rename(temp_path, &path).await?;
+        size_in_bytes += size;
+    }
+
+    let last_checkpoint = serde_json::json!({
+        "version": version,
+        "size": parts.num_actions,
+        "parts": num_parts,
+        "sizeInBytes": size_in_bytes,
+        "numOfAddFiles": parts.num_add_files,
+    });
+    root_store
+        .put(
+            &checkpoint_file_path(cp_url, "_last_checkpoint")?,
+            last_checkpoint.to_string().into_bytes().into(),
+        )
+        .await?;
 
     Ok(())
 }
@@ -182,8 +380,9 @@ pub async fn create_checkpoint_from_table_url_and_cleanup(
 
 /// Delete expired Delta log files up to a safe checkpoint boundary.
 ///
-/// This routine removes JSON commit files, in-progress JSON temp files, and
-/// checkpoint files under `_delta_log/` that are both:
+/// This routine removes JSON commit files, in-progress JSON temp files, checkpoint files,
+/// log compaction files, version checksum files and partition statistics summaries under
+/// `_delta_log/` that are both:
 /// - older than the provided `cutoff_timestamp` (milliseconds since epoch), and
 /// - strictly less than the provided `until_version`.
 ///
@@ -275,13 +474,8 @@ pub async fn cleanup_expired_logs_for(
                     return None;
                 }
             };
-            let path_str = meta.location.as_ref();
-            let captures = DELTA_LOG_REGEX.captures(path_str)?;
+            let log_ver = expired_log_file_version(meta.location.as_ref())?;
             let ts = meta.last_modified.timestamp_millis();
-            let log_ver_str = captures.get(1).unwrap().as_str();
-            let Ok(log_ver) = log_ver_str.parse::<i64>() else {
-                return None;
-            };
             if log_ver < safe_checkpoint_version && ts <= cutoff_timestamp {
                 debug!("file to delete: {:?}", meta.location);
                 Some(Ok(meta.location))
@@ -300,6 +494,21 @@ pub async fn cleanup_expired_logs_for(
     Ok(deleted.len())
 }
 
+/// The version up to which a file in `_delta_log` is needed, if it can expire at all.
+///
+/// Log compaction files are needed as long as the last commit they cover.
+fn expired_log_file_version(path: &str) -> Option<i64> {
+    DELTA_LOG_REGEX
+        .captures(path)
+        .or_else(|| COMPACTED_LOG_REGEX.captures(path))
+        .or_else(|| CHECKSUM_REGEX.captures(path))
+        .or_else(|| PARTITION_STATS_REGEX.captures(path))?
+        .get(1)?
+        .as_str()
+        .parse()
+        .ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -771,5 +980,134 @@ mod tests {
             assert_batches_sorted_eq!(&expected, &actual);
             Ok(())
         }
+
+        #[tokio::test]
+        async fn test_create_multi_part_checkpoint() -> DeltaResult<()> {
+            use crate::table::config::TableProperty;
+            use crate::writer::test_utils::get_arrow_schema;
+
+            let tmp_dir = tempfile::tempdir().unwrap();
+            let table_uri = ensure_table_uri(tmp_dir.path().to_str().unwrap())?;
+            let mut table = DeltaTable::try_from_url(table_uri.clone())
+                .await?
+                .create()
+                .with_columns(get_delta_schema().fields().cloned())
+                .with_configuration_property(TableProperty::CheckpointPartSize, Some("2"))
+                .await?;
+
+            for value in 0..5 {
+                let batch = RecordBatch::try_new(
+                    Arc::clone(&get_arrow_schema(&None)),
+                    vec![
+                        Arc::new(arrow::array::StringArray::from(vec!["A"])),
+                        Arc::new(arrow::array::Int32Array::from(vec![value])),
+                        Arc::new(arrow::array::StringArray::from(vec!["2021-02-02"])),
+                    ],
+                )
+                .unwrap();
+                table = table.write(vec![batch]).await?;
+            }
+            assert_eq!(table.version(), Some(5));
+
+            create_checkpoint(&table, None).await?;
+
+            // protocol, metadata and five add actions
+            let log_path = Path::from("_delta_log");
+            let store = table.log_store().object_store(None);
+            let last_checkpoint = read_last_checkpoint(store.as_ref(), &log_path)
+                .await?
+                .expect("Expected checkpoint hint");
+            assert_eq!(last_checkpoint.version, 5);
+            assert_eq!(last_checkpoint.parts, Some(4));
+            for part in 1..=4 {
+                let part_path =
+                    log_path.child(format!("{:020}.checkpoint.{part:010}.{:010}.parquet", 5, 4));
+                store.head(&part_path).await?;
+            }
+            assert!(
+                store
+                    .head(&log_path.child(format!("{:020}.checkpoint.parquet", 5)))
+                    .await
+                    .is_err()
+            );
+
+            let table = crate::open_table(table_uri).await?;
+            assert_eq!(table.version(), Some(5));
+            assert_eq!(table.snapshot()?.log_data().num_files(), 5);
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_cleanup_multi_part_checkpoint() -> DeltaResult<()> {
+            use crate::table::config::TableProperty;
+            use crate::writer::test_utils::get_arrow_schema;
+
+            let tmp_dir = tempfile::tempdir().unwrap();
+            let table_uri = ensure_table_uri(tmp_dir.path().to_str().unwrap())?;
+            let mut table = DeltaTable::try_from_url(table_uri.clone())
+                .await?
+                .create()
+                .with_columns(get_delta_schema().fields().cloned())
+                .with_configuration_property(TableProperty::CheckpointPartSize, Some("2"))
+                .await?;
+
+            for value in 0..5 {
+                let batch = RecordBatch::try_new(
+                    Arc::clone(&get_arrow_schema(&None)),
+                    vec![
+                        Arc::new(arrow::array::StringArray::from(vec!["A"])),
+                        Arc::new(arrow::array::Int32Array::from(vec![value])),
+                        Arc::new(arrow::array::StringArray::from(vec!["2021-02-02"])),
+                    ],
+                )
+                .unwrap();
+                table = table.write(vec![batch]).await?;
+                if value == 1 {
+                    create_checkpoint(&table, None).await?;
+                }
+            }
+            create_checkpoint(&table, None).await?;
+
+            let log_path = Path::from("_delta_log");
+            let store = table.log_store().object_store(None);
+            let old_parts = (1..=2)
+                .map(|part| {
+                    log_path.child(format!("{:020}.checkpoint.{part:010}.{:010}.parquet", 2, 2))
+                })
+                .collect::<Vec<_>>();
+            for part in &old_parts {
+                store.head(part).await?;
+            }
+
+            let log_retention_timestamp = (Utc::now().timestamp_millis()
+                + Duration::days(32).num_milliseconds())
+                - table
+                    .snapshot()?
+                    .table_config()
+                    .log_retention_duration()
+                    .as_millis() as i64;
+            let count = cleanup_expired_logs_for(
+                table.version().unwrap(),
+                table.log_store().as_ref(),
+                log_retention_timestamp,
+                None,
+            )
+            .await?;
+            // commits 0 to 4 and both parts of the checkpoint at version 2
+            assert_eq!(count, 7);
+            for part in &old_parts {
+                assert!(store.head(part).await.is_err());
+            }
+            for part in 1..=4 {
+                let part_path =
+                    log_path.child(format!("{:020}.checkpoint.{part:010}.{:010}.parquet", 5, 4));
+                store.head(&part_path).await?;
+            }
+
+            let table = crate::open_table(table_uri).await?;
+            assert_eq!(table.version(), Some(5));
+            assert_eq!(table.snapshot()?.log_data().num_files(), 5);
+            Ok(())
+        }
     }
 }
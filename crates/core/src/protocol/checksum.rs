@@ -0,0 +1,473 @@
+//! Implementation for reading and writing version checksum files.
+//!
+//! A version checksum file `<version>.crc` summarizes the state of the table at a version, i.e.
+//! its size, number of files, protocol and metadata. Readers can use it to validate the
+//! reconstructed table state.
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::{Error as ObjectStoreError, ObjectStore};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::kernel::{Action, DomainMetadata, EagerSnapshot, Metadata, Protocol, Transaction};
+use crate::logstore::{LogStore, get_actions};
+use crate::{DeltaResult, DeltaTableError};
+
+const ENABLE_IN_COMMIT_TIMESTAMPS_KEY: &str = "delta.enableInCommitTimestamps";
+const IN_COMMIT_TIMESTAMP_KEY: &str = "inCommitTimestamp";
+
+/// The content of a version checksum file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionChecksum {
+    /// A unique identifier for the transaction that produced this commit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_id: Option<String>,
+    /// Total size of the table in bytes
+    pub table_size_bytes: i64,
+    /// Number of active files in the table
+    pub num_files: i64,
+    /// Number of metadata actions, always 1
+    pub num_metadata: i64,
+    /// Number of protocol actions, always 1
+    pub num_protocol: i64,
+    /// The in-commit timestamp of this version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_commit_timestamp_opt: Option<i64>,
+    /// The live application transactions of the table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_transactions: Option<Vec<Transaction>>,
+    /// The live domain metadata of the table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_metadata: Option<Vec<DomainMetadata>>,
+    /// The table metadata
+    pub metadata: Metadata,
+    /// The table protocol
+    pub protocol: Protocol,
+}
+
+impl VersionChecksum {
+    /// Compute the checksum of the version following `previous` from the actions of its commit.
+    ///
+    /// Without a previous checksum the commit has to create the table. Returns `None` if the
+    /// checksum cannot be derived from the commit alone, i.e. when removes do not record the
+    /// file size or files are re-added without being removed.
+    pub(crate) fn try_apply_commit(previous: Option<&Self>, actions: &[Action]) -> Option<Self> {
+        let mut protocol = previous.map(|p| p.protocol.clone());
+        let mut metadata = previous.map(|p| p.metadata.clone());
+        let (mut table_size_bytes, mut num_files) = previous
+            .map(|p| (p.table_size_bytes, p.num_files))
+            .unwrap_or_default();
+        let mut txns: Option<HashMap<_, _>> = match previous {
+            Some(p) => p.set_transactions.as_ref().map(|txns| {
+                txns.iter()
+                    .map(|txn| (txn.app_id.clone(), txn.clone()))
+                    .collect()
+            }),
+            None => Some(HashMap::new()),
+        };
+        let mut domains: Option<HashMap<_, _>> = match previous {
+            Some(p) => p.domain_metadata.as_ref().map(|domains| {
+                domains
+                    .iter()
+                    .map(|domain| (domain.domain.clone(), domain.clone()))
+                    .collect()
+            }),
+            None => Some(HashMap::new()),
+        };
+
+        let has_removes = actions.iter().any(|a| matches!(a, Action::Remove(_)));
+        for action in actions {
+            match action {
+                Action::Protocol(p) => protocol = Some(p.clone()),
+                Action::Metadata(m) => metadata = Some(m.clone()),
+                Action::Add(add) => {
+                    // Adds without data change and without any removes, re-add existing files
+                    if !add.data_change && !has_removes {
+                        return None;
+                    }
+                    table_size_bytes += add.size;
+                    num_files += 1;
+                }
+                Action::Remove(remove) => {
+                    table_size_bytes -= remove.size?;
+                    num_files -= 1;
+                }
+                Action::Txn(txn) => {
+                    if let Some(txns) = txns.as_mut() {
+                        txns.insert(txn.app_id.clone(), txn.clone());
+                    }
+                }
+                Action::DomainMetadata(domain) => {
+                    if let Some(domains) = domains.as_mut() {
+                        if domain.removed {
+                            domains.remove(&domain.domain);
+                        } else {
+                            domains.insert(domain.domain.clone(), domain.clone());
+                        }
+                    }
+                }
+                Action::Cdc(_) | Action::CommitInfo(_) => {}
+            }
+        }
+
+        let metadata = metadata?;
+        Some(Self {
+            txn_id: None,
+            table_size_bytes,
+            num_files,
+            num_metadata: 1,
+            num_protocol: 1,
+            in_commit_timestamp_opt: in_commit_timestamp(&metadata, actions),
+            set_transactions: txns.map(|txns| txns.into_values().collect()),
+            domain_metadata: domains.map(|domains| domains.into_values().collect()),
+            metadata,
+            protocol: protocol?,
+        })
+    }
+
+    /// Compute the checksum from a snapshot of the table.
+    ///
+    /// Application transactions and domain metadata cannot be enumerated from a snapshot and
+    /// are omitted.
+    pub(crate) async fn try_from_snapshot(
+        snapshot: &EagerSnapshot,
+        log_store: &dyn LogStore,
+    ) -> DeltaResult<Self> {
+        let (table_size_bytes, num_files) = snapshot
+            .file_views(log_store, None)
+            .try_fold((0, 0), |(size, count), file| async move {
+                Ok((size + file.size(), count + 1))
+            })
+            .await?;
+        let in_commit_timestamp_opt = if in_commit_timestamps_enabled(snapshot.metadata()) {
+            let version = snapshot.version();
+            match log_store.read_commit_entry(version).await? {
+                Some(bytes) => {
+                    in_commit_timestamp(snapshot.metadata(), &get_actions(version, &bytes)?)
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            txn_id: None,
+            table_size_bytes,
+            num_files,
+            num_metadata: 1,
+            num_protocol: 1,
+            in_commit_timestamp_opt,
+            set_transactions: None,
+            domain_metadata: None,
+            metadata: snapshot.metadata().clone(),
+            protocol: snapshot.protocol().clone(),
+        })
+    }
+
+    /// Compare the checksum with the state of the table, returns a description of every
+    /// mismatching field.
+    pub(crate) async fn mismatches(
+        &self,
+        snapshot: &EagerSnapshot,
+        log_store: &dyn LogStore,
+    ) -> DeltaResult<Vec<String>> {
+        let expected = Self::try_from_snapshot(snapshot, log_store).await?;
+        let mut mismatches = Vec::new();
+        if self.table_size_bytes != expected.table_size_bytes {
+            mismatches.push(format!(
+                "tableSizeBytes is {} but the table has {} bytes",
+                self.table_size_bytes, expected.table_size_bytes
+            ));
+        }
+        if self.num_files != expected.num_files {
+            mismatches.push(format!(
+                "numFiles is {} but the table has {} files",
+                self.num_files, expected.num_files
+            ));
+        }
+        if self.protocol != expected.protocol {
+            mismatches.push("protocol does not match the table protocol".to_string());
+        }
+        if self.metadata != expected.metadata {
+            mismatches.push("metadata does not match the table metadata".to_string());
+        }
+        for txn in self.set_transactions.iter().flatten() {
+            let version = snapshot.transaction_version(log_store, &txn.app_id).await?;
+            if version != Some(txn.version) {
+                mismatches.push(format!(
+                    "setTransactions has version {} for application '{}' but the table has {version:?}",
+                    txn.version, txn.app_id
+                ));
+            }
+        }
+        for domain in self.domain_metadata.iter().flatten() {
+            let configuration = snapshot.domain_metadata(log_store, &domain.domain).await?;
+            if configuration.as_ref() != Some(&domain.configuration) {
+                mismatches.push(format!(
+                    "domainMetadata does not match the table configuration of domain '{}'",
+                    domain.domain
+                ));
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+fn in_commit_timestamps_enabled(metadata: &Metadata) -> bool {
+    metadata
+        .configuration()
+        .get(ENABLE_IN_COMMIT_TIMESTAMPS_KEY)
+        .is_some_and(|enabled| enabled == "true")
+}
+
+/// The in-commit timestamp recorded in the commit info, if in-commit timestamps are enabled
+fn in_commit_timestamp(metadata: &Metadata, actions: &[Action]) -> Option<i64> {
+    if !in_commit_timestamps_enabled(metadata) {
+        return None;
+    }
+    actions.iter().find_map(|action| match action {
+        Action::CommitInfo(info) => info
+            .info
+            .get(IN_COMMIT_TIMESTAMP_KEY)
+            .and_then(|timestamp| timestamp.as_i64()),
+        _ => None,
+    })
+}
+
+fn checksum_path(log_store: &dyn LogStore, version: i64) -> Path {
+    log_store.log_path().child(format!("{version:020}.crc"))
+}
+
+/// Read the version checksum file of the given version, if it exists
+pub(crate) async fn read_checksum(
+    log_store: &dyn LogStore,
+    version: i64,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<Option<VersionChecksum>> {
+    let path = checksum_path(log_store, version);
+    let data = match log_store.object_store(operation_id).get(&path).await {
+        Ok(data) => data.bytes().await?,
+        Err(ObjectStoreError::NotFound { .. }) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let checksum = serde_json::from_slice(&data).map_err(|json_err| {
+        let line = format!(
+            "Error at line {}, column {}",
+            json_err.line(),
+            json_err.column()
+        );
+        DeltaTableError::InvalidJsonLog {
+            json_err,
+            line,
+            version,
+        }
+    })?;
+    Ok(Some(checksum))
+}
+
+/// Write the version checksum file of the given version
+pub(crate) async fn write_checksum(
+    log_store: &dyn LogStore,
+    version: i64,
+    checksum: &VersionChecksum,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<()> {
+    let path = checksum_path(log_store, version);
+    debug!("writing version checksum {path}");
+    log_store
+        .object_store(operation_id)
+        .put(&path, Bytes::from(serde_json::to_vec(checksum)?).into())
+        .await?;
+    Ok(())
+}
+
+/// Write the checksum of a committed version incrementally from the checksum of the previous
+/// version and the actions of the commit.
+///
+/// Returns whether a checksum file was written, which requires the checksum of the previous
+/// version to exist unless the commit created the table.
+pub(crate) async fn create_checksum_for(
+    version: i64,
+    actions: &[Action],
+    log_store: &dyn LogStore,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<bool> {
+    let previous = if version > 0 {
+        match read_checksum(log_store, version - 1, operation_id).await {
+            Ok(Some(previous)) => Some(previous),
+            Ok(None) => {
+                debug!("no checksum for version {}, skipping", version - 1);
+                return Ok(false);
+            }
+            Err(err @ DeltaTableError::InvalidJsonLog { .. }) => {
+                warn!(
+                    "ignoring invalid checksum for version {}: {err}",
+                    version - 1
+                );
+                return Ok(false);
+            }
+            Err(err) => return Err(err),
+        }
+    } else {
+        None
+    };
+
+    let Some(checksum) = VersionChecksum::try_apply_commit(previous.as_ref(), actions) else {
+        debug!("checksum of version {version} cannot be derived from its commit, skipping");
+        return Ok(false);
+    };
+    write_checksum(log_store, version, &checksum, operation_id).await?;
+    Ok(true)
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use super::*;
+    use crate::DeltaTable;
+    use crate::kernel::transaction::CommitProperties;
+    use crate::protocol::SaveMode;
+    use crate::writer::test_utils::{get_delta_schema, get_record_batch_from_rows};
+
+    #[tokio::test]
+    async fn test_incremental_checksum() -> DeltaResult<()> {
+        let mut table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_commit_properties(CommitProperties::default().with_create_checksum(true))
+            .await?;
+        let checksum = read_checksum(table.log_store().as_ref(), 0, None)
+            .await?
+            .expect("checksum of the created table");
+        assert_eq!((checksum.num_files, checksum.table_size_bytes), (0, 0));
+
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-02")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 2, "2021-02-02")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 3, "2021-02-02")])])
+            .with_save_mode(SaveMode::Overwrite)
+            .await?;
+        assert_eq!(table.version(), Some(3));
+
+        let log_store = table.log_store();
+        let snapshot = table.snapshot()?.snapshot();
+        let checksum = read_checksum(log_store.as_ref(), 3, None)
+            .await?
+            .expect("incremental checksum");
+        assert_eq!(checksum.num_files, 1);
+        assert_eq!(
+            checksum,
+            VersionChecksum {
+                set_transactions: checksum.set_transactions.clone(),
+                domain_metadata: checksum.domain_metadata.clone(),
+                ..VersionChecksum::try_from_snapshot(snapshot, log_store.as_ref()).await?
+            }
+        );
+        assert!(
+            checksum
+                .mismatches(snapshot, log_store.as_ref())
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_commit_timestamp() -> DeltaResult<()> {
+        use crate::kernel::{CommitInfo, MetadataExt as _};
+
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_commit_properties(CommitProperties::default().with_create_checksum(true))
+            .await?;
+        let previous = read_checksum(table.log_store().as_ref(), 0, None)
+            .await?
+            .expect("checksum of the created table");
+        let commit_info = Action::CommitInfo(CommitInfo {
+            info: HashMap::from([(
+                IN_COMMIT_TIMESTAMP_KEY.to_string(),
+                1_700_000_000_000.into(),
+            )]),
+            ..Default::default()
+        });
+
+        let checksum =
+            VersionChecksum::try_apply_commit(Some(&previous), &[commit_info.clone()]).unwrap();
+        assert_eq!(checksum.in_commit_timestamp_opt, None);
+
+        let metadata = previous.metadata.clone().add_config_key(
+            ENABLE_IN_COMMIT_TIMESTAMPS_KEY.to_string(),
+            "true".to_string(),
+        )?;
+        let checksum = VersionChecksum::try_apply_commit(
+            Some(&previous),
+            &[Action::Metadata(metadata), commit_info],
+        )
+        .unwrap();
+        assert_eq!(checksum.in_commit_timestamp_opt, Some(1_700_000_000_000));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_expired_checksums() -> DeltaResult<()> {
+        use chrono::Utc;
+
+        use crate::protocol::checkpoints::{cleanup_expired_logs_for, create_checkpoint};
+
+        let mut table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_commit_properties(CommitProperties::default().with_create_checksum(true))
+            .await?;
+        for value in 0..2 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        create_checkpoint(&table, None).await?;
+
+        // commits and checksums of versions 0 and 1
+        let count = cleanup_expired_logs_for(
+            table.version().unwrap(),
+            table.log_store().as_ref(),
+            Utc::now().timestamp_millis() + 60_000,
+            None,
+        )
+        .await?;
+        assert_eq!(count, 4);
+        let log_store = table.log_store();
+        assert!(read_checksum(log_store.as_ref(), 1, None).await?.is_none());
+        assert!(read_checksum(log_store.as_ref(), 2, None).await?.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_checksum_without_previous() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-02")])])
+            .await?;
+        assert!(
+            read_checksum(table.log_store().as_ref(), 1, None)
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+}
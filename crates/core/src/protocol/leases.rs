@@ -0,0 +1,206 @@
+//! Implementation for reading and writing reader leases.
+//!
+//! Long running readers can record a lease `_delta_log/_leases/<id>.json` for the version they
+//! read. Vacuum keeps the files of the versions referenced by live leases, so these readers do
+//! not fail when files of their version are removed from the table and vacuumed. Leases expire,
+//! so a reader that crashes without releasing its lease does not block vacuum forever. Expired
+//! leases are removed by vacuum runs that are not dry runs, or by [`delete_expired_leases`].
+use bytes::Bytes;
+use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::{Error as ObjectStoreError, ObjectStore};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::DeltaResult;
+use crate::logstore::LogStore;
+
+/// A lease of a reader on a version of the table
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReaderLease {
+    /// Unique id of the lease
+    pub id: String,
+    /// The version of the table the reader reads
+    pub version: i64,
+    /// Expiration time of the lease in milliseconds since epoch
+    pub expires_at: i64,
+}
+
+impl ReaderLease {
+    /// Whether the lease has not yet expired at the given time in milliseconds since epoch
+    pub fn is_live(&self, now_millis: i64) -> bool {
+        self.expires_at > now_millis
+    }
+}
+
+fn leases_path(log_store: &dyn LogStore) -> Path {
+    log_store.log_path().child("_leases")
+}
+
+fn lease_path(log_store: &dyn LogStore, id: &str) -> Path {
+    leases_path(log_store).child(format!("{id}.json"))
+}
+
+/// Read all leases recorded for the table, including expired ones.
+///
+/// Lease files that cannot be parsed are skipped.
+pub async fn read_leases(log_store: &dyn LogStore) -> DeltaResult<Vec<ReaderLease>> {
+    let store = log_store.object_store(None);
+    let files: Vec<_> = store
+        .list(Some(&leases_path(log_store)))
+        .try_collect()
+        .await?;
+
+    let mut leases = Vec::with_capacity(files.len());
+    for file in files {
+        let data = match store.get(&file.location).await {
+            Ok(data) => data.bytes().await?,
+            // the lease was released while listing
+            Err(ObjectStoreError::NotFound { .. }) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        match serde_json::from_slice(&data) {
+            Ok(lease) => leases.push(lease),
+            Err(err) => warn!("ignoring unreadable lease {}: {err}", file.location),
+        }
+    }
+    Ok(leases)
+}
+
+/// The versions referenced by leases that are live at the given time
+pub(crate) async fn live_lease_versions(
+    log_store: &dyn LogStore,
+    now_millis: i64,
+) -> DeltaResult<Vec<i64>> {
+    Ok(read_leases(log_store)
+        .await?
+        .into_iter()
+        .filter(|lease| lease.is_live(now_millis))
+        .map(|lease| lease.version)
+        .collect())
+}
+
+/// Remove the leases that expired at the given time in milliseconds since epoch.
+///
+/// Returns the ids of the removed leases. Vacuum calls this when it is not a dry run.
+pub async fn delete_expired_leases(
+    log_store: &dyn LogStore,
+    now_millis: i64,
+) -> DeltaResult<Vec<String>> {
+    let mut deleted = Vec::new();
+    for lease in read_leases(log_store).await? {
+        if !lease.is_live(now_millis) {
+            debug!("removing expired reader lease {}", lease.id);
+            delete_lease(log_store, &lease.id).await?;
+            deleted.push(lease.id);
+        }
+    }
+    Ok(deleted)
+}
+
+/// Record or renew a lease
+pub(crate) async fn write_lease(log_store: &dyn LogStore, lease: &ReaderLease) -> DeltaResult<()> {
+    let path = lease_path(log_store, &lease.id);
+    debug!("writing reader lease {path}");
+    log_store
+        .object_store(None)
+        .put(&path, Bytes::from(serde_json::to_vec(lease)?).into())
+        .await?;
+    Ok(())
+}
+
+/// Remove a lease, removing a lease that does not exist is not an error
+pub(crate) async fn delete_lease(log_store: &dyn LogStore, id: &str) -> DeltaResult<()> {
+    match log_store
+        .object_store(None)
+        .delete(&lease_path(log_store, id))
+        .await
+    {
+        Ok(()) | Err(ObjectStoreError::NotFound { .. }) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeltaTable;
+    use crate::writer::test_utils::get_delta_schema;
+
+    #[tokio::test]
+    async fn test_leases_roundtrip() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let log_store = table.log_store();
+
+        let live = ReaderLease {
+            id: "live".to_string(),
+            version: 0,
+            expires_at: 2_000,
+        };
+        let expired = ReaderLease {
+            id: "expired".to_string(),
+            version: 1,
+            expires_at: 500,
+        };
+        write_lease(log_store.as_ref(), &live).await?;
+        write_lease(log_store.as_ref(), &expired).await?;
+        log_store
+            .object_store(None)
+            .put(
+                &lease_path(log_store.as_ref(), "corrupt"),
+                Bytes::from_static(b"{").into(),
+            )
+            .await?;
+
+        assert_eq!(read_leases(log_store.as_ref()).await?.len(), 2);
+        assert_eq!(
+            live_lease_versions(log_store.as_ref(), 1_000).await?,
+            vec![0]
+        );
+
+        delete_lease(log_store.as_ref(), "live").await?;
+        delete_lease(log_store.as_ref(), "live").await?;
+        assert!(
+            live_lease_versions(log_store.as_ref(), 1_000)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_expired_leases() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let log_store = table.log_store();
+
+        for (id, expires_at) in [("live", 2_000), ("expired", 500)] {
+            let lease = ReaderLease {
+                id: id.to_string(),
+                version: 0,
+                expires_at,
+            };
+            write_lease(log_store.as_ref(), &lease).await?;
+        }
+
+        assert_eq!(
+            delete_expired_leases(log_store.as_ref(), 1_000).await?,
+            vec!["expired".to_string()]
+        );
+        let leases = read_leases(log_store.as_ref()).await?;
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].id, "live");
+        assert!(
+            delete_expired_leases(log_store.as_ref(), 1_000)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+}
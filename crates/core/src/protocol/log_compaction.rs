@@ -0,0 +1,375 @@
+//! Implementation for writing delta log compaction files.
+//!
+//! A log compaction file `<x>.<y>.compacted.json` contains the reconciled actions of the commits
+//! `x` through `y`, which allows readers to replay a single file instead of every commit in the
+//! range. Snapshots pick compaction files up through the log segment of the kernel, which
+//! replays a compaction file in place of the commits it covers. The individual commits are kept
+//! and expire together with the compaction files in
+//! [`cleanup_expired_logs_for`](crate::protocol::checkpoints::cleanup_expired_logs_for).
+use std::collections::{HashMap, HashSet};
+
+use bytes::Bytes;
+use object_store::ObjectStore;
+use object_store::path::Path;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::kernel::{Action, DeletionVectorDescriptor};
+use crate::logstore::{LogStore, get_actions};
+use crate::{DeltaResult, DeltaTable, DeltaTableError};
+
+/// Creates a log compaction file for the commits `start_version` through `end_version` of the
+/// table.
+pub async fn create_log_compaction(
+    table: &DeltaTable,
+    start_version: i64,
+    end_version: i64,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<()> {
+    let table_version = table.version().ok_or(DeltaTableError::NotInitialized)?;
+    if end_version > table_version {
+        return Err(DeltaTableError::InvalidVersion(end_version));
+    }
+    create_log_compaction_for(
+        start_version,
+        end_version,
+        table.log_store.as_ref(),
+        operation_id,
+    )
+    .await
+}
+
+/// Creates a log compaction file for the commits `start_version` through `end_version`
+#[tracing::instrument(skip(log_store), fields(operation = "log_compaction", table_uri = %log_store.root_url()))]
+pub(crate) async fn create_log_compaction_for(
+    start_version: i64,
+    end_version: i64,
+    log_store: &dyn LogStore,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<()> {
+    if start_version < 0 || start_version >= end_version {
+        return Err(DeltaTableError::Generic(format!(
+            "Invalid log compaction range [{start_version}, {end_version}], \
+             the start version must be smaller than the end version"
+        )));
+    }
+
+    let mut commits = Vec::with_capacity((end_version - start_version + 1) as usize);
+    for version in start_version..=end_version {
+        let bytes = log_store
+            .read_commit_entry(version)
+            .await?
+            .ok_or(DeltaTableError::InvalidVersion(version))?;
+        commits.push(get_actions(version, &bytes)?);
+    }
+
+    let actions = reconcile_actions(commits);
+    let mut jsons = Vec::with_capacity(actions.len());
+    for action in &actions {
+        jsons.push(serde_json::to_string(action)?);
+    }
+
+    let path = compaction_path(log_store, start_version, end_version);
+    debug!(
+        "writing log compaction file {path} with {} actions",
+        actions.len()
+    );
+    log_store
+        .object_store(operation_id)
+        .put(&path, Bytes::from(jsons.join("\n")).into())
+        .await?;
+
+    Ok(())
+}
+
+fn compaction_path(log_store: &dyn LogStore, start_version: i64, end_version: i64) -> Path {
+    log_store.log_path().child(format!(
+        "{start_version:020}.{end_version:020}.compacted.json"
+    ))
+}
+
+/// Reconcile the actions of the given commits, ordered by ascending version.
+///
+/// Only the latest protocol, metadata, transaction per application and domain metadata per domain
+/// are retained. For file actions the latest `add` or `remove` per file wins, removes are kept as
+/// tombstones since the file may have been added before the compacted range. Commit infos and
+/// change data files only describe individual commits and are dropped.
+fn reconcile_actions(commits: Vec<Vec<Action>>) -> Vec<Action> {
+    let mut protocol = None;
+    let mut metadata = None;
+    let mut txns = HashMap::new();
+    let mut domains = HashMap::new();
+    let mut seen_files = HashSet::new();
+    let mut files = Vec::new();
+
+    for action in commits.into_iter().rev().flatten() {
+        match action {
+            Action::Protocol(p) => {
+                protocol.get_or_insert(p);
+            }
+            Action::Metadata(m) => {
+                metadata.get_or_insert(m);
+            }
+            Action::Txn(txn) => {
+                txns.entry(txn.app_id.clone()).or_insert(txn);
+            }
+            Action::DomainMetadata(domain) => {
+                domains.entry(domain.domain.clone()).or_insert(domain);
+            }
+            Action::Add(add) => {
+                let key = (add.path.clone(), dv_unique_id(add.deletion_vector.as_ref()));
+                if seen_files.insert(key) {
+                    files.push(Action::Add(add));
+                }
+            }
+            Action::Remove(remove) => {
+                let key = (
+                    remove.path.clone(),
+                    dv_unique_id(remove.deletion_vector.as_ref()),
+                );
+                if seen_files.insert(key) {
+                    files.push(Action::Remove(remove));
+                }
+            }
+            Action::CommitInfo(_) | Action::Cdc(_) => {}
+        }
+    }
+
+    protocol
+        .map(Action::Protocol)
+        .into_iter()
+        .chain(metadata.map(Action::Metadata))
+        .chain(txns.into_values().map(Action::Txn))
+        .chain(domains.into_values().map(Action::DomainMetadata))
+        .chain(files.into_iter().rev())
+        .collect()
+}
+
+/// Files are identified by their path together with the unique id of their deletion vector
+fn dv_unique_id(dv: Option<&DeletionVectorDescriptor>) -> Option<String> {
+    dv.map(|dv| match dv.offset {
+        Some(offset) => format!("{}{}@{offset}", dv.storage_type, dv.path_or_inline_dv),
+        None => format!("{}{}", dv.storage_type, dv.path_or_inline_dv),
+    })
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::kernel::transaction::CommitProperties;
+    use crate::protocol::SaveMode;
+    use crate::writer::test_utils::{get_delta_schema, get_record_batch_from_rows};
+
+    #[tokio::test]
+    async fn test_create_log_compaction() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = crate::ensure_table_uri(tmp_dir.path().to_str().unwrap())?;
+        let mut table = DeltaTable::try_from_url(table_uri.clone())
+            .await?
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-02")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("B", 2, "2021-02-02")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("C", 3, "2021-02-02")])])
+            .with_save_mode(SaveMode::Overwrite)
+            .await?;
+        assert_eq!(table.version(), Some(3));
+
+        create_log_compaction(&table, 1, 3, None).await?;
+
+        let path = compaction_path(table.log_store().as_ref(), 1, 3);
+        let bytes = table
+            .log_store()
+            .object_store(None)
+            .get(&path)
+            .await?
+            .bytes()
+            .await?;
+        let actions = get_actions(3, &bytes)?;
+        let adds = actions
+            .iter()
+            .filter(|a| matches!(a, Action::Add(_)))
+            .count();
+        let removes = actions
+            .iter()
+            .filter(|a| matches!(a, Action::Remove(_)))
+            .count();
+        assert_eq!((adds, removes), (1, 2));
+        assert!(
+            !actions
+                .iter()
+                .any(|a| matches!(a, Action::CommitInfo(_) | Action::Protocol(_)))
+        );
+
+        // the table still loads with the compaction file in the log
+        let table = crate::open_table(table_uri).await?;
+        assert_eq!(table.version(), Some(3));
+        let files: Vec<_> = table
+            .snapshot()?
+            .snapshot()
+            .file_views(&table.log_store, None)
+            .try_collect()
+            .await?;
+        assert_eq!(files.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_replays_log_compaction() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = crate::ensure_table_uri(tmp_dir.path().to_str().unwrap())?;
+        let mut table = DeltaTable::try_from_url(table_uri.clone())
+            .await?
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        for value in 0..3 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        create_log_compaction(&table, 1, 3, None).await?;
+
+        // Replace a commit inside the compacted range, a snapshot replaying the compaction file
+        // in place of the commits does not see the file it adds.
+        let store = table.log_store().object_store(None);
+        let mut actions = get_actions(2, &table.log_store().read_commit_entry(2).await?.unwrap())?;
+        for action in actions.iter_mut() {
+            if let Action::Add(add) = action {
+                add.path = "phantom.parquet".to_string();
+            }
+        }
+        let jsons = actions
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        store
+            .put(
+                &table
+                    .log_store()
+                    .log_path()
+                    .child(format!("{:020}.json", 2)),
+                Bytes::from(jsons.join("\n")).into(),
+            )
+            .await?;
+
+        let table = crate::open_table(table_uri).await?;
+        assert_eq!(table.version(), Some(3));
+        let files: Vec<_> = table
+            .snapshot()?
+            .snapshot()
+            .file_views(&table.log_store, None)
+            .try_collect()
+            .await?;
+        assert_eq!(files.len(), 3);
+        assert!(
+            files
+                .iter()
+                .all(|file| file.path().as_ref() != "phantom.parquet")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_log_compaction() -> DeltaResult<()> {
+        use chrono::Utc;
+
+        use crate::protocol::checkpoints::{cleanup_expired_logs_for, create_checkpoint};
+
+        let mut table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        for value in 0..4 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        create_log_compaction(&table, 0, 1, None).await?;
+        create_log_compaction(&table, 2, 4, None).await?;
+        create_checkpoint(&table, None).await?;
+
+        let count = cleanup_expired_logs_for(
+            table.version().unwrap(),
+            table.log_store().as_ref(),
+            Utc::now().timestamp_millis() + 60_000,
+            None,
+        )
+        .await?;
+        assert!(count > 0);
+
+        // compaction files are kept as long as the last commit they cover
+        let store = table.log_store().object_store(None);
+        assert!(
+            store
+                .head(&compaction_path(table.log_store().as_ref(), 0, 1))
+                .await
+                .is_err()
+        );
+        store
+            .head(&compaction_path(table.log_store().as_ref(), 2, 4))
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_compaction_post_commit_hook() -> DeltaResult<()> {
+        let mut table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        for value in 0..4 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .with_commit_properties(
+                    CommitProperties::default().with_log_compaction_interval(Some(2)),
+                )
+                .await?;
+        }
+        assert_eq!(table.version(), Some(4));
+
+        let store = table.log_store().object_store(None);
+        for (start, end) in [(0, 1), (2, 3)] {
+            let path = compaction_path(table.log_store().as_ref(), start, end);
+            store.head(&path).await?;
+        }
+        assert!(
+            store
+                .head(&compaction_path(table.log_store().as_ref(), 3, 4))
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_range() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        assert!(create_log_compaction(&table, 0, 0, None).await.is_err());
+        assert!(create_log_compaction(&table, 0, 1, None).await.is_err());
+        Ok(())
+    }
+}
@@ -18,8 +18,15 @@ use crate::errors::{DeltaResult, DeltaTableError};
 use crate::kernel::{Add, CommitInfo, Metadata, Protocol, Remove, StructField, TableFeatures};
 
 pub mod checkpoints;
+pub mod checksum;
+pub mod leases;
+pub mod log_compaction;
+pub mod partition_stats;
 
 pub(crate) use checkpoints::{cleanup_expired_logs_for, create_checkpoint_for};
+pub(crate) use checksum::create_checksum_for;
+pub(crate) use log_compaction::create_log_compaction_for;
+pub(crate) use partition_stats::create_partition_stats_for;
 
 /// Struct used to represent minValues and maxValues in add action statistics.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -335,6 +342,13 @@ pub enum DeltaOperation {
     /// Represents a `FileSystemCheck` operation
     FileSystemCheck {},
 
+    #[serde(rename_all = "camelCase")]
+    /// Represents a `ComputeStats` operation re-adding files with fresh statistics
+    ComputeStats {
+        /// Whether statistics were recomputed for all files instead of only incomplete ones
+        recompute: bool,
+    },
+
     /// Represents a `Restore` operation
     Restore {
         /// Version to restore
@@ -392,6 +406,7 @@ impl DeltaOperation {
             DeltaOperation::SetTableProperties { .. } => "SET TBLPROPERTIES",
             DeltaOperation::Optimize { .. } => "OPTIMIZE",
             DeltaOperation::FileSystemCheck { .. } => "FSCK",
+            DeltaOperation::ComputeStats { .. } => "COMPUTE STATS",
             DeltaOperation::Restore { .. } => "RESTORE",
             DeltaOperation::VacuumStart { .. } => "VACUUM START",
             DeltaOperation::VacuumEnd { .. } => "VACUUM END",
@@ -434,6 +449,7 @@ impl DeltaOperation {
     pub fn changes_data(&self) -> bool {
         match self {
             Self::Optimize { .. }
+            | Self::ComputeStats { .. }
             | Self::UpdateFieldMetadata { .. }
             | Self::UpdateTableMetadata { .. }
             | Self::SetTableProperties { .. }
@@ -0,0 +1,699 @@
+//! Implementation for reading and writing partition statistics summaries.
+//!
+//! A partition statistics summary `_delta_log/_partition_stats/<version>.partition_stats.json`
+//! aggregates the number of files and records and the minimum and maximum values of the columns
+//! of the files in each partition of the table at a version. Scans use it to prune whole
+//! partitions before evaluating predicates against the statistics of individual files.
+//!
+//! Writers of tables with `delta-rs.partitionStats.enabled` derive the summary of a version from
+//! the summary of the previous version and the actions of the commit. Summaries are not written
+//! for tables with column mapping, whose statistics are keyed by physical column names.
+//! Summaries expire together with the commits of their version in
+//! [`cleanup_expired_logs_for`](crate::protocol::checkpoints::cleanup_expired_logs_for).
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use chrono::DateTime;
+use delta_kernel::schema::{DataType, PrimitiveType, StructType};
+use delta_kernel::table_features::ColumnMappingMode;
+use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::{Error as ObjectStoreError, ObjectStore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::kernel::scalars::ScalarExt;
+use crate::kernel::{Action, EagerSnapshot};
+use crate::logstore::LogStore;
+use crate::protocol::{ColumnValueStat, Stats};
+use crate::table::config::TablePropertiesExt as _;
+use crate::{DeltaResult, DeltaTableError};
+
+/// Aggregated statistics of the files in a partition
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionStats {
+    /// Serialized partition values, keyed by partition column
+    pub partition_values: HashMap<String, Option<String>>,
+    /// Number of active files in the partition
+    pub num_files: i64,
+    /// Number of records in the partition, `None` if it is unknown for any of its files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_records: Option<i64>,
+    /// Lower bounds of the top-level columns with statistics in all files of the partition
+    pub min_values: HashMap<String, Value>,
+    /// Upper bounds of the top-level columns with statistics in all files of the partition
+    pub max_values: HashMap<String, Value>,
+}
+
+/// The content of a partition statistics summary file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionStatsSummary {
+    /// The partition columns of the table the summary was computed for
+    pub partition_columns: Vec<String>,
+    /// The statistics of every partition with active files
+    pub partitions: Vec<PartitionStats>,
+}
+
+impl PartitionStats {
+    fn new(partition_values: HashMap<String, Option<String>>) -> Self {
+        Self {
+            partition_values,
+            num_files: 0,
+            num_records: Some(0),
+            min_values: HashMap::new(),
+            max_values: HashMap::new(),
+        }
+    }
+
+    /// Include a file with the given statistics in the partition
+    fn add_file(&mut self, schema: &StructType, stats: Option<&Stats>) {
+        let min_values = stats
+            .map(|stats| top_level_values(&stats.min_values))
+            .unwrap_or_default();
+        let max_values = stats
+            .map(|stats| top_level_values(&stats.max_values))
+            .unwrap_or_default();
+        let num_records = stats.map(|stats| stats.num_records);
+
+        if self.num_files == 0 {
+            self.num_records = num_records;
+            self.min_values = min_values;
+            self.max_values = max_values;
+        } else {
+            self.num_records = self.num_records.zip(num_records).map(|(a, b)| a + b);
+            merge_bounds(schema, &mut self.min_values, min_values, Ordering::Less);
+            merge_bounds(schema, &mut self.max_values, max_values, Ordering::Greater);
+        }
+        self.num_files += 1;
+    }
+
+    /// Exclude a file from the partition.
+    ///
+    /// Removes do not carry statistics, the bounds of the remaining files stay valid but may
+    /// no longer be tight.
+    fn remove_file(&mut self) {
+        self.num_files -= 1;
+        self.num_records = None;
+    }
+}
+
+impl PartitionStatsSummary {
+    /// Compute the summary of the version following `self` from the actions of its commit.
+    ///
+    /// Returns `None` if the summary cannot be derived from the commit alone, i.e. when the
+    /// partitioning changes, removes do not record their partition values or files are
+    /// re-added without being removed.
+    pub(crate) fn try_apply_commit(&self, schema: &StructType, actions: &[Action]) -> Option<Self> {
+        let mut partition_columns = &self.partition_columns;
+        for action in actions {
+            if let Action::Metadata(metadata) = action {
+                partition_columns = metadata.partition_columns();
+            }
+        }
+        if partition_columns != &self.partition_columns {
+            return None;
+        }
+
+        let mut partitions: HashMap<_, _> = self
+            .partitions
+            .iter()
+            .map(|partition| (partition.key(partition_columns), partition.clone()))
+            .collect();
+        let has_removes = actions.iter().any(|a| matches!(a, Action::Remove(_)));
+        for action in actions {
+            match action {
+                Action::Add(add) => {
+                    // Adds without data change and without any removes, re-add existing files
+                    if !add.data_change && !has_removes {
+                        return None;
+                    }
+                    let (key, values) = normalized_partition_values(
+                        schema,
+                        partition_columns,
+                        &add.partition_values,
+                    )
+                    .ok()?;
+                    let stats = add.get_stats().ok()?;
+                    partitions
+                        .entry(key)
+                        .or_insert_with(|| PartitionStats::new(values))
+                        .add_file(schema, stats.as_ref());
+                }
+                Action::Remove(remove) => {
+                    let (key, _) = normalized_partition_values(
+                        schema,
+                        partition_columns,
+                        remove.partition_values.as_ref()?,
+                    )
+                    .ok()?;
+                    let partition = partitions.get_mut(&key)?;
+                    partition.remove_file();
+                    if partition.num_files < 0 {
+                        return None;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            partition_columns: partition_columns.clone(),
+            partitions: partitions
+                .into_values()
+                .filter(|partition| partition.num_files > 0)
+                .collect(),
+        })
+    }
+
+    /// Compute the summary from all active files of a snapshot of the table.
+    pub(crate) async fn try_from_snapshot(
+        snapshot: &EagerSnapshot,
+        log_store: &dyn LogStore,
+    ) -> DeltaResult<Self> {
+        let partition_columns = snapshot.metadata().partition_columns().clone();
+        let schema = snapshot.schema();
+        let partitions = snapshot
+            .file_views(log_store, None)
+            .try_fold(HashMap::new(), |mut partitions, file| {
+                let partition_columns = &partition_columns;
+                let schema = schema.as_ref();
+                async move {
+                    let add = file.add_action();
+                    let stats = add.get_stats()?;
+                    partitions
+                        .entry(partition_key(partition_columns, &add.partition_values))
+                        .or_insert_with(|| PartitionStats::new(add.partition_values.clone()))
+                        .add_file(schema, stats.as_ref());
+                    Ok(partitions)
+                }
+            })
+            .await?;
+        Ok(Self {
+            partition_columns,
+            partitions: partitions.into_values().collect(),
+        })
+    }
+}
+
+/// The serialized partition values in the order of the partition columns
+pub(crate) fn partition_key(
+    partition_columns: &[String],
+    partition_values: &HashMap<String, Option<String>>,
+) -> Vec<Option<String>> {
+    partition_columns
+        .iter()
+        .map(|column| partition_values.get(column).cloned().flatten())
+        .collect()
+}
+
+/// Serialize the raw partition values of a file action the way the snapshot serializes the
+/// partition values of its files.
+fn normalized_partition_values(
+    schema: &StructType,
+    partition_columns: &[String],
+    partition_values: &HashMap<String, Option<String>>,
+) -> DeltaResult<(Vec<Option<String>>, HashMap<String, Option<String>>)> {
+    let mut normalized = HashMap::with_capacity(partition_columns.len());
+    for column in partition_columns {
+        let value = match partition_values.get(column).cloned().flatten() {
+            Some(raw) => {
+                let data_type = schema
+                    .field(column)
+                    .and_then(|field| field.data_type().as_primitive_opt())
+                    .ok_or_else(|| {
+                        DeltaTableError::Generic(format!(
+                            "Partition column {column} is not a primitive column of the schema"
+                        ))
+                    })?;
+                let scalar = data_type.parse_scalar(&raw)?;
+                (!scalar.is_null()).then(|| scalar.serialize())
+            }
+            None => None,
+        };
+        normalized.insert(column.clone(), value);
+    }
+    Ok((partition_key(partition_columns, &normalized), normalized))
+}
+
+fn top_level_values(values: &HashMap<String, ColumnValueStat>) -> HashMap<String, Value> {
+    values
+        .iter()
+        .filter_map(|(column, stat)| match stat.as_value()? {
+            Value::Null => None,
+            value => Some((column.clone(), value.clone())),
+        })
+        .collect()
+}
+
+/// Merge the bounds of another file into `bounds`, keeping the value ordered `keep` relative to
+/// the other. Columns without a bound in either file or with incomparable values are dropped.
+fn merge_bounds(
+    schema: &StructType,
+    bounds: &mut HashMap<String, Value>,
+    other: HashMap<String, Value>,
+    keep: Ordering,
+) {
+    bounds.retain(|column, _| other.contains_key(column));
+    for (column, value) in other {
+        let Some(current) = bounds.get(&column) else {
+            continue;
+        };
+        let data_type = schema.field(&column).map(|field| field.data_type());
+        match compare_values(&value, current, data_type) {
+            Some(ordering) if ordering == keep => {
+                bounds.insert(column, value);
+            }
+            Some(_) => {}
+            None => {
+                bounds.remove(&column);
+            }
+        }
+    }
+}
+
+/// Compare two statistics values of a column of the given type
+fn compare_values(a: &Value, b: &Value, data_type: Option<&DataType>) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        },
+        // timestamp statistics may be written with different UTC offsets
+        (Value::String(a), Value::String(b))
+            if matches!(
+                data_type,
+                Some(DataType::Primitive(PrimitiveType::Timestamp))
+            ) =>
+        {
+            match (
+                DateTime::parse_from_rfc3339(a),
+                DateTime::parse_from_rfc3339(b),
+            ) {
+                (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+                _ => Some(a.cmp(b)),
+            }
+        }
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Whether the partition statistics summary is maintained and used for the table
+pub(crate) fn partition_stats_enabled(snapshot: &EagerSnapshot) -> bool {
+    snapshot.table_properties().partition_stats_enabled()
+        && snapshot.table_configuration().column_mapping_mode() == ColumnMappingMode::None
+}
+
+fn partition_stats_path(log_store: &dyn LogStore, version: i64) -> Path {
+    log_store
+        .log_path()
+        .child("_partition_stats")
+        .child(format!("{version:020}.partition_stats.json"))
+}
+
+/// Read the partition statistics summary of the given version, if it exists
+pub(crate) async fn read_partition_stats(
+    log_store: &dyn LogStore,
+    version: i64,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<Option<PartitionStatsSummary>> {
+    let path = partition_stats_path(log_store, version);
+    let data = match log_store.object_store(operation_id).get(&path).await {
+        Ok(data) => data.bytes().await?,
+        Err(ObjectStoreError::NotFound { .. }) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let summary = serde_json::from_slice(&data).map_err(|json_err| {
+        let line = format!(
+            "Error at line {}, column {}",
+            json_err.line(),
+            json_err.column()
+        );
+        DeltaTableError::InvalidJsonLog {
+            json_err,
+            line,
+            version,
+        }
+    })?;
+    Ok(Some(summary))
+}
+
+/// Write the partition statistics summary of the given version
+pub(crate) async fn write_partition_stats(
+    log_store: &dyn LogStore,
+    version: i64,
+    summary: &PartitionStatsSummary,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<()> {
+    let path = partition_stats_path(log_store, version);
+    debug!("writing partition statistics summary {path}");
+    log_store
+        .object_store(operation_id)
+        .put(&path, Bytes::from(serde_json::to_vec(summary)?).into())
+        .await?;
+    Ok(())
+}
+
+/// Write the partition statistics summary of a committed version.
+///
+/// The summary is derived from the summary of the previous version and the actions of the
+/// commit. If that is not possible it is recomputed from the files of the snapshot, as long as
+/// the snapshot was loaded with files. Returns whether a summary was written.
+pub(crate) async fn create_partition_stats_for(
+    snapshot: &EagerSnapshot,
+    actions: &[Action],
+    log_store: &dyn LogStore,
+    operation_id: Option<Uuid>,
+) -> DeltaResult<bool> {
+    if !partition_stats_enabled(snapshot) {
+        return Ok(false);
+    }
+    let version = snapshot.version();
+    let previous = if version > 0 {
+        match read_partition_stats(log_store, version - 1, operation_id).await {
+            Ok(previous) => previous,
+            Err(err @ DeltaTableError::InvalidJsonLog { .. }) => {
+                warn!(
+                    "ignoring invalid partition statistics for version {}: {err}",
+                    version - 1
+                );
+                None
+            }
+            Err(err) => return Err(err),
+        }
+    } else {
+        None
+    };
+
+    let summary = match previous
+        .and_then(|previous| previous.try_apply_commit(snapshot.schema().as_ref(), actions))
+    {
+        Some(summary) => summary,
+        None if snapshot.load_config().require_files => {
+            debug!("recomputing partition statistics of version {version} from the snapshot");
+            PartitionStatsSummary::try_from_snapshot(snapshot, log_store).await?
+        }
+        None => {
+            debug!("partition statistics of version {version} cannot be derived, skipping");
+            return Ok(false);
+        }
+    };
+    write_partition_stats(log_store, version, &summary, operation_id).await?;
+    Ok(true)
+}
+
+#[cfg(feature = "datafusion")]
+pub(crate) use self::datafusion::PartitionStatsPruning;
+
+#[cfg(feature = "datafusion")]
+mod datafusion {
+    use std::collections::HashSet;
+
+    use ::datafusion::common::Column;
+    use ::datafusion::common::pruning::PruningStatistics;
+    use ::datafusion::common::scalar::ScalarValue;
+    use arrow_array::{ArrayRef, BooleanArray};
+    use arrow_schema::{DataType as ArrowDataType, Schema};
+
+    use super::*;
+    use crate::delta_datafusion::{get_null_of_arrow_type, to_correct_scalar_value};
+
+    /// Prunes the partitions of a [`PartitionStatsSummary`], every partition is a container
+    pub(crate) struct PartitionStatsPruning<'a> {
+        summary: &'a PartitionStatsSummary,
+        schema: &'a Schema,
+    }
+
+    impl<'a> PartitionStatsPruning<'a> {
+        pub(crate) fn new(summary: &'a PartitionStatsSummary, schema: &'a Schema) -> Self {
+            Self { summary, schema }
+        }
+
+        fn pick_stats(
+            &self,
+            column: &Column,
+            bounds: impl Fn(&PartitionStats) -> &HashMap<String, Value>,
+        ) -> Option<ArrayRef> {
+            let field = self.schema.field_with_name(&column.name).ok()?;
+            let data_type = field.data_type();
+            // See issue #1214. Binary type does not support natural order which is required for Datafusion to prune
+            if matches!(
+                data_type,
+                ArrowDataType::Binary | ArrowDataType::LargeBinary | ArrowDataType::BinaryView
+            ) {
+                return None;
+            }
+            let is_partition_column = self.summary.partition_columns.contains(&column.name);
+            let values = self
+                .summary
+                .partitions
+                .iter()
+                .map(|partition| {
+                    let value = if is_partition_column {
+                        partition
+                            .partition_values
+                            .get(&column.name)
+                            .cloned()
+                            .flatten()
+                            .map(Value::String)
+                    } else {
+                        bounds(partition).get(&column.name).cloned()
+                    };
+                    match value {
+                        Some(value) => to_correct_scalar_value(&value, data_type).ok().flatten(),
+                        None => get_null_of_arrow_type(data_type).ok(),
+                    }
+                })
+                .collect::<Option<Vec<_>>>()?;
+            ScalarValue::iter_to_array(values).ok()
+        }
+    }
+
+    impl PruningStatistics for PartitionStatsPruning<'_> {
+        fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+            self.pick_stats(column, |partition| &partition.min_values)
+        }
+
+        fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+            self.pick_stats(column, |partition| &partition.max_values)
+        }
+
+        fn num_containers(&self) -> usize {
+            self.summary.partitions.len()
+        }
+
+        // Null counts are not aggregated, predicates on nulls never prune partitions
+        fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
+            None
+        }
+
+        fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+            None
+        }
+
+        fn contained(
+            &self,
+            _column: &Column,
+            _values: &HashSet<ScalarValue>,
+        ) -> Option<BooleanArray> {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use ::datafusion::common::ToDFSchema;
+    use ::datafusion::physical_optimizer::pruning::PruningPredicate;
+    use ::datafusion::prelude::{SessionContext, col, lit};
+
+    use super::*;
+    use crate::DeltaTable;
+    use crate::TableProperty;
+    use crate::protocol::SaveMode;
+    use crate::writer::test_utils::{
+        get_arrow_schema, get_delta_schema, get_record_batch_from_rows,
+    };
+
+    async fn create_table() -> DeltaResult<DeltaTable> {
+        DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_partition_columns(["modified"])
+            .with_configuration_property(TableProperty::PartitionStatsEnabled, Some("true"))
+            .await
+    }
+
+    fn sorted(mut summary: PartitionStatsSummary) -> PartitionStatsSummary {
+        summary.partitions.sort_by_key(|partition| {
+            partition_key(&summary.partition_columns, &partition.partition_values)
+        });
+        summary
+    }
+
+    #[tokio::test]
+    async fn test_incremental_partition_stats() -> DeltaResult<()> {
+        let mut table = create_table().await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-01")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 5, "2021-02-01")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 3, "2021-02-02")])])
+            .await?;
+
+        let log_store = table.log_store();
+        let summary = read_partition_stats(log_store.as_ref(), 3, None)
+            .await?
+            .expect("partition statistics");
+        let summary = sorted(summary);
+        assert_eq!(summary.partitions.len(), 2);
+        let partition = &summary.partitions[0];
+        assert_eq!(partition.num_files, 2);
+        assert_eq!(partition.num_records, Some(2));
+        assert_eq!(partition.min_values["value"], Value::from(1));
+        assert_eq!(partition.max_values["value"], Value::from(5));
+
+        let snapshot = table.snapshot()?.snapshot();
+        assert_eq!(
+            summary,
+            sorted(PartitionStatsSummary::try_from_snapshot(snapshot, log_store.as_ref()).await?)
+        );
+
+        // Overwriting a partition drops the replaced files
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 7, "2021-02-01")])])
+            .with_save_mode(SaveMode::Overwrite)
+            .with_replace_where(col("modified").eq(lit("2021-02-01")))
+            .await?;
+        let summary = read_partition_stats(table.log_store().as_ref(), 4, None)
+            .await?
+            .expect("partition statistics");
+        let summary = sorted(summary);
+        assert_eq!(summary.partitions.len(), 2);
+        assert_eq!(summary.partitions[0].num_files, 1);
+        assert_eq!(summary.partitions[0].max_values["value"], Value::from(7));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_string_bounds_are_not_ordered_as_timestamps() -> DeltaResult<()> {
+        let mut table = create_table().await?;
+        for id in ["2021-01-01T10:00:00+02:00", "2021-01-01T09:00:00Z"] {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(id, 1, "2021-02-01")])])
+                .await?;
+        }
+        let summary = read_partition_stats(table.log_store().as_ref(), 2, None)
+            .await?
+            .expect("partition statistics");
+        let partition = &summary.partitions[0];
+        assert_eq!(
+            partition.min_values["id"],
+            Value::from("2021-01-01T09:00:00Z")
+        );
+        assert_eq!(
+            partition.max_values["id"],
+            Value::from("2021-01-01T10:00:00+02:00")
+        );
+
+        let schema = get_arrow_schema(&None);
+        let df_schema = schema.clone().to_dfschema()?;
+        let predicate = SessionContext::new()
+            .state()
+            .create_physical_expr(col("id").eq(lit("2021-01-01T09:00:00Z")), &df_schema)?;
+        let pruning_predicate = PruningPredicate::try_new(predicate, schema.clone())?;
+        let keep = pruning_predicate.prune(&PartitionStatsPruning::new(&summary, &schema))?;
+        assert_eq!(keep, vec![true]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_partition_stats_when_disabled() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        let table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-01")])])
+            .await?;
+        assert!(
+            read_partition_stats(table.log_store().as_ref(), 1, None)
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_expired_partition_stats() -> DeltaResult<()> {
+        use chrono::Utc;
+
+        use crate::protocol::checkpoints::{cleanup_expired_logs_for, create_checkpoint};
+
+        let mut table = create_table().await?;
+        for value in 0..2 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        create_checkpoint(&table, None).await?;
+
+        cleanup_expired_logs_for(
+            table.version().unwrap(),
+            table.log_store().as_ref(),
+            Utc::now().timestamp_millis() + 60_000,
+            None,
+        )
+        .await?;
+        let log_store = table.log_store();
+        assert!(
+            read_partition_stats(log_store.as_ref(), 1, None)
+                .await?
+                .is_none()
+        );
+        assert!(
+            read_partition_stats(log_store.as_ref(), 2, None)
+                .await?
+                .is_some()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prune_partitions() -> DeltaResult<()> {
+        let mut table = create_table().await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 1, "2021-02-01")])])
+            .await?;
+        table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 10, "2021-02-02")])])
+            .await?;
+        let summary = read_partition_stats(table.log_store().as_ref(), 2, None)
+            .await?
+            .expect("partition statistics");
+        let summary = sorted(summary);
+
+        let schema = get_arrow_schema(&None);
+        let df_schema = schema.clone().to_dfschema()?;
+        let predicate = SessionContext::new()
+            .state()
+            .create_physical_expr(col("value").gt(lit(5)), &df_schema)?;
+        let pruning_predicate = PruningPredicate::try_new(predicate, schema.clone())?;
+        let keep = pruning_predicate.prune(&PartitionStatsPruning::new(&summary, &schema))?;
+        assert_eq!(keep, vec![false, true]);
+        Ok(())
+    }
+}
@@ -8,12 +8,14 @@ use chrono::{DateTime, FixedOffset, Utc};
 use deltalake_derive::DeltaConfig;
 use object_store::DynObjectStore;
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 use url::Url;
 
 use super::normalize_table_url;
+use super::snapshot_cache::SnapshotCache;
+use super::state::DeltaTableState;
 use crate::logstore::storage::IORuntime;
-use crate::logstore::{LogStoreRef, StorageConfig, object_store_factories};
+use crate::logstore::{LogStoreExt as _, LogStoreRef, StorageConfig, object_store_factories};
 use crate::{DeltaResult, DeltaTable, DeltaTableError};
 
 /// possible version specifications for loading a delta table
@@ -90,6 +92,7 @@ pub struct DeltaTableBuilder {
     storage_options: Option<HashMap<String, String>>,
     allow_http: Option<bool>,
     table_config: DeltaTableConfig,
+    snapshot_cache: Option<SnapshotCache>,
 }
 
 impl DeltaTableBuilder {
@@ -126,10 +129,15 @@ impl DeltaTableBuilder {
             storage_options: None,
             allow_http: None,
             table_config: DeltaTableConfig::default(),
+            snapshot_cache: None,
         })
     }
 
     /// Sets `require_files=false` to the builder
+    ///
+    /// Files are streamed from the log when needed instead of being held in memory.
+    /// Operations which only touch some files, like `delete` and `update` with a predicate
+    /// or `optimize` with filters, only load the files matching their predicate.
     pub fn without_files(mut self) -> Self {
         self.table_config.require_files = false;
         self
@@ -230,6 +238,15 @@ impl DeltaTableBuilder {
         self
     }
 
+    /// Restore the table state from the given [`SnapshotCache`] when loading the table.
+    ///
+    /// Only commits newer than the cached snapshot are replayed, the loaded state is written
+    /// back to the cache. The cache is not used when loading tables without files or by timestamp.
+    pub fn with_snapshot_cache(mut self, snapshot_cache: SnapshotCache) -> Self {
+        self.snapshot_cache = Some(snapshot_cache);
+        self
+    }
+
     /// Storage options for configuring backend object store
     pub fn storage_options(&self) -> HashMap<String, String> {
         let mut storage_options = self.storage_options.clone().unwrap_or_default();
@@ -275,16 +292,69 @@ impl DeltaTableBuilder {
     /// Build the [`DeltaTable`] and load its state
     pub async fn load(self) -> DeltaResult<DeltaTable> {
         let version = self.version;
+        let snapshot_cache = self
+            .snapshot_cache
+            .clone()
+            .filter(|_| self.table_config.require_files);
         let mut table = self.build()?;
-        match version {
-            DeltaVersion::Newest => table.load().await?,
-            DeltaVersion::Version(v) => table.load_version(v).await?,
-            DeltaVersion::Timestamp(ts) => table.load_with_datetime(ts).await?,
+        match (version, snapshot_cache) {
+            (DeltaVersion::Newest, Some(cache)) => table.load_cached(&cache, None).await?,
+            (DeltaVersion::Version(v), Some(cache)) => table.load_cached(&cache, Some(v)).await?,
+            (DeltaVersion::Newest, None) => table.load().await?,
+            (DeltaVersion::Version(v), None) => table.load_version(v).await?,
+            (DeltaVersion::Timestamp(ts), _) => table.load_with_datetime(ts).await?,
         }
         Ok(table)
     }
 }
 
+impl DeltaTable {
+    /// Load the table from the most recent cached snapshot, replaying only newer commits.
+    ///
+    /// Failures to read or write the cache are logged and fall back to loading from the log.
+    async fn load_cached(
+        &mut self,
+        cache: &SnapshotCache,
+        max_version: Option<i64>,
+    ) -> DeltaResult<()> {
+        let table_url = self.log_store.table_root_url();
+        let cached = cache
+            .get(&table_url, max_version, self.log_store.as_ref())
+            .await
+            .inspect_err(|err| warn!("failed to read snapshot cache for {table_url}: {err}"))
+            .ok()
+            .flatten();
+
+        let cached_version = cached.as_ref().map(|snapshot| snapshot.version());
+        let mut restored = None;
+        if let Some(snapshot) = cached {
+            let mut state = DeltaTableState::new(snapshot);
+            // e.g. the commits following the cached version were already cleaned up
+            match state.update(self.log_store.as_ref(), max_version).await {
+                Ok(()) => restored = Some(state),
+                Err(err) => warn!("failed to update cached snapshot of {table_url}: {err}"),
+            }
+        }
+        match restored {
+            Some(state) => self.state = Some(state),
+            None => match max_version {
+                Some(version) => self.load_version(version).await?,
+                None => self.load().await?,
+            },
+        }
+
+        if let Some(state) = &self.state
+            && Some(state.version()) != cached_version
+            && let Err(err) = cache
+                .put(&table_url, state.snapshot(), self.log_store.as_ref())
+                .await
+        {
+            warn!("failed to write snapshot cache for {table_url}: {err}");
+        }
+        Ok(())
+    }
+}
+
 enum UriType {
     LocalPath(PathBuf),
     Url(Url),
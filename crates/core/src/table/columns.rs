@@ -80,3 +80,36 @@ impl DataCheck for GeneratedColumn {
         self
     }
 }
+
+/// Field metadata key holding the SQL expression for a column's default value
+pub const COLUMN_DEFAULT_KEY: &str = "CURRENT_DEFAULT";
+
+/// A column with a default value
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ColumnDefault {
+    /// The name of the column.
+    pub name: String,
+    /// The SQL string that evaluates to the default value.
+    pub default_expr: String,
+    /// Data Type
+    pub data_type: DataType,
+}
+
+impl ColumnDefault {
+    /// Create a new column default
+    pub fn new(field_name: &str, default_expr: &str, data_type: &DataType) -> Self {
+        Self {
+            name: field_name.to_string(),
+            default_expr: default_expr.to_string(),
+            data_type: data_type.clone(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_default_expression(&self) -> &str {
+        &self.default_expr
+    }
+}
@@ -1,12 +1,19 @@
 //! Delta Table configuration
+use std::collections::BTreeMap;
 use std::num::NonZero;
 use std::str::FromStr;
 use std::sync::LazyLock;
 use std::time::Duration;
 
 use delta_kernel::table_properties::{DataSkippingNumIndexedCols, IsolationLevel, TableProperties};
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use parquet::errors::ParquetError;
+use parquet::file::properties::{WriterProperties, WriterPropertiesBuilder};
+use parquet::schema::types::ColumnPath;
+use tracing::warn;
 
 use super::Constraint;
+use crate::crate_version;
 use crate::errors::DeltaTableError;
 
 /// Typed property keys that can be defined on a delta table
@@ -26,6 +33,13 @@ pub enum TableProperty {
     /// true for Delta Lake to automatically optimize the layout of the files for this Delta table during writes.
     AutoOptimizeOptimizeWrite,
 
+    /// Minimum number of small files in a partition touched by a commit before auto compaction runs.
+    AutoOptimizeAutoCompactMinNumFiles,
+
+    /// Size in bytes below which files are considered small by auto compaction, and the target
+    /// size of the files it writes.
+    AutoOptimizeAutoCompactMaxFileSize,
+
     /// Interval (number of commits) after which a new checkpoint should be created
     CheckpointInterval,
 
@@ -40,6 +54,10 @@ pub enum TableProperty {
     /// Some readers don't support run length encoding (i.e. Fabric) so this can be disabled.
     CheckpointUseRunLengthEncoding,
 
+    /// Maximum number of actions in a single part of a classic checkpoint. Checkpoints with more
+    /// actions are written as multi-part checkpoints, whose parts are written in parallel.
+    CheckpointPartSize,
+
     /// Whether column mapping is enabled for Delta table columns and the corresponding
     /// Parquet columns that use different names.
     ColumnMappingMode,
@@ -119,6 +137,29 @@ pub enum TableProperty {
 
     /// 'classic' for classic Delta Lake checkpoints. 'v2' for v2 checkpoints.
     CheckpointPolicy,
+
+    /// Compression codec of written parquet files, e.g. `snappy` or `zstd`.
+    ParquetCompression,
+
+    /// Level of the codec set by `delta-rs.parquet.compression`, for `gzip`, `brotli` and `zstd`.
+    ParquetCompressionLevel,
+
+    /// Maximum number of rows in a row group of written parquet files.
+    ParquetMaxRowGroupSize,
+
+    /// Best effort maximum size of a data page in bytes of written parquet files.
+    ParquetDataPageSize,
+
+    /// true to dictionary encode columns of written parquet files, can be overridden per column
+    /// with `delta-rs.parquet.dictionary.<column>.enabled`.
+    ParquetDictionaryEnabled,
+
+    /// Length in bytes to truncate min and max values in parquet statistics to.
+    ParquetStatisticsTruncateLength,
+
+    /// true for writers to maintain a summary of the statistics of each partition, which scans
+    /// use to prune partitions before looking at individual files.
+    PartitionStatsEnabled,
 }
 
 impl AsRef<str> for TableProperty {
@@ -128,9 +169,16 @@ impl AsRef<str> for TableProperty {
             Self::CheckpointInterval => "delta.checkpointInterval",
             Self::AutoOptimizeAutoCompact => "delta.autoOptimize.autoCompact",
             Self::AutoOptimizeOptimizeWrite => "delta.autoOptimize.optimizeWrite",
+            Self::AutoOptimizeAutoCompactMinNumFiles => {
+                "delta.autoOptimize.autoCompact.minNumFiles"
+            }
+            Self::AutoOptimizeAutoCompactMaxFileSize => {
+                "delta.autoOptimize.autoCompact.maxFileSize"
+            }
             Self::CheckpointWriteStatsAsJson => "delta.checkpoint.writeStatsAsJson",
             Self::CheckpointWriteStatsAsStruct => "delta.checkpoint.writeStatsAsStruct",
             Self::CheckpointUseRunLengthEncoding => "delta-rs.checkpoint.useRunLengthEncoding",
+            Self::CheckpointPartSize => "delta-rs.checkpoint.partSize",
             Self::CheckpointPolicy => "delta.checkpointPolicy",
             Self::ColumnMappingMode => "delta.columnMapping.mode",
             Self::DataSkippingNumIndexedCols => "delta.dataSkippingNumIndexedCols",
@@ -148,6 +196,13 @@ impl AsRef<str> for TableProperty {
             Self::SetTransactionRetentionDuration => "delta.setTransactionRetentionDuration",
             Self::TargetFileSize => "delta.targetFileSize",
             Self::TuneFileSizesForRewrites => "delta.tuneFileSizesForRewrites",
+            Self::ParquetCompression => "delta-rs.parquet.compression",
+            Self::ParquetCompressionLevel => "delta-rs.parquet.compressionLevel",
+            Self::ParquetMaxRowGroupSize => "delta-rs.parquet.maxRowGroupSize",
+            Self::ParquetDataPageSize => "delta-rs.parquet.dataPageSize",
+            Self::ParquetDictionaryEnabled => "delta-rs.parquet.dictionaryEnabled",
+            Self::ParquetStatisticsTruncateLength => "delta-rs.parquet.statisticsTruncateLength",
+            Self::PartitionStatsEnabled => "delta-rs.partitionStats.enabled",
         }
     }
 }
@@ -161,9 +216,16 @@ impl FromStr for TableProperty {
             "delta.checkpointInterval" => Ok(Self::CheckpointInterval),
             "delta.autoOptimize.autoCompact" => Ok(Self::AutoOptimizeAutoCompact),
             "delta.autoOptimize.optimizeWrite" => Ok(Self::AutoOptimizeOptimizeWrite),
+            "delta.autoOptimize.autoCompact.minNumFiles" => {
+                Ok(Self::AutoOptimizeAutoCompactMinNumFiles)
+            }
+            "delta.autoOptimize.autoCompact.maxFileSize" => {
+                Ok(Self::AutoOptimizeAutoCompactMaxFileSize)
+            }
             "delta.checkpoint.writeStatsAsJson" => Ok(Self::CheckpointWriteStatsAsJson),
             "delta.checkpoint.writeStatsAsStruct" => Ok(Self::CheckpointWriteStatsAsStruct),
             "delta-rs.checkpoint.useRunLengthEncoding" => Ok(Self::CheckpointUseRunLengthEncoding),
+            "delta-rs.checkpoint.partSize" => Ok(Self::CheckpointPartSize),
             "delta.checkpointPolicy" => Ok(Self::CheckpointPolicy),
             "delta.columnMapping.mode" => Ok(Self::ColumnMappingMode),
             "delta.dataSkippingNumIndexedCols" => Ok(Self::DataSkippingNumIndexedCols),
@@ -185,6 +247,15 @@ impl FromStr for TableProperty {
             "delta.setTransactionRetentionDuration" => Ok(Self::SetTransactionRetentionDuration),
             "delta.targetFileSize" => Ok(Self::TargetFileSize),
             "delta.tuneFileSizesForRewrites" => Ok(Self::TuneFileSizesForRewrites),
+            "delta-rs.parquet.compression" => Ok(Self::ParquetCompression),
+            "delta-rs.parquet.compressionLevel" => Ok(Self::ParquetCompressionLevel),
+            "delta-rs.parquet.maxRowGroupSize" => Ok(Self::ParquetMaxRowGroupSize),
+            "delta-rs.parquet.dataPageSize" => Ok(Self::ParquetDataPageSize),
+            "delta-rs.parquet.dictionaryEnabled" => Ok(Self::ParquetDictionaryEnabled),
+            "delta-rs.parquet.statisticsTruncateLength" => {
+                Ok(Self::ParquetStatisticsTruncateLength)
+            }
+            "delta-rs.partitionStats.enabled" => Ok(Self::PartitionStatsEnabled),
             _ => Err(DeltaTableError::Generic("unknown config key".into())),
         }
     }
@@ -202,6 +273,255 @@ pub enum DeltaConfigError {
 pub const DEFAULT_NUM_INDEX_COLS: u64 = 32;
 /// Default target file size
 pub const DEFAULT_TARGET_FILE_SIZE: u64 = 100 * 1024 * 1024;
+/// Default minimum number of small files in a partition before auto compaction runs
+pub const DEFAULT_AUTO_COMPACT_MIN_NUM_FILES: u64 = 50;
+/// Default size below which files are compacted by auto compaction
+pub const DEFAULT_AUTO_COMPACT_MAX_FILE_SIZE: u64 = 128 * 1024 * 1024;
+/// Prefix of the table properties configuring parquet bloom filters per column
+pub const BLOOM_FILTER_PROPERTY_PREFIX: &str = "delta.bloomFilter.";
+/// Prefix of the table properties configuring parquet dictionary encoding per column
+pub const PARQUET_DICTIONARY_PROPERTY_PREFIX: &str = "delta-rs.parquet.dictionary.";
+
+/// Whether `key` is a table property configuring a single column, which is not a
+/// [`TableProperty`] since its key contains the column name.
+pub fn is_column_property(key: &str) -> bool {
+    key.starts_with(BLOOM_FILTER_PROPERTY_PREFIX)
+        || key.starts_with(PARQUET_DICTIONARY_PROPERTY_PREFIX)
+}
+
+/// Path of a dot separated column in the parquet schema
+fn column_path(column: &str) -> ColumnPath {
+    ColumnPath::new(column.split('.').map(|s| s.to_string()).collect())
+}
+
+/// Parquet bloom filter settings of a column.
+///
+/// Configured with the `delta.bloomFilter.<column>.enabled`, `delta.bloomFilter.<column>.fpp`
+/// and `delta.bloomFilter.<column>.ndv` table properties, nested columns are addressed by their
+/// dot separated path. Unset values fall back to the defaults of the parquet writer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilterColumn {
+    /// Dot separated path of the column
+    pub column: String,
+    /// False positive probability of the filter
+    pub fpp: Option<f64>,
+    /// Expected number of distinct values in a row group
+    pub ndv: Option<u64>,
+}
+
+impl BloomFilterColumn {
+    /// Path of the column in the parquet schema
+    pub fn column_path(&self) -> ColumnPath {
+        column_path(&self.column)
+    }
+}
+
+/// Parse the bloom filter settings of the columns enabled in `properties`.
+///
+/// Invalid values are logged and ignored, so a bad setting never fails a write.
+pub fn parse_bloom_filter_columns<'a>(
+    properties: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<BloomFilterColumn> {
+    let mut columns: BTreeMap<&str, (bool, BloomFilterColumn)> = BTreeMap::new();
+    for (key, value) in properties {
+        let Some((column, setting)) = key
+            .strip_prefix(BLOOM_FILTER_PROPERTY_PREFIX)
+            .and_then(|key| key.rsplit_once('.'))
+        else {
+            continue;
+        };
+        let (enabled, entry) = columns.entry(column).or_insert_with(|| {
+            (
+                false,
+                BloomFilterColumn {
+                    column: column.to_string(),
+                    fpp: None,
+                    ndv: None,
+                },
+            )
+        });
+        match setting {
+            "enabled" => match value.to_ascii_lowercase().parse::<bool>() {
+                Ok(value) => *enabled = value,
+                Err(_) => warn!("Ignoring invalid value '{value}' for {key}"),
+            },
+            "fpp" => match value.parse::<f64>() {
+                Ok(fpp) if fpp > 0.0 && fpp < 1.0 => entry.fpp = Some(fpp),
+                _ => warn!("Ignoring invalid value '{value}' for {key}, must be in (0, 1)"),
+            },
+            "ndv" | "numItems" => match value.parse::<u64>() {
+                Ok(ndv) if ndv > 0 => entry.ndv = Some(ndv),
+                _ => warn!("Ignoring invalid value '{value}' for {key}, must be positive"),
+            },
+            _ => warn!("Ignoring unknown bloom filter property {key}"),
+        }
+    }
+    columns
+        .into_values()
+        .filter_map(|(enabled, column)| enabled.then_some(column))
+        .collect()
+}
+
+/// Default settings of the parquet writer, persisted in the table configuration.
+///
+/// Every write path uses these settings unless it is given explicit [`WriterProperties`], so
+/// files written by different writers share compression and layout. Unset values fall back to
+/// the defaults of the writer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParquetWriterSettings {
+    /// Compression codec, including its level
+    pub compression: Option<Compression>,
+    /// Maximum number of rows in a row group
+    pub max_row_group_size: Option<usize>,
+    /// Best effort maximum size of a data page in bytes
+    pub data_page_size: Option<usize>,
+    /// Whether columns are dictionary encoded
+    pub dictionary_enabled: Option<bool>,
+    /// Dictionary encoding of single columns, by dot separated column path
+    pub column_dictionary_enabled: BTreeMap<String, bool>,
+    /// Length in bytes to truncate min and max values in statistics to
+    pub statistics_truncate_length: Option<usize>,
+}
+
+impl ParquetWriterSettings {
+    /// Whether no setting is configured
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Apply the configured settings on top of `builder`.
+    pub fn apply(&self, mut builder: WriterPropertiesBuilder) -> WriterPropertiesBuilder {
+        if let Some(compression) = self.compression {
+            builder = builder.set_compression(compression);
+        }
+        if let Some(max_row_group_size) = self.max_row_group_size {
+            builder = builder.set_max_row_group_size(max_row_group_size);
+        }
+        if let Some(data_page_size) = self.data_page_size {
+            builder = builder.set_data_page_size_limit(data_page_size);
+        }
+        if let Some(dictionary_enabled) = self.dictionary_enabled {
+            builder = builder.set_dictionary_enabled(dictionary_enabled);
+        }
+        for (column, enabled) in &self.column_dictionary_enabled {
+            builder = builder.set_column_dictionary_enabled(column_path(column), *enabled);
+        }
+        if let Some(length) = self.statistics_truncate_length {
+            builder = builder.set_statistics_truncate_length(Some(length));
+        }
+        builder
+    }
+
+    /// Writer properties with the configured settings, `None` if nothing is configured and
+    /// writers should use their own defaults.
+    pub fn writer_properties(&self) -> Option<WriterProperties> {
+        if self.is_empty() {
+            return None;
+        }
+        let builder = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_created_by(format!("delta-rs version {}", crate_version()));
+        Some(self.apply(builder).build())
+    }
+}
+
+/// Parse the parquet writer settings in `properties`.
+///
+/// Invalid values are logged and ignored, so a bad setting never fails a write.
+pub fn parse_parquet_writer_settings<'a>(
+    properties: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> ParquetWriterSettings {
+    fn parse<T: FromStr>(key: &str, value: &str) -> Option<T> {
+        let parsed = value.parse().ok();
+        if parsed.is_none() {
+            warn!("Ignoring invalid value '{value}' for {key}");
+        }
+        parsed
+    }
+
+    let mut settings = ParquetWriterSettings::default();
+    let mut codec = None;
+    let mut level = None;
+    for (key, value) in properties {
+        if let Some(column) = key
+            .strip_prefix(PARQUET_DICTIONARY_PROPERTY_PREFIX)
+            .and_then(|column| column.strip_suffix(".enabled"))
+        {
+            if let Some(enabled) = parse(key, &value.to_ascii_lowercase()) {
+                settings
+                    .column_dictionary_enabled
+                    .insert(column.to_string(), enabled);
+            }
+            continue;
+        }
+        let Ok(property) = key.parse::<TableProperty>() else {
+            continue;
+        };
+        match property {
+            TableProperty::ParquetCompression => codec = Some(value),
+            TableProperty::ParquetCompressionLevel => level = parse::<i32>(key, value),
+            TableProperty::ParquetMaxRowGroupSize => {
+                settings.max_row_group_size = parse::<usize>(key, value).filter(|v| *v > 0)
+            }
+            TableProperty::ParquetDataPageSize => settings.data_page_size = parse(key, value),
+            TableProperty::ParquetDictionaryEnabled => {
+                settings.dictionary_enabled = parse(key, &value.to_ascii_lowercase())
+            }
+            TableProperty::ParquetStatisticsTruncateLength => {
+                settings.statistics_truncate_length = parse::<usize>(key, value).filter(|v| *v > 0)
+            }
+            _ => {}
+        }
+    }
+    match codec.map(|codec| parse_compression(codec, level)) {
+        Some(Ok(compression)) => settings.compression = Some(compression),
+        Some(Err(err)) => warn!("Ignoring parquet compression setting: {err}"),
+        None if level.is_some() => {
+            warn!("Ignoring parquet compression level without delta-rs.parquet.compression")
+        }
+        None => {}
+    }
+    settings
+}
+
+fn parse_compression(codec: &str, level: Option<i32>) -> Result<Compression, DeltaConfigError> {
+    let invalid_level = |err: ParquetError| {
+        DeltaConfigError::Validation(format!("invalid level for {codec}: {err}"))
+    };
+    let compression = match (codec.to_ascii_lowercase().as_str(), level) {
+        ("gzip", Some(level)) => {
+            Compression::GZIP(GzipLevel::try_new(level as u32).map_err(invalid_level)?)
+        }
+        ("gzip", None) => Compression::GZIP(GzipLevel::default()),
+        ("brotli", Some(level)) => {
+            Compression::BROTLI(BrotliLevel::try_new(level as u32).map_err(invalid_level)?)
+        }
+        ("brotli", None) => Compression::BROTLI(BrotliLevel::default()),
+        ("zstd", Some(level)) => {
+            Compression::ZSTD(ZstdLevel::try_new(level).map_err(invalid_level)?)
+        }
+        ("zstd", None) => Compression::ZSTD(ZstdLevel::default()),
+        (codec, level) => {
+            let compression = match codec {
+                "uncompressed" | "none" => Compression::UNCOMPRESSED,
+                "snappy" => Compression::SNAPPY,
+                "lzo" => Compression::LZO,
+                "lz4" => Compression::LZ4,
+                "lz4_raw" => Compression::LZ4_RAW,
+                _ => {
+                    return Err(DeltaConfigError::Validation(format!(
+                        "unknown compression codec '{codec}'"
+                    )));
+                }
+            };
+            if level.is_some() {
+                warn!("Ignoring compression level for {codec}, which has no levels");
+            }
+            compression
+        }
+    };
+    Ok(compression)
+}
 
 pub trait TablePropertiesExt {
     /// true for this Delta table to be append-only. If append-only, existing records cannot be
@@ -226,6 +546,10 @@ pub trait TablePropertiesExt {
     /// E.g. if checkpoint interval = 10, then a checkpoint should be written every 10 commits.
     fn checkpoint_interval(&self) -> NonZero<u64>;
 
+    /// Maximum number of actions in a part of a multi-part checkpoint, `None` if checkpoints
+    /// are written as a single file.
+    fn checkpoint_part_size(&self) -> Option<NonZero<u64>>;
+
     /// Number of columns to be indexed.
     fn num_indexed_cols(&self) -> DataSkippingNumIndexedCols;
 
@@ -238,6 +562,21 @@ pub trait TablePropertiesExt {
     fn isolation_level(&self) -> IsolationLevel;
 
     fn get_constraints(&self) -> Vec<Constraint>;
+
+    /// Columns to write parquet bloom filters for, see [`BloomFilterColumn`].
+    fn bloom_filter_columns(&self) -> Vec<BloomFilterColumn>;
+
+    /// Default settings of the parquet writer, see [`ParquetWriterSettings`].
+    fn parquet_writer_settings(&self) -> ParquetWriterSettings;
+
+    /// true if writes should shuffle their input by partition values before writing files.
+    fn optimize_write(&self) -> bool;
+
+    /// Settings of the auto compaction post-commit hook, `None` if it is disabled.
+    fn auto_compact(&self) -> Option<AutoCompactSettings>;
+
+    /// true if writers should maintain the partition statistics summary of the table.
+    fn partition_stats_enabled(&self) -> bool;
 }
 
 impl TablePropertiesExt for TableProperties {
@@ -263,6 +602,16 @@ impl TablePropertiesExt for TableProperties {
             .unwrap_or(DEFAULT_INTERVAL.to_owned())
     }
 
+    fn checkpoint_part_size(&self) -> Option<NonZero<u64>> {
+        let key = TableProperty::CheckpointPartSize.as_ref();
+        let value = self.unknown_properties.get(key)?;
+        let part_size = value.trim().parse::<NonZero<u64>>().ok();
+        if part_size.is_none() {
+            warn!("ignoring invalid value '{value}' for '{key}'");
+        }
+        part_size
+    }
+
     fn num_indexed_cols(&self) -> DataSkippingNumIndexedCols {
         self.data_skipping_num_indexed_cols
             .unwrap_or(DataSkippingNumIndexedCols::NumColumns(32))
@@ -304,6 +653,84 @@ impl TablePropertiesExt for TableProperties {
             })
             .collect()
     }
+
+    fn bloom_filter_columns(&self) -> Vec<BloomFilterColumn> {
+        parse_bloom_filter_columns(
+            self.unknown_properties
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        )
+    }
+
+    fn parquet_writer_settings(&self) -> ParquetWriterSettings {
+        parse_parquet_writer_settings(
+            self.unknown_properties
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        )
+    }
+
+    fn optimize_write(&self) -> bool {
+        self.unknown_properties
+            .get(TableProperty::AutoOptimizeOptimizeWrite.as_ref())
+            .is_some_and(|value| parse_bool(value))
+    }
+
+    fn auto_compact(&self) -> Option<AutoCompactSettings> {
+        let property = |key: &TableProperty| self.unknown_properties.get(key.as_ref());
+        let enabled = property(&TableProperty::AutoOptimizeAutoCompact).is_some_and(|value| {
+            matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "true" | "auto" | "legacy"
+            )
+        });
+        if !enabled {
+            return None;
+        }
+
+        let parse = |key: TableProperty, default: u64| {
+            property(&key)
+                .and_then(|value| match value.trim().parse::<u64>() {
+                    Ok(value) if value > 0 => Some(value),
+                    _ => {
+                        warn!("ignoring invalid value '{value}' for '{}'", key.as_ref());
+                        None
+                    }
+                })
+                .unwrap_or(default)
+        };
+        Some(AutoCompactSettings {
+            min_num_files: parse(
+                TableProperty::AutoOptimizeAutoCompactMinNumFiles,
+                DEFAULT_AUTO_COMPACT_MIN_NUM_FILES,
+            ),
+            max_file_size: parse(
+                TableProperty::AutoOptimizeAutoCompactMaxFileSize,
+                DEFAULT_AUTO_COMPACT_MAX_FILE_SIZE,
+            ),
+        })
+    }
+
+    fn partition_stats_enabled(&self) -> bool {
+        self.unknown_properties
+            .get(TableProperty::PartitionStatsEnabled.as_ref())
+            .is_some_and(|value| parse_bool(value))
+    }
+}
+
+/// Settings of the auto compaction post-commit hook, configured by
+/// `delta.autoOptimize.autoCompact` and its `minNumFiles` and `maxFileSize` sub-properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoCompactSettings {
+    /// Minimum number of small files in a partition before it is compacted
+    pub min_num_files: u64,
+    /// Files below this size are small, compacted files are written towards this size
+    pub max_file_size: u64,
+}
+
+/// Parse a boolean table property, anything other than `true` is treated as false
+pub(crate) fn parse_bool(value: &str) -> bool {
+    value.trim().eq_ignore_ascii_case("true")
 }
 
 const SECONDS_PER_MINUTE: u64 = 60;
@@ -439,6 +866,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_bloom_filter_columns_test() {
+        let properties = [
+            ("delta.bloomFilter.id.enabled", "true"),
+            ("delta.bloomFilter.id.fpp", "0.01"),
+            ("delta.bloomFilter.id.ndv", "1000000"),
+            ("delta.bloomFilter.nested.key.enabled", "TRUE"),
+            ("delta.bloomFilter.nested.key.fpp", "1.5"),
+            ("delta.bloomFilter.value.enabled", "false"),
+            ("delta.bloomFilter.value.ndv", "10"),
+            ("delta.appendOnly", "true"),
+        ];
+        let columns = parse_bloom_filter_columns(properties);
+        assert_eq!(
+            columns,
+            vec![
+                BloomFilterColumn {
+                    column: "id".to_string(),
+                    fpp: Some(0.01),
+                    ndv: Some(1_000_000),
+                },
+                BloomFilterColumn {
+                    column: "nested.key".to_string(),
+                    fpp: None,
+                    ndv: None,
+                },
+            ]
+        );
+        assert_eq!(
+            columns[1].column_path(),
+            ColumnPath::new(vec!["nested".to_string(), "key".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_parquet_writer_settings_test() {
+        let settings = parse_parquet_writer_settings([
+            ("delta-rs.parquet.compression", "ZSTD"),
+            ("delta-rs.parquet.compressionLevel", "7"),
+            ("delta-rs.parquet.maxRowGroupSize", "10000"),
+            ("delta-rs.parquet.dataPageSize", "65536"),
+            ("delta-rs.parquet.dictionaryEnabled", "false"),
+            ("delta-rs.parquet.dictionary.nested.key.enabled", "true"),
+            ("delta-rs.parquet.statisticsTruncateLength", "64"),
+            ("delta.appendOnly", "true"),
+        ]);
+        assert_eq!(
+            settings,
+            ParquetWriterSettings {
+                compression: Some(Compression::ZSTD(ZstdLevel::try_new(7).unwrap())),
+                max_row_group_size: Some(10_000),
+                data_page_size: Some(65_536),
+                dictionary_enabled: Some(false),
+                column_dictionary_enabled: BTreeMap::from([("nested.key".to_string(), true)]),
+                statistics_truncate_length: Some(64),
+            }
+        );
+
+        let properties = settings.writer_properties().unwrap();
+        assert_eq!(properties.max_row_group_size(), 10_000);
+        assert_eq!(properties.data_page_size_limit(), 65_536);
+        assert!(!properties.dictionary_enabled(&ColumnPath::from("id")));
+        assert!(properties.dictionary_enabled(&ColumnPath::new(vec![
+            "nested".to_string(),
+            "key".to_string()
+        ])));
+
+        // invalid settings are ignored
+        let settings = parse_parquet_writer_settings([
+            ("delta-rs.parquet.compression", "gzip"),
+            ("delta-rs.parquet.compressionLevel", "100"),
+            ("delta-rs.parquet.maxRowGroupSize", "many"),
+        ]);
+        assert!(settings.is_empty());
+        assert!(settings.writer_properties().is_none());
+    }
+
     #[test]
     fn parse_interval_invalid_test() {
         assert_eq!(
@@ -0,0 +1,500 @@
+//! Changes to a table between two versions
+//!
+//! [`DeltaTable::diff`] reports the files added and removed between two versions of a table,
+//! together with changes to the table metadata and protocol and the resulting change in the
+//! number of records.
+//!
+//! Short ranges are answered by replaying the commits in the range. Longer ranges, or ranges
+//! whose commits were already cleaned up, compare the active files of both versions instead,
+//! which reads the latest checkpoint before each version rather than every commit.
+use std::collections::{HashMap, HashSet};
+
+use futures::TryStreamExt;
+use serde::Serialize;
+
+use super::DeltaTable;
+use crate::kernel::{Action, Add, DeletionVectorDescriptor, Metadata, Protocol, Remove, Snapshot};
+use crate::logstore::{LogStore, get_actions};
+use crate::{DeltaResult, DeltaTableError};
+
+/// Ranges spanning more commits than this compare the snapshots at both ends of the range.
+const MAX_REPLAYED_COMMITS: i64 = 100;
+
+/// The changes to a table between two versions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaTableDiff {
+    /// The version the changes are relative to
+    pub from_version: i64,
+    /// The version the changes lead to
+    pub to_version: i64,
+    /// Files active at `to_version` that were not active at `from_version`
+    pub added_files: Vec<Add>,
+    /// Files active at `from_version` that are no longer active at `to_version`
+    pub removed_files: Vec<Remove>,
+    /// The change of the table metadata, if it differs between both versions
+    pub metadata: Option<MetadataChange>,
+    /// The change of the table protocol, if it differs between both versions
+    pub protocol: Option<ProtocolChange>,
+    /// Number of records in the added files, `None` if some files have no statistics
+    pub num_records_added: Option<i64>,
+    /// Number of records in the removed files, `None` if some files have no statistics
+    pub num_records_removed: Option<i64>,
+}
+
+impl DeltaTableDiff {
+    /// The change in the number of records of the table, `None` if some of the added or
+    /// removed files have no statistics.
+    pub fn num_records_delta(&self) -> Option<i64> {
+        Some(self.num_records_added? - self.num_records_removed?)
+    }
+}
+
+/// A change of the table metadata
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataChange {
+    /// The metadata at the start of the range
+    pub from: Metadata,
+    /// The metadata at the end of the range
+    pub to: Metadata,
+    /// Whether the table schema changed
+    pub schema_changed: bool,
+}
+
+/// A change of the table protocol
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolChange {
+    /// The protocol at the start of the range
+    pub from: Protocol,
+    /// The protocol at the end of the range
+    pub to: Protocol,
+}
+
+/// Files are identified by their path and deletion vector, updating the deletion vector of a
+/// file removes and re-adds it.
+type FileKey = (String, Option<(String, Option<i32>)>);
+
+fn file_key(path: &str, deletion_vector: Option<&DeletionVectorDescriptor>) -> FileKey {
+    (
+        path.to_string(),
+        deletion_vector.map(|dv| (dv.path_or_inline_dv.clone(), dv.offset)),
+    )
+}
+
+fn num_records(
+    stats: Option<i64>,
+    deletion_vector: Option<&DeletionVectorDescriptor>,
+) -> Option<i64> {
+    Some(stats? - deletion_vector.map(|dv| dv.cardinality).unwrap_or_default())
+}
+
+struct FileChanges {
+    added: Vec<Add>,
+    removed: Vec<Remove>,
+    /// Number of records in the removed files, if it is already known
+    num_records_removed: Option<Option<i64>>,
+}
+
+impl DeltaTable {
+    /// Get the files, metadata and protocol that changed between two versions of the table.
+    ///
+    /// See [`DeltaTableDiff`] for details on the reported changes.
+    pub async fn diff(&self, from_version: i64, to_version: i64) -> DeltaResult<DeltaTableDiff> {
+        if from_version > to_version {
+            return Err(DeltaTableError::generic(format!(
+                "Cannot diff from version {from_version} to the earlier version {to_version}"
+            )));
+        }
+
+        let log_store = self.log_store();
+        let mut config = self.config.clone();
+        config.require_files = false;
+        let from =
+            Snapshot::try_new(log_store.as_ref(), config.clone(), Some(from_version)).await?;
+        let to = Snapshot::try_new(log_store.as_ref(), config, Some(to_version)).await?;
+
+        let replayed = if to_version - from_version <= MAX_REPLAYED_COMMITS {
+            replay_commits(log_store.as_ref(), &from, to_version).await?
+        } else {
+            None
+        };
+        let changes = match replayed {
+            Some(changes) => changes,
+            None => compare_snapshots(log_store.as_ref(), &from, &to).await?,
+        };
+        let num_records_removed = match changes.num_records_removed {
+            Some(num_records) => num_records,
+            None => removed_records(log_store.as_ref(), &from, &changes.removed).await?,
+        };
+        let num_records_added = changes
+            .added
+            .iter()
+            .map(|add| {
+                let stats = add.get_stats().ok().flatten().map(|s| s.num_records);
+                num_records(stats, add.deletion_vector.as_ref())
+            })
+            .sum();
+
+        let metadata = (from.metadata() != to.metadata()).then(|| MetadataChange {
+            from: from.metadata().clone(),
+            to: to.metadata().clone(),
+            schema_changed: from.schema() != to.schema(),
+        });
+        let protocol = (from.protocol() != to.protocol()).then(|| ProtocolChange {
+            from: from.protocol().clone(),
+            to: to.protocol().clone(),
+        });
+
+        Ok(DeltaTableDiff {
+            from_version,
+            to_version,
+            added_files: changes.added,
+            removed_files: changes.removed,
+            metadata,
+            protocol,
+            num_records_added,
+            num_records_removed,
+        })
+    }
+}
+
+/// Replay the commits after the version of `from` up to `to_version`.
+///
+/// Files re-added without changing data, e.g. to update their statistics, are only reported if
+/// they were not active at `from`. Returns `None` if some of the commits no longer exist.
+async fn replay_commits(
+    log_store: &dyn LogStore,
+    from: &Snapshot,
+    to_version: i64,
+) -> DeltaResult<Option<FileChanges>> {
+    let mut added: HashMap<FileKey, Add> = HashMap::new();
+    let mut removed: HashMap<FileKey, Remove> = HashMap::new();
+    // files re-added without data change, which may have been active at `from`
+    let mut readded: HashMap<FileKey, Add> = HashMap::new();
+    // removed files that were re-added without data change before, and thus may not have
+    // been active at `from`
+    let mut unverified_removes: HashSet<FileKey> = HashSet::new();
+    for version in from.version() + 1..=to_version {
+        let Some(bytes) = log_store.read_commit_entry(version).await? else {
+            return Ok(None);
+        };
+        for action in get_actions(version, &bytes)? {
+            match action {
+                Action::Add(add) => {
+                    let key = file_key(&add.path, add.deletion_vector.as_ref());
+                    // a file removed and re-added within the range did not change, unless
+                    // it was not active at `from` to begin with
+                    if removed.remove(&key).is_some() {
+                        if unverified_removes.remove(&key) {
+                            readded.insert(key, add);
+                        }
+                    } else if add.data_change || added.contains_key(&key) {
+                        readded.remove(&key);
+                        added.insert(key, add);
+                    } else {
+                        readded.insert(key, add);
+                    }
+                }
+                Action::Remove(remove) => {
+                    let key = file_key(&remove.path, remove.deletion_vector.as_ref());
+                    // a file added and removed within the range never was visible
+                    if added.remove(&key).is_some() {
+                        continue;
+                    }
+                    if readded.remove(&key).is_some() {
+                        unverified_removes.insert(key.clone());
+                    }
+                    removed.insert(key, remove);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    if !readded.is_empty() || !unverified_removes.is_empty() {
+        let candidates = readded.keys().chain(&unverified_removes).cloned().collect();
+        let active = active_files(log_store, from, candidates).await?;
+        added.extend(readded.into_iter().filter(|(key, _)| !active.contains(key)));
+        removed.retain(|key, _| !unverified_removes.contains(key) || active.contains(key));
+    }
+
+    Ok(Some(FileChanges {
+        added: added.into_values().collect(),
+        removed: removed.into_values().collect(),
+        num_records_removed: None,
+    }))
+}
+
+/// The subset of `candidates` that are active files of `from`.
+async fn active_files(
+    log_store: &dyn LogStore,
+    from: &Snapshot,
+    mut candidates: HashSet<FileKey>,
+) -> DeltaResult<HashSet<FileKey>> {
+    let mut active = HashSet::new();
+    let mut files = from.file_views(log_store, None);
+    while let Some(file) = files.try_next().await? {
+        let key = file_key(file.path_raw(), file.deletion_vector_descriptor().as_ref());
+        if let Some(key) = candidates.take(&key) {
+            active.insert(key);
+            if candidates.is_empty() {
+                break;
+            }
+        }
+    }
+    Ok(active)
+}
+
+/// Compare the active files of the snapshots at both ends of the range.
+async fn compare_snapshots(
+    log_store: &dyn LogStore,
+    from: &Snapshot,
+    to: &Snapshot,
+) -> DeltaResult<FileChanges> {
+    let mut previous: HashMap<FileKey, (Remove, Option<i64>)> = from
+        .file_views(log_store, None)
+        .map_ok(|file| {
+            // actions read from the commits keep the encoded path as stored in the log
+            let mut remove = file.remove_action(true);
+            remove.path = file.path_raw().to_string();
+            let num_records = num_records(
+                file.num_records().map(|n| n as i64),
+                remove.deletion_vector.as_ref(),
+            );
+            (
+                file_key(&remove.path, remove.deletion_vector.as_ref()),
+                (remove, num_records),
+            )
+        })
+        .try_collect()
+        .await?;
+
+    let mut added = Vec::new();
+    let mut files = to.file_views(log_store, None);
+    while let Some(file) = files.try_next().await? {
+        let mut add = file.add_action();
+        add.path = file.path_raw().to_string();
+        if previous
+            .remove(&file_key(&add.path, add.deletion_vector.as_ref()))
+            .is_none()
+        {
+            added.push(add);
+        }
+    }
+
+    let (removed, num_records_removed): (Vec<_>, Vec<_>) = previous.into_values().unzip();
+    Ok(FileChanges {
+        added,
+        removed,
+        num_records_removed: Some(num_records_removed.into_iter().sum()),
+    })
+}
+
+/// Count the records of the removed files from their statistics at `from`.
+async fn removed_records(
+    log_store: &dyn LogStore,
+    from: &Snapshot,
+    removed: &[Remove],
+) -> DeltaResult<Option<i64>> {
+    if removed.is_empty() {
+        return Ok(Some(0));
+    }
+    let mut remaining: HashSet<FileKey> = removed
+        .iter()
+        .map(|remove| file_key(&remove.path, remove.deletion_vector.as_ref()))
+        .collect();
+
+    let mut total = Some(0);
+    let mut files = from.file_views(log_store, None);
+    while let Some(file) = files.try_next().await? {
+        let dv = file.deletion_vector_descriptor();
+        if remaining.remove(&file_key(file.path_raw(), dv.as_ref())) {
+            let num_records = num_records(file.num_records().map(|n| n as i64), dv.as_ref());
+            total = total.zip(num_records).map(|(total, n)| total + n);
+            if remaining.is_empty() {
+                break;
+            }
+        }
+    }
+    Ok(total.filter(|_| remaining.is_empty()))
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use datafusion::prelude::{col, lit};
+
+    use super::*;
+    use crate::TableProperty;
+    use crate::writer::test_utils::datafusion::write_batch;
+    use crate::writer::test_utils::{
+        get_delta_schema, get_record_batch_from_rows, setup_table_with_configuration,
+    };
+
+    async fn setup_table() -> DeltaResult<DeltaTable> {
+        let table = setup_table_with_configuration(TableProperty::AppendOnly, Some("false")).await;
+        let table = write_batch(
+            table,
+            get_record_batch_from_rows(&[("A", 1, "2021-02-01"), ("B", 2, "2021-02-01")]),
+        )
+        .await;
+        let table = write_batch(table, get_record_batch_from_rows(&[("C", 3, "2021-02-01")])).await;
+        let (table, _) = table
+            .delete()
+            .with_predicate(col("value").eq(lit(1)))
+            .await?;
+        Ok(table)
+    }
+
+    fn paths<'a>(paths: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+        let mut paths: Vec<_> = paths.collect();
+        paths.sort();
+        paths
+    }
+
+    #[tokio::test]
+    async fn test_diff() -> DeltaResult<()> {
+        let table = setup_table().await?;
+
+        let diff = table.diff(1, 3).await?;
+        assert_eq!(diff.added_files.len(), 2);
+        assert_eq!(diff.removed_files.len(), 1);
+        assert_eq!(diff.num_records_added, Some(2));
+        assert_eq!(diff.num_records_removed, Some(2));
+        assert_eq!(diff.num_records_delta(), Some(0));
+        assert!(diff.metadata.is_none());
+        assert!(diff.protocol.is_none());
+
+        let diff = table.diff(0, 1).await?;
+        assert_eq!(diff.added_files.len(), 1);
+        assert!(diff.removed_files.is_empty());
+        assert_eq!(diff.num_records_delta(), Some(2));
+
+        assert!(table.diff(2, 1).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_diff_compare_snapshots() -> DeltaResult<()> {
+        let table = setup_table().await?;
+        let log_store = table.log_store();
+        let config = table.config.clone();
+        let from = Snapshot::try_new(log_store.as_ref(), config.clone(), Some(1)).await?;
+        let to = Snapshot::try_new(log_store.as_ref(), config, Some(3)).await?;
+
+        let replayed = replay_commits(log_store.as_ref(), &from, 3).await?.unwrap();
+        let compared = compare_snapshots(log_store.as_ref(), &from, &to).await?;
+        assert_eq!(
+            paths(replayed.added.iter().map(|add| &add.path)),
+            paths(compared.added.iter().map(|add| &add.path))
+        );
+        assert_eq!(replayed.removed.len(), compared.removed.len());
+        assert_eq!(compared.num_records_removed, Some(Some(2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_diff_compute_stats() -> DeltaResult<()> {
+        let table =
+            setup_table_with_configuration(TableProperty::DataSkippingNumIndexedCols, Some("0"))
+                .await;
+        let table = write_batch(
+            table,
+            get_record_batch_from_rows(&[("A", 1, "2021-02-01"), ("B", 2, "2021-02-01")]),
+        )
+        .await;
+        let table = write_batch(table, get_record_batch_from_rows(&[("C", 3, "2021-02-01")])).await;
+        // the files were written without statistics for `value`, which compute stats adds
+        let table = table
+            .set_tbl_properties()
+            .with_properties(HashMap::from([(
+                TableProperty::DataSkippingStatsColumns.as_ref().to_string(),
+                "value".to_string(),
+            )]))
+            .await?;
+        let (table, metrics) = table.compute_stats().await?;
+        assert_eq!(metrics.num_files_updated, 2);
+        let (table, _) = table
+            .delete()
+            .with_predicate(col("value").eq(lit(1)))
+            .await?;
+        assert_eq!(table.version(), Some(5));
+
+        // re-adding files to update their statistics does not change them
+        let diff = table.diff(2, 4).await?;
+        assert!(diff.added_files.is_empty());
+        assert!(diff.removed_files.is_empty());
+        assert_eq!(diff.num_records_delta(), Some(0));
+
+        let log_store = table.log_store();
+        let config = table.config.clone();
+        let from = Snapshot::try_new(log_store.as_ref(), config.clone(), Some(2)).await?;
+        let to = Snapshot::try_new(log_store.as_ref(), config, Some(5)).await?;
+
+        let replayed = replay_commits(log_store.as_ref(), &from, 5).await?.unwrap();
+        let compared = compare_snapshots(log_store.as_ref(), &from, &to).await?;
+        assert_eq!(replayed.added.len(), 1);
+        assert_eq!(replayed.removed.len(), 1);
+        assert_eq!(
+            paths(replayed.added.iter().map(|add| &add.path)),
+            paths(compared.added.iter().map(|add| &add.path))
+        );
+        assert_eq!(
+            paths(replayed.removed.iter().map(|remove| &remove.path)),
+            paths(compared.removed.iter().map(|remove| &remove.path))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_diff_escaped_partition_values() -> DeltaResult<()> {
+        let table = DeltaTable::new_in_memory()
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .with_partition_columns(["modified"])
+            .await?;
+        let table = write_batch(
+            table,
+            get_record_batch_from_rows(&[
+                ("A", 1, "2021-02-01 10:00:00"),
+                ("B", 2, "2021-02-01 10:00:00"),
+            ]),
+        )
+        .await;
+        let table = write_batch(
+            table,
+            get_record_batch_from_rows(&[("C", 3, "2021-02-02 10:00:00")]),
+        )
+        .await;
+        let (table, _) = table
+            .delete()
+            .with_predicate(col("value").eq(lit(1)))
+            .await?;
+        let log_store = table.log_store();
+        let config = table.config.clone();
+        let from = Snapshot::try_new(log_store.as_ref(), config.clone(), Some(1)).await?;
+        let to = Snapshot::try_new(log_store.as_ref(), config, Some(3)).await?;
+
+        let replayed = replay_commits(log_store.as_ref(), &from, 3).await?.unwrap();
+        let compared = compare_snapshots(log_store.as_ref(), &from, &to).await?;
+        assert!(replayed.added.iter().all(|add| add.path.contains("%20")));
+        assert_eq!(
+            paths(replayed.added.iter().map(|add| &add.path)),
+            paths(compared.added.iter().map(|add| &add.path))
+        );
+        assert_eq!(
+            paths(replayed.removed.iter().map(|remove| &remove.path)),
+            paths(compared.removed.iter().map(|remove| &remove.path))
+        );
+        assert_eq!(
+            removed_records(log_store.as_ref(), &from, &replayed.removed).await?,
+            Some(2)
+        );
+        assert_eq!(compared.num_records_removed, Some(Some(2)));
+
+        let diff = table.diff(1, 3).await?;
+        assert_eq!(diff.num_records_delta(), Some(0));
+        Ok(())
+    }
+}
@@ -14,19 +14,23 @@ use serde::de::{Error, SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use url::Url;
+use uuid::Uuid;
 
 use self::builder::DeltaTableConfig;
 use self::state::DeltaTableState;
-use crate::kernel::{CommitInfo, DataCheck, LogicalFileView};
+use crate::kernel::{CommitInfo, DataCheck, LogicalFileView, Snapshot};
 use crate::logstore::{
     LogStoreConfig, LogStoreExt, LogStoreRef, ObjectStoreRef, commit_uri_from_version,
     extract_version_from_filename,
 };
 use crate::partitions::PartitionFilter;
+use crate::protocol::leases::{ReaderLease, delete_lease, write_lease};
 use crate::{DeltaResult, DeltaTableBuilder, DeltaTableError};
 
 pub mod builder;
 pub mod config;
+pub mod diff;
+pub mod snapshot_cache;
 pub mod state;
 
 mod columns;
@@ -141,8 +145,10 @@ impl DeltaTable {
     ///
     /// NOTE: This is for advanced users. If you don't know why you need to use this method,
     /// please call one of the `open_table` helper methods instead.
-    pub(crate) fn new_with_state(log_store: LogStoreRef, state: DeltaTableState) -> Self {
+    pub(crate) fn new_with_state(log_store: LogStoreRef, mut state: DeltaTableState) -> Self {
         let config = state.load_config().clone();
+        // files an operation loaded for its predicate are not a valid state of the table
+        state.snapshot = state.snapshot.without_partial_files();
         Self {
             state: Some(state),
             log_store,
@@ -255,6 +261,46 @@ impl DeltaTable {
         Ok(infos.into_iter().flatten())
     }
 
+    /// Record a lease on the loaded version of the table.
+    ///
+    /// Until the lease expires or is released, vacuum keeps the files of the version, so
+    /// long running reads of that version do not fail. See [`crate::protocol::leases`].
+    ///
+    /// Fails if the version can no longer be loaded from the log of the table, e.g. because
+    /// its commits were cleaned up after the table was loaded.
+    pub async fn acquire_lease(&self, duration: std::time::Duration) -> DeltaResult<ReaderLease> {
+        let version = self.version().ok_or(DeltaTableError::NotInitialized)?;
+        let mut config = self.config.clone();
+        config.require_files = false;
+        if let Err(err) = Snapshot::try_new(self.log_store.as_ref(), config, Some(version)).await {
+            return Err(DeltaTableError::Generic(format!(
+                "Cannot lease version {version}, it can no longer be loaded: {err}"
+            )));
+        }
+        let lease = ReaderLease {
+            id: Uuid::new_v4().to_string(),
+            version,
+            expires_at: lease_expiration(duration)?,
+        };
+        write_lease(self.log_store.as_ref(), &lease).await?;
+        Ok(lease)
+    }
+
+    /// Extend a lease to expire after the given duration from now.
+    pub async fn renew_lease(
+        &self,
+        lease: &mut ReaderLease,
+        duration: std::time::Duration,
+    ) -> DeltaResult<()> {
+        lease.expires_at = lease_expiration(duration)?;
+        write_lease(self.log_store.as_ref(), lease).await
+    }
+
+    /// Release a lease, allowing vacuum to remove the files of its version.
+    pub async fn release_lease(&self, lease: ReaderLease) -> DeltaResult<()> {
+        delete_lease(self.log_store.as_ref(), &lease.id).await
+    }
+
     #[cfg(test)]
     /// We have enough internal tests that just need to check the last commit of the table.
     ///
@@ -441,6 +487,12 @@ impl std::fmt::Debug for DeltaTable {
     }
 }
 
+fn lease_expiration(duration: std::time::Duration) -> DeltaResult<i64> {
+    let duration = chrono::Duration::from_std(duration)
+        .map_err(|_| DeltaTableError::generic("Lease duration is out of range"))?;
+    Ok((Utc::now() + duration).timestamp_millis())
+}
+
 /// Normalize a given [Url] to **always** contain a trailing slash. This is critically important
 /// for assumptions about [Url] equivalency and more importantly for **joining** on a Url`.
 ///
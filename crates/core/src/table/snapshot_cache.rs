@@ -0,0 +1,589 @@
+//! Persistent cache for table snapshots
+//!
+//! Loading a table requires reading the latest checkpoint and all commits since. The
+//! [`SnapshotCache`] persists the reconciled state of a table in an [`ObjectStore`], so that a new
+//! process can restore the cached state and only replay the commits added since it was cached.
+//!
+//! Cache entries are keyed by table location and version and are stored as
+//! `<table hash>/<version>.snapshot`. Each entry is prefixed with a SHA-256 checksum of its
+//! content, entries that fail validation are discarded and removed from the cache. Entries also
+//! record the e-tag, modification time and size of the commit they were cached at and are only
+//! restored while that commit still exists unchanged, e.g. not after the table was recreated at
+//! the same location.
+//!
+//! The checksum only detects corrupted entries, anyone able to modify an entry can also update
+//! its checksum. To detect tampering, configure a secret key with
+//! [`SnapshotCache::with_signing_key`], entries are then prefixed with an HMAC-SHA256 of their
+//! content instead.
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::TryStreamExt;
+use hmac::{Hmac, Mac};
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::kernel::EagerSnapshot;
+use crate::logstore::{LogStore, ObjectStoreRef, commit_uri_from_version};
+use crate::{DeltaResult, DeltaTableError};
+
+const ENTRY_SUFFIX: &str = ".snapshot";
+const CHECKSUM_LEN: usize = 32;
+const FINGERPRINT_LEN_SIZE: usize = 4;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Secret key signing cache entries, kept out of debug output
+#[derive(Clone)]
+struct SigningKey(Arc<[u8]>);
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SigningKey(..)")
+    }
+}
+
+impl SigningKey {
+    fn mac(&self, data: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac
+    }
+}
+
+/// The commit file a snapshot was cached at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitFingerprint {
+    e_tag: Option<String>,
+    last_modified: i64,
+    size: u64,
+}
+
+impl CommitFingerprint {
+    /// The fingerprint of the commit file of `version`, `None` if the commit does not exist
+    async fn of(log_store: &dyn LogStore, version: i64) -> DeltaResult<Option<Self>> {
+        match log_store
+            .object_store(None)
+            .head(&commit_uri_from_version(version))
+            .await
+        {
+            Ok(meta) => Ok(Some(Self {
+                e_tag: meta.e_tag,
+                last_modified: meta.last_modified.timestamp_millis(),
+                size: meta.size,
+            })),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// A persistent cache of table snapshots backed by an [`ObjectStore`]
+#[derive(Debug, Clone)]
+pub struct SnapshotCache {
+    store: ObjectStoreRef,
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    signing_key: Option<SigningKey>,
+}
+
+impl SnapshotCache {
+    /// Create a cache storing the snapshots in the given object store
+    pub fn new(store: ObjectStoreRef) -> Self {
+        Self {
+            store,
+            max_size: None,
+            max_age: None,
+            signing_key: None,
+        }
+    }
+
+    /// Create a cache storing the snapshots in a local directory, the directory is created if
+    /// it does not exist.
+    pub fn try_new_local(path: impl AsRef<std::path::Path>) -> DeltaResult<Self> {
+        std::fs::create_dir_all(path.as_ref())
+            .map_err(|err| DeltaTableError::generic(err.to_string()))?;
+        Ok(Self::new(Arc::new(LocalFileSystem::new_with_prefix(
+            path.as_ref(),
+        )?)))
+    }
+
+    /// Limit the total size in bytes of the cached snapshots per table, older snapshots are
+    /// evicted first.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Ignore and evict cached snapshots that are older than the given duration
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sign cache entries with an HMAC-SHA256 using the given secret key.
+    ///
+    /// Entries that were not signed with the same key are discarded, so modified entries are
+    /// never restored as long as the key is kept secret.
+    pub fn with_signing_key(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.signing_key = Some(SigningKey(Arc::from(key.as_ref())));
+        self
+    }
+
+    /// Get the most recent valid cached snapshot of the table with a version of at most
+    /// `max_version`, whose commit still exists unchanged in the log of `log_store`.
+    pub(crate) async fn get(
+        &self,
+        table_url: &Url,
+        max_version: Option<i64>,
+        log_store: &dyn LogStore,
+    ) -> DeltaResult<Option<EagerSnapshot>> {
+        let mut entries = self.list_entries(table_url).await?;
+        entries.retain(|(version, _)| max_version.is_none_or(|max| *version <= max));
+
+        for (version, meta) in entries.into_iter().rev() {
+            let data = self.store.get(&meta.location).await?.bytes().await?;
+            let commit = CommitFingerprint::of(log_store, version).await?;
+            let snapshot = self
+                .verify_checksum(&data)
+                .and_then(split_fingerprint)
+                .and_then(|(fingerprint, data)| {
+                    if commit.as_ref() != Some(&fingerprint) {
+                        return Err(DeltaTableError::generic(format!(
+                            "commit {version} was removed or replaced since it was cached"
+                        )));
+                    }
+                    EagerSnapshot::decode(data)
+                })
+                .and_then(|snapshot| {
+                    if snapshot.version() != version
+                        || snapshot.snapshot().table_root_path()?
+                            != Path::from_url_path(table_url.path())?
+                    {
+                        return Err(DeltaTableError::generic(
+                            "cached snapshot does not match its cache key",
+                        ));
+                    }
+                    Ok(snapshot)
+                });
+            match snapshot {
+                Ok(snapshot) => {
+                    debug!("restored snapshot of {table_url} at version {version} from cache");
+                    return Ok(Some(snapshot));
+                }
+                Err(err) => {
+                    warn!("evicting invalid cached snapshot {}: {err}", meta.location);
+                    self.store.delete(&meta.location).await?;
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Store the snapshot in the cache and evict entries exceeding the configured limits.
+    pub(crate) async fn put(
+        &self,
+        table_url: &Url,
+        snapshot: &EagerSnapshot,
+        log_store: &dyn LogStore,
+    ) -> DeltaResult<()> {
+        let Some(commit) = CommitFingerprint::of(log_store, snapshot.version()).await? else {
+            debug!(
+                "not caching snapshot of {table_url}, the commit of version {} does not exist",
+                snapshot.version()
+            );
+            return Ok(());
+        };
+        let fingerprint = serde_json::to_vec(&commit)?;
+        let data = snapshot.encode()?;
+
+        let mut payload = Vec::with_capacity(FINGERPRINT_LEN_SIZE + fingerprint.len() + data.len());
+        payload.extend_from_slice(&(fingerprint.len() as u32).to_le_bytes());
+        payload.extend_from_slice(&fingerprint);
+        payload.extend_from_slice(&data);
+        if self
+            .max_size
+            .is_some_and(|max| (payload.len() + CHECKSUM_LEN) as u64 > max)
+        {
+            debug!(
+                "not caching snapshot of {table_url}, its size exceeds the configured cache size"
+            );
+            return Ok(());
+        }
+
+        let mut entry = Vec::with_capacity(payload.len() + CHECKSUM_LEN);
+        entry.extend_from_slice(&self.checksum(&payload));
+        entry.extend_from_slice(&payload);
+        let location =
+            table_prefix(table_url).child(format!("{:020}{ENTRY_SUFFIX}", snapshot.version()));
+        self.store.put(&location, Bytes::from(entry).into()).await?;
+
+        // Keep the most recent entries within the configured size
+        if let Some(max_size) = self.max_size {
+            let mut total_size = 0;
+            for (_, meta) in self.list_entries(table_url).await?.into_iter().rev() {
+                total_size += meta.size;
+                if total_size > max_size {
+                    self.store.delete(&meta.location).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The checksum of an entry, an HMAC if a signing key is configured
+    fn checksum(&self, data: &[u8]) -> Vec<u8> {
+        match &self.signing_key {
+            Some(key) => key.mac(data).finalize().into_bytes().to_vec(),
+            None => Sha256::digest(data).to_vec(),
+        }
+    }
+
+    /// Validate the checksum of a cache entry and return its content
+    fn verify_checksum<'a>(&self, entry: &'a [u8]) -> DeltaResult<&'a [u8]> {
+        let (checksum, data) = entry
+            .split_at_checked(CHECKSUM_LEN)
+            .ok_or_else(|| DeltaTableError::generic("cache entry is truncated"))?;
+        let valid = match &self.signing_key {
+            Some(key) => key.mac(data).verify_slice(checksum).is_ok(),
+            None => Sha256::digest(data).as_slice() == checksum,
+        };
+        if !valid {
+            return Err(DeltaTableError::generic("cache entry checksum mismatch"));
+        }
+        Ok(data)
+    }
+
+    /// List the cached entries of a table ordered by ascending version, expired entries are
+    /// removed from the cache.
+    async fn list_entries(&self, table_url: &Url) -> DeltaResult<Vec<(i64, ObjectMeta)>> {
+        let prefix = table_prefix(table_url);
+        let mut entries = Vec::new();
+        for meta in self
+            .store
+            .list(Some(&prefix))
+            .try_collect::<Vec<_>>()
+            .await?
+        {
+            let Some(version) = meta
+                .location
+                .filename()
+                .and_then(|name| name.strip_suffix(ENTRY_SUFFIX))
+                .and_then(|version| version.parse::<i64>().ok())
+            else {
+                continue;
+            };
+            let expired = self.max_age.is_some_and(|max_age| {
+                (Utc::now() - meta.last_modified)
+                    .to_std()
+                    .is_ok_and(|age| age > max_age)
+            });
+            if expired {
+                debug!("evicting expired cached snapshot {}", meta.location);
+                self.store.delete(&meta.location).await?;
+                continue;
+            }
+            entries.push((version, meta));
+        }
+        entries.sort_unstable_by_key(|(version, _)| *version);
+        Ok(entries)
+    }
+}
+
+/// Split the validated content of an entry into the commit fingerprint and the snapshot data
+fn split_fingerprint(payload: &[u8]) -> DeltaResult<(CommitFingerprint, &[u8])> {
+    let (len, rest) = payload
+        .split_first_chunk::<FINGERPRINT_LEN_SIZE>()
+        .ok_or_else(|| DeltaTableError::generic("cache entry is truncated"))?;
+    let (fingerprint, data) = rest
+        .split_at_checked(u32::from_le_bytes(*len) as usize)
+        .ok_or_else(|| DeltaTableError::generic("cache entry is truncated"))?;
+    Ok((serde_json::from_slice(fingerprint)?, data))
+}
+
+/// All entries of a table are stored under a prefix derived from the table location
+fn table_prefix(table_url: &Url) -> Path {
+    let hash = Sha256::digest(table_url.as_str().trim_end_matches('/').as_bytes());
+    Path::from(
+        hash.iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>(),
+    )
+}
+
+#[cfg(all(test, feature = "datafusion"))]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+    use crate::DeltaTableBuilder;
+    use crate::protocol::checkpoints::create_checkpoint;
+    use crate::writer::test_utils::{get_delta_schema, get_record_batch_from_rows};
+    use crate::{DeltaTable, ensure_table_uri};
+
+    async fn setup_table(path: &std::path::Path, commits: i32) -> DeltaResult<Url> {
+        let table_uri = ensure_table_uri(path.to_str().unwrap())?;
+        let mut table = DeltaTable::try_from_url(table_uri.clone())
+            .await?
+            .create()
+            .with_columns(get_delta_schema().fields().cloned())
+            .await?;
+        for value in 0..commits {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        Ok(table_uri)
+    }
+
+    #[tokio::test]
+    async fn test_restore_and_update_cached_snapshot() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 2).await?;
+        let cache = SnapshotCache::new(Arc::new(InMemory::new()));
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        assert_eq!(table.version(), Some(2));
+        let cached = cache
+            .get(&table_uri, None, table.log_store().as_ref())
+            .await?
+            .expect("cached snapshot");
+        assert_eq!(cached.version(), 2);
+        assert_eq!(cached.log_data().num_files(), 2);
+
+        let table = table
+            .write(vec![get_record_batch_from_rows(&[("A", 3, "2021-02-02")])])
+            .await?;
+        assert_eq!(table.version(), Some(3));
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        assert_eq!(table.version(), Some(3));
+        assert_eq!(table.snapshot()?.log_data().num_files(), 3);
+        let cached = cache
+            .get(&table_uri, None, table.log_store().as_ref())
+            .await?
+            .expect("cached snapshot");
+        assert_eq!(cached.version(), 3);
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .with_version(2)
+            .load()
+            .await?;
+        assert_eq!(table.version(), Some(2));
+        assert_eq!(table.snapshot()?.log_data().num_files(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tampered_entry_is_evicted() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 1).await?;
+        let store = Arc::new(InMemory::new());
+        let cache = SnapshotCache::new(store.clone());
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        let location = table_prefix(&table_uri).child(format!("{:020}{ENTRY_SUFFIX}", 1));
+        let mut entry = store.get(&location).await?.bytes().await?.to_vec();
+        let last = entry.len() - 1;
+        entry[last] ^= 0xff;
+        store.put(&location, Bytes::from(entry).into()).await?;
+
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+        assert!(store.head(&location).await.is_err());
+
+        let reloaded = DeltaTableBuilder::from_url(table_uri)?
+            .with_snapshot_cache(cache)
+            .load()
+            .await?;
+        assert_eq!(reloaded.version(), table.version());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entry_of_replaced_commit_is_evicted() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 1).await?;
+        let store = Arc::new(InMemory::new());
+        let cache = SnapshotCache::new(store.clone());
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_some()
+        );
+
+        // the table is recreated at the same location
+        std::fs::remove_dir_all(tmp_dir.path().join("_delta_log")).unwrap();
+        setup_table(tmp_dir.path(), 2).await?;
+
+        let location = table_prefix(&table_uri).child(format!("{:020}{ENTRY_SUFFIX}", 1));
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+        assert!(store.head(&location).await.is_err());
+
+        let table = DeltaTableBuilder::from_url(table_uri)?
+            .with_snapshot_cache(cache)
+            .load()
+            .await?;
+        assert_eq!(table.version(), Some(2));
+        assert_eq!(table.snapshot()?.log_data().num_files(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_entries() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 1).await?;
+        let store = Arc::new(InMemory::new());
+        let cache = SnapshotCache::new(store.clone()).with_signing_key("secret");
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_some()
+        );
+
+        // a modified entry with a recomputed checksum is rejected
+        let location = table_prefix(&table_uri).child(format!("{:020}{ENTRY_SUFFIX}", 1));
+        let entry = store.get(&location).await?.bytes().await?;
+        let data = entry[CHECKSUM_LEN..].to_vec();
+        let mut forged = Sha256::digest(&data).to_vec();
+        forged.extend_from_slice(&data);
+        store.put(&location, Bytes::from(forged).into()).await?;
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+        assert!(store.head(&location).await.is_err());
+
+        // entries signed with another key are rejected
+        store.put(&location, entry.into()).await?;
+        let other = SnapshotCache::new(store.clone()).with_signing_key("other");
+        assert!(
+            other
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fallback_when_cached_snapshot_cannot_be_updated() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 1).await?;
+        let cache = SnapshotCache::new(Arc::new(InMemory::new()));
+
+        let mut table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        for value in 2..4 {
+            table = table
+                .write(vec![get_record_batch_from_rows(&[(
+                    "A",
+                    value,
+                    "2021-02-02",
+                )])])
+                .await?;
+        }
+        create_checkpoint(&table, None).await?;
+        // the commits following the cached version are cleaned up
+        for version in 0..3 {
+            std::fs::remove_file(
+                tmp_dir
+                    .path()
+                    .join("_delta_log")
+                    .join(format!("{version:020}.json")),
+            )
+            .unwrap();
+        }
+
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .with_snapshot_cache(cache.clone())
+            .load()
+            .await?;
+        assert_eq!(table.version(), Some(3));
+        assert_eq!(table.snapshot()?.log_data().num_files(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_limits() -> DeltaResult<()> {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let table_uri = setup_table(tmp_dir.path(), 2).await?;
+        let table = DeltaTableBuilder::from_url(table_uri.clone())?
+            .load()
+            .await?;
+        let snapshot = table.snapshot()?.snapshot();
+
+        let cache = SnapshotCache::new(Arc::new(InMemory::new())).with_max_size(1);
+        cache
+            .put(&table_uri, snapshot, table.log_store().as_ref())
+            .await?;
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+
+        let cache = SnapshotCache::new(Arc::new(InMemory::new())).with_max_age(Duration::ZERO);
+        cache
+            .put(&table_uri, snapshot, table.log_store().as_ref())
+            .await?;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(
+            cache
+                .get(&table_uri, None, table.log_store().as_ref())
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+}
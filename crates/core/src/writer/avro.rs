@@ -0,0 +1,649 @@
+//! Writer for Avro records to a delta lake table
+//!
+//! Records are decoded straight into arrow arrays of the table schema, fields are matched by
+//! name and fields missing in the table schema are ignored. Messages in the Confluent wire format
+//! (magic byte, schema id, datum) are decoded with the schemas of a [`SchemaRegistry`].
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::Decimal as AvroDecimal;
+use apache_avro::schema::{RecordSchema, Schema as AvroSchema};
+use apache_avro::types::Value as AvroValue;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array,
+    Int32Array, Int64Array, ListArray, MapArray, RecordBatch, StringArray, StructArray,
+    TimestampMicrosecondArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType as ArrowDataType, Field, SchemaRef as ArrowSchemaRef, TimeUnit};
+use parquet::file::properties::WriterProperties;
+
+use super::{DeltaWriter, DeltaWriterError, RecordBatchWriter, WriteMode};
+use crate::DeltaTable;
+use crate::errors::{DeltaResult, DeltaTableError};
+use crate::kernel::{Add, ArrayType, DataType, MapType, PrimitiveType, StructField, StructType};
+
+/// Magic byte starting messages in the Confluent wire format
+const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// Source of the writer schemas referenced by id in messages of the Confluent wire format
+#[async_trait::async_trait]
+pub trait SchemaRegistry: std::fmt::Debug + Send + Sync {
+    /// Get the schema registered with the given id
+    async fn schema(&self, id: u32) -> DeltaResult<AvroSchema>;
+}
+
+/// A [`SchemaRegistry`] serving a fixed set of schemas, e.g. for tests or pinned schemas
+#[derive(Debug, Default, Clone)]
+pub struct StaticSchemaRegistry {
+    schemas: HashMap<u32, AvroSchema>,
+}
+
+impl StaticSchemaRegistry {
+    /// Register `schema` under `id`
+    pub fn with_schema(mut self, id: u32, schema: AvroSchema) -> Self {
+        self.schemas.insert(id, schema);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl SchemaRegistry for StaticSchemaRegistry {
+    async fn schema(&self, id: u32) -> DeltaResult<AvroSchema> {
+        self.schemas
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| DeltaTableError::Generic(format!("Unknown Avro schema id {id}")))
+    }
+}
+
+/// Convert the Avro schema of a record to a delta schema.
+///
+/// Unions of `null` and a single other type are nullable fields of that type, other unions are
+/// not supported. Decimals, dates and timestamps map to their delta counterparts, local
+/// timestamps to `timestamp_ntz`.
+pub fn avro_schema_to_delta(schema: &AvroSchema) -> DeltaResult<StructType> {
+    match schema {
+        AvroSchema::Record(record) => record_to_struct(record),
+        _ => Err(unsupported_schema(schema)),
+    }
+}
+
+fn record_to_struct(record: &RecordSchema) -> DeltaResult<StructType> {
+    let fields = record
+        .fields
+        .iter()
+        .map(|field| {
+            let (data_type, nullable) = avro_type_to_delta(&field.schema)?;
+            Ok(StructField::new(field.name.clone(), data_type, nullable))
+        })
+        .collect::<DeltaResult<Vec<_>>>()?;
+    Ok(StructType::try_new(fields)?)
+}
+
+/// Delta type of an Avro schema and whether values may be null
+fn avro_type_to_delta(schema: &AvroSchema) -> DeltaResult<(DataType, bool)> {
+    let data_type = match schema {
+        AvroSchema::Union(union) => {
+            let variants = union
+                .variants()
+                .iter()
+                .filter(|variant| !matches!(variant, AvroSchema::Null))
+                .collect::<Vec<_>>();
+            return match variants.as_slice() {
+                [variant] => Ok((avro_type_to_delta(variant)?.0, true)),
+                _ => Err(unsupported_schema(schema)),
+            };
+        }
+        AvroSchema::Boolean => DataType::BOOLEAN,
+        AvroSchema::Int | AvroSchema::TimeMillis => DataType::INTEGER,
+        AvroSchema::Long | AvroSchema::TimeMicros => DataType::LONG,
+        AvroSchema::Float => DataType::FLOAT,
+        AvroSchema::Double => DataType::DOUBLE,
+        AvroSchema::Bytes | AvroSchema::Fixed(_) => DataType::BINARY,
+        AvroSchema::String | AvroSchema::Enum(_) | AvroSchema::Uuid => DataType::STRING,
+        AvroSchema::Date => DataType::DATE,
+        AvroSchema::TimestampMillis | AvroSchema::TimestampMicros | AvroSchema::TimestampNanos => {
+            DataType::TIMESTAMP
+        }
+        AvroSchema::LocalTimestampMillis
+        | AvroSchema::LocalTimestampMicros
+        | AvroSchema::LocalTimestampNanos => DataType::TIMESTAMP_NTZ,
+        AvroSchema::Decimal(decimal) => DataType::Primitive(PrimitiveType::decimal(
+            decimal.precision as u8,
+            decimal.scale as u8,
+        )?),
+        AvroSchema::Array(array) => {
+            let (element_type, contains_null) = avro_type_to_delta(&array.items)?;
+            DataType::Array(Box::new(ArrayType::new(element_type, contains_null)))
+        }
+        AvroSchema::Map(map) => {
+            let (value_type, value_contains_null) = avro_type_to_delta(&map.types)?;
+            DataType::Map(Box::new(MapType::new(
+                DataType::STRING,
+                value_type,
+                value_contains_null,
+            )))
+        }
+        AvroSchema::Record(record) => DataType::Struct(Box::new(record_to_struct(record)?)),
+        _ => return Err(unsupported_schema(schema)),
+    };
+    Ok((data_type, false))
+}
+
+fn unsupported_schema(schema: &AvroSchema) -> DeltaTableError {
+    DeltaTableError::Generic(format!(
+        "Avro schema {} cannot be mapped to a delta schema",
+        schema.canonical_form()
+    ))
+}
+
+/// Convert Avro records to a [`RecordBatch`] of `schema`, matching record fields by name.
+pub(crate) fn record_batch_from_avro(
+    schema: ArrowSchemaRef,
+    records: &[AvroValue],
+) -> Result<RecordBatch, DeltaWriterError> {
+    let records = records.iter().map(Some).collect::<Vec<_>>();
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let values = records
+                .iter()
+                .map(|record| record_field(*record, field.name()))
+                .collect::<Result<Vec<_>, _>>()?;
+            avro_to_array(field, &values)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// Value of the field `name` of a record, `None` if the record or field is null or missing
+fn record_field<'a>(
+    record: Option<&'a AvroValue>,
+    name: &str,
+) -> Result<Option<&'a AvroValue>, DeltaWriterError> {
+    match record.and_then(non_null) {
+        None => Ok(None),
+        Some(AvroValue::Record(fields)) => Ok(fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .and_then(|(_, value)| non_null(value))),
+        Some(value) => Err(DeltaWriterError::InvalidRecord(format!("{value:?}"))),
+    }
+}
+
+/// Unwrap unions, mapping `null` to `None`
+fn non_null(value: &AvroValue) -> Option<&AvroValue> {
+    match value {
+        AvroValue::Null => None,
+        AvroValue::Union(_, value) => non_null(value),
+        value => Some(value),
+    }
+}
+
+/// Convert values with a conversion returning `None` for values of an unexpected type
+fn convert<'a, T>(
+    field: &Field,
+    values: &[Option<&'a AvroValue>],
+    f: impl Fn(&'a AvroValue) -> Option<T>,
+) -> Result<Vec<Option<T>>, DeltaWriterError> {
+    values
+        .iter()
+        .map(|value| match *value {
+            None => Ok(None),
+            Some(value) => f(value).map(Some).ok_or_else(|| {
+                DeltaWriterError::InvalidRecord(format!(
+                    "Avro value {value:?} cannot be written to field {} of type {}",
+                    field.name(),
+                    field.data_type()
+                ))
+            }),
+        })
+        .collect()
+}
+
+fn avro_to_array(
+    field: &Field,
+    values: &[Option<&AvroValue>],
+) -> Result<ArrayRef, DeltaWriterError> {
+    let values = values
+        .iter()
+        .map(|value| value.and_then(non_null))
+        .collect::<Vec<_>>();
+    let array: ArrayRef = match field.data_type() {
+        ArrowDataType::Boolean => {
+            Arc::new(BooleanArray::from(convert(field, &values, |v| match v {
+                AvroValue::Boolean(b) => Some(*b),
+                _ => None,
+            })?))
+        }
+        ArrowDataType::Int32 => Arc::new(Int32Array::from(convert(field, &values, |v| match v {
+            AvroValue::Int(i) | AvroValue::TimeMillis(i) => Some(*i),
+            _ => None,
+        })?)),
+        ArrowDataType::Int64 => Arc::new(Int64Array::from(convert(field, &values, |v| match v {
+            AvroValue::Long(l) | AvroValue::TimeMicros(l) => Some(*l),
+            AvroValue::Int(i) => Some(*i as i64),
+            _ => None,
+        })?)),
+        ArrowDataType::Float32 => {
+            Arc::new(Float32Array::from(convert(field, &values, |v| match v {
+                AvroValue::Float(f) => Some(*f),
+                _ => None,
+            })?))
+        }
+        ArrowDataType::Float64 => {
+            Arc::new(Float64Array::from(convert(field, &values, |v| match v {
+                AvroValue::Double(d) => Some(*d),
+                AvroValue::Float(f) => Some(*f as f64),
+                _ => None,
+            })?))
+        }
+        ArrowDataType::Utf8 => Arc::new(StringArray::from(convert(field, &values, |v| match v {
+            AvroValue::String(s) | AvroValue::Enum(_, s) => Some(s.clone()),
+            AvroValue::Uuid(uuid) => Some(uuid.to_string()),
+            _ => None,
+        })?)),
+        ArrowDataType::Binary => Arc::new(BinaryArray::from_iter(convert(
+            field,
+            &values,
+            |v| match v {
+                AvroValue::Bytes(b) | AvroValue::Fixed(_, b) => Some(b.clone()),
+                _ => None,
+            },
+        )?)),
+        ArrowDataType::Date32 => {
+            Arc::new(Date32Array::from(convert(field, &values, |v| match v {
+                AvroValue::Date(d) | AvroValue::Int(d) => Some(*d),
+                _ => None,
+            })?))
+        }
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, tz) => Arc::new(
+            TimestampMicrosecondArray::from(convert(field, &values, |v| match v {
+                AvroValue::TimestampMillis(t) | AvroValue::LocalTimestampMillis(t) => {
+                    t.checked_mul(1000)
+                }
+                AvroValue::TimestampMicros(t)
+                | AvroValue::LocalTimestampMicros(t)
+                | AvroValue::Long(t) => Some(*t),
+                AvroValue::TimestampNanos(t) | AvroValue::LocalTimestampNanos(t) => {
+                    Some(t.div_euclid(1000))
+                }
+                _ => None,
+            })?)
+            .with_timezone_opt(tz.clone()),
+        ),
+        ArrowDataType::Decimal128(precision, scale) => Arc::new(
+            Decimal128Array::from(convert(field, &values, |v| match v {
+                AvroValue::Decimal(decimal) => decimal_to_i128(decimal),
+                AvroValue::Bytes(b) | AvroValue::Fixed(_, b) => i128_from_be_bytes(b),
+                _ => None,
+            })?)
+            .with_precision_and_scale(*precision, *scale)?,
+        ),
+        ArrowDataType::Struct(fields) => {
+            let children = fields
+                .iter()
+                .map(|child| {
+                    let child_values = values
+                        .iter()
+                        .map(|value| record_field(*value, child.name()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    avro_to_array(child, &child_values)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(StructArray::try_new(
+                fields.clone(),
+                children,
+                nulls(&values),
+            )?)
+        }
+        ArrowDataType::List(item_field) => {
+            let items = convert(field, &values, |v| match v {
+                AvroValue::Array(items) => Some(items),
+                _ => None,
+            })?;
+            let lengths = items
+                .iter()
+                .map(|items| items.map_or(0, |items| items.len()));
+            let flattened = items
+                .iter()
+                .flatten()
+                .flat_map(|items| items.iter().map(Some))
+                .collect::<Vec<_>>();
+            Arc::new(ListArray::try_new(
+                item_field.clone(),
+                OffsetBuffer::from_lengths(lengths),
+                avro_to_array(item_field, &flattened)?,
+                nulls(&values),
+            )?)
+        }
+        ArrowDataType::Map(entries_field, sorted) => {
+            let ArrowDataType::Struct(entry_fields) = entries_field.data_type() else {
+                return Err(DeltaWriterError::InvalidRecord(format!(
+                    "Unexpected map entries {entries_field}"
+                )));
+            };
+            let maps = convert(field, &values, |v| match v {
+                AvroValue::Map(map) => Some(map),
+                _ => None,
+            })?;
+            let lengths = maps.iter().map(|map| map.map_or(0, |map| map.len()));
+            let (keys, map_values): (Vec<&str>, Vec<Option<&AvroValue>>) = maps
+                .iter()
+                .flatten()
+                .flat_map(|map| map.iter().map(|(key, value)| (key.as_str(), Some(value))))
+                .unzip();
+            let entries = StructArray::try_new(
+                entry_fields.clone(),
+                vec![
+                    Arc::new(StringArray::from(keys)),
+                    avro_to_array(&entry_fields[1], &map_values)?,
+                ],
+                None,
+            )?;
+            Arc::new(MapArray::try_new(
+                entries_field.clone(),
+                OffsetBuffer::from_lengths(lengths),
+                entries,
+                nulls(&values),
+                *sorted,
+            )?)
+        }
+        data_type => {
+            return Err(DeltaWriterError::InvalidRecord(format!(
+                "Writing Avro values to field {} of type {data_type} is not supported",
+                field.name()
+            )));
+        }
+    };
+    Ok(array)
+}
+
+fn nulls(values: &[Option<&AvroValue>]) -> Option<NullBuffer> {
+    let nulls = NullBuffer::from(values.iter().map(Option::is_some).collect::<Vec<_>>());
+    (nulls.null_count() > 0).then_some(nulls)
+}
+
+fn decimal_to_i128(decimal: &AvroDecimal) -> Option<i128> {
+    i128_from_be_bytes(&Vec::<u8>::try_from(decimal).ok()?)
+}
+
+/// Unscaled value of a decimal stored as big-endian two's complement
+fn i128_from_be_bytes(bytes: &[u8]) -> Option<i128> {
+    if bytes.len() > 16 {
+        return None;
+    }
+    let fill = match bytes.first() {
+        Some(byte) if byte & 0x80 != 0 => 0xff,
+        _ => 0,
+    };
+    let mut buffer = [fill; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buffer))
+}
+
+/// Writes Avro records to a delta lake table.
+///
+/// Partitioning and file statistics are handled by an inner [`RecordBatchWriter`].
+#[derive(Debug)]
+pub struct AvroWriter {
+    writer: RecordBatchWriter,
+    schema_registry: Option<Arc<dyn SchemaRegistry>>,
+    /// Writer schemas fetched from the registry
+    schemas: HashMap<u32, Arc<AvroSchema>>,
+}
+
+impl AvroWriter {
+    /// Creates an [`AvroWriter`] to write to the given table
+    pub fn for_table(table: &DeltaTable) -> Result<Self, DeltaTableError> {
+        Ok(Self {
+            writer: RecordBatchWriter::for_table(table)?,
+            schema_registry: None,
+            schemas: HashMap::new(),
+        })
+    }
+
+    /// Sets the registry resolving the schema ids of messages passed to
+    /// [`AvroWriter::write_messages`].
+    pub fn with_schema_registry(mut self, schema_registry: Arc<dyn SchemaRegistry>) -> Self {
+        self.schema_registry = Some(schema_registry);
+        self
+    }
+
+    /// Sets the writer properties for the underlying arrow writer.
+    pub fn with_writer_properties(mut self, writer_properties: WriterProperties) -> Self {
+        self.writer = self.writer.with_writer_properties(writer_properties);
+        self
+    }
+
+    /// Returns the current byte length of the in memory buffer.
+    pub fn buffer_len(&self) -> usize {
+        self.writer.buffer_len()
+    }
+
+    /// Returns the arrow schema records are converted to.
+    pub fn arrow_schema(&self) -> ArrowSchemaRef {
+        self.writer.arrow_schema()
+    }
+
+    /// Decode and write messages in the Confluent wire format, a zero byte and the big-endian
+    /// schema id followed by the Avro datum.
+    pub async fn write_messages(
+        &mut self,
+        messages: &[impl AsRef<[u8]>],
+    ) -> Result<(), DeltaTableError> {
+        let mut records = Vec::with_capacity(messages.len());
+        for message in messages {
+            let message = message.as_ref();
+            let (schema_id, mut datum) = match message {
+                [CONFLUENT_MAGIC_BYTE, id @ ..] if id.len() >= 4 => {
+                    let (id, datum) = id.split_at(4);
+                    (
+                        u32::from_be_bytes(id.try_into().expect("four bytes")),
+                        datum,
+                    )
+                }
+                _ => {
+                    return Err(DeltaWriterError::InvalidRecord(
+                        "Message is not in the Confluent wire format".to_string(),
+                    )
+                    .into());
+                }
+            };
+            let schema = self.writer_schema(schema_id).await?;
+            let record =
+                apache_avro::from_avro_datum(&schema, &mut datum, None).map_err(|err| {
+                    DeltaTableError::GenericError {
+                        source: Box::new(err),
+                    }
+                })?;
+            records.push(record);
+        }
+        self.write(records).await
+    }
+
+    async fn writer_schema(&mut self, id: u32) -> DeltaResult<Arc<AvroSchema>> {
+        if let Some(schema) = self.schemas.get(&id) {
+            return Ok(schema.clone());
+        }
+        let registry = self.schema_registry.as_ref().ok_or_else(|| {
+            DeltaTableError::Generic("Decoding messages requires a schema registry".to_string())
+        })?;
+        let schema = Arc::new(registry.schema(id).await?);
+        self.schemas.insert(id, schema.clone());
+        Ok(schema)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeltaWriter<Vec<AvroValue>> for AvroWriter {
+    /// Write a chunk of records into the internal write buffers with the default write mode
+    async fn write(&mut self, values: Vec<AvroValue>) -> Result<(), DeltaTableError> {
+        self.write_with_mode(values, WriteMode::Default).await
+    }
+
+    /// Converts the records to a record batch of the table schema and writes it to the buffers
+    /// of its partitions.
+    async fn write_with_mode(
+        &mut self,
+        values: Vec<AvroValue>,
+        mode: WriteMode,
+    ) -> Result<(), DeltaTableError> {
+        let batch = record_batch_from_avro(self.writer.arrow_schema(), &values)?;
+        self.writer.write_with_mode(batch, mode).await
+    }
+
+    /// Writes the existing parquet bytes to storage and resets internal state to handle another
+    /// file.
+    async fn flush(&mut self) -> Result<Vec<Add>, DeltaTableError> {
+        self.writer.flush().await
+    }
+
+    /// Flush the internal write buffers to files in the delta table folder structure.
+    /// and commit the changes to the Delta log, creating a new table version.
+    async fn flush_and_commit(&mut self, table: &mut DeltaTable) -> Result<i64, DeltaTableError> {
+        self.writer.flush_and_commit(table).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::Array;
+    use delta_kernel::engine::arrow_conversion::TryIntoArrow as _;
+
+    use crate::operations::create::CreateBuilder;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "order",
+        "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}},
+            {"name": "created", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "note", "type": ["null", "string"], "default": null},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "day", "type": "string"}
+        ]
+    }"#;
+
+    fn order(id: &str, amount: i64, note: Option<&str>) -> AvroValue {
+        AvroValue::Record(vec![
+            ("id".to_string(), AvroValue::String(id.to_string())),
+            (
+                "amount".to_string(),
+                AvroValue::Decimal(AvroDecimal::from(amount.to_be_bytes().to_vec())),
+            ),
+            (
+                "created".to_string(),
+                AvroValue::TimestampMillis(1_700_000_000_000),
+            ),
+            (
+                "note".to_string(),
+                match note {
+                    Some(note) => AvroValue::Union(1, Box::new(AvroValue::String(note.into()))),
+                    None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+                },
+            ),
+            (
+                "tags".to_string(),
+                AvroValue::Array(vec![AvroValue::String("new".to_string())]),
+            ),
+            (
+                "day".to_string(),
+                AvroValue::String("2024-01-01".to_string()),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_avro_schema_to_delta() {
+        let schema = AvroSchema::parse_str(SCHEMA).unwrap();
+        let delta_schema = avro_schema_to_delta(&schema).unwrap();
+        let fields = delta_schema.fields().collect::<Vec<_>>();
+        assert_eq!(
+            fields[1].data_type(),
+            &DataType::Primitive(PrimitiveType::decimal(10, 2).unwrap())
+        );
+        assert_eq!(fields[2].data_type(), &DataType::TIMESTAMP);
+        assert!(!fields[0].is_nullable());
+        assert!(fields[3].is_nullable());
+        assert_eq!(
+            fields[4].data_type(),
+            &DataType::Array(Box::new(ArrayType::new(DataType::STRING, false)))
+        );
+
+        let union = AvroSchema::parse_str(r#"["int", "string"]"#).unwrap();
+        assert!(avro_type_to_delta(&union).is_err());
+    }
+
+    #[test]
+    fn test_record_batch_from_avro() {
+        let schema = avro_schema_to_delta(&AvroSchema::parse_str(SCHEMA).unwrap()).unwrap();
+        let arrow_schema: arrow_schema::Schema = (&schema).try_into_arrow().unwrap();
+        let batch = record_batch_from_avro(
+            Arc::new(arrow_schema),
+            &[order("a", 1250, Some("rush")), order("b", -5, None)],
+        )
+        .unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        let amount = batch
+            .column_by_name("amount")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(amount.value_as_string(0), "12.50");
+        assert_eq!(amount.value_as_string(1), "-0.05");
+        let created = batch
+            .column_by_name("created")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(created.value(0), 1_700_000_000_000_000);
+        assert!(batch.column_by_name("note").unwrap().is_null(1));
+    }
+
+    #[tokio::test]
+    async fn test_write_messages() {
+        let avro_schema = AvroSchema::parse_str(SCHEMA).unwrap();
+        let schema = avro_schema_to_delta(&avro_schema).unwrap();
+        let table_dir = tempfile::tempdir().unwrap();
+        let mut table = CreateBuilder::new()
+            .with_location(table_dir.path().to_str().unwrap())
+            .with_columns(schema.fields().cloned())
+            .with_partition_columns(["day"])
+            .await
+            .unwrap();
+
+        let messages = [order("a", 1250, None), order("b", 300, Some("gift"))]
+            .into_iter()
+            .map(|record| {
+                let mut message = vec![CONFLUENT_MAGIC_BYTE];
+                message.extend(7u32.to_be_bytes());
+                message.extend(apache_avro::to_avro_datum(&avro_schema, record).unwrap());
+                message
+            })
+            .collect::<Vec<_>>();
+
+        let mut writer = AvroWriter::for_table(&table).unwrap();
+        assert!(writer.write_messages(&messages).await.is_err());
+
+        let registry = StaticSchemaRegistry::default().with_schema(7, avro_schema);
+        let mut writer = writer.with_schema_registry(Arc::new(registry));
+        writer.write_messages(&messages).await.unwrap();
+        let adds = writer.flush().await.unwrap();
+        assert_eq!(adds.len(), 1);
+        assert!(adds[0].path.starts_with("day=2024-01-01/"));
+        assert!(adds[0].stats.as_ref().unwrap().contains("\"numRecords\":2"));
+
+        let mut writer = AvroWriter::for_table(&table).unwrap();
+        writer.write(vec![order("c", 1, None)]).await.unwrap();
+        assert_eq!(writer.flush_and_commit(&mut table).await.unwrap(), 1);
+    }
+}
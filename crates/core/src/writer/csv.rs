@@ -0,0 +1,237 @@
+//! Writer for CSV data to a delta lake table
+//!
+//! CSV documents are parsed straight into record batches of the table schema. With a header,
+//! columns are matched by name and may come in any order, table columns missing from the document
+//! are written as nulls. Without a header, the columns must be in the order of the table schema.
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::csv::ReaderBuilder;
+use arrow::csv::reader::Format;
+use arrow_array::{RecordBatch, new_null_array};
+use arrow_schema::{Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
+use bytes::Bytes;
+use parquet::file::properties::WriterProperties;
+
+use super::{DeltaWriter, DeltaWriterError, RecordBatchWriter, WriteMode};
+use crate::DeltaTable;
+use crate::errors::DeltaTableError;
+use crate::kernel::Add;
+
+/// Default number of rows per record batch parsed from a document
+const DEFAULT_BATCH_SIZE: usize = 8192;
+
+/// Writes CSV documents to a delta lake table.
+///
+/// Partitioning and file statistics are handled by an inner [`RecordBatchWriter`].
+#[derive(Debug)]
+pub struct CsvWriter {
+    writer: RecordBatchWriter,
+    format: Format,
+    has_header: bool,
+    batch_size: usize,
+}
+
+impl CsvWriter {
+    /// Creates a [`CsvWriter`] to write to the given table, expecting documents with a header
+    /// and comma separated values.
+    pub fn for_table(table: &DeltaTable) -> Result<Self, DeltaTableError> {
+        Ok(Self {
+            writer: RecordBatchWriter::for_table(table)?,
+            format: Format::default().with_header(true),
+            has_header: true,
+            batch_size: DEFAULT_BATCH_SIZE,
+        })
+    }
+
+    /// Sets whether documents start with a header line.
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.format = self.format.with_header(has_header);
+        self.has_header = has_header;
+        self
+    }
+
+    /// Sets the field delimiter, `,` by default.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.format = self.format.with_delimiter(delimiter);
+        self
+    }
+
+    /// Sets the quote character, `"` by default.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.format = self.format.with_quote(quote);
+        self
+    }
+
+    /// Sets the number of rows per record batch parsed from a document.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the writer properties for the underlying arrow writer.
+    pub fn with_writer_properties(mut self, writer_properties: WriterProperties) -> Self {
+        self.writer = self.writer.with_writer_properties(writer_properties);
+        self
+    }
+
+    /// Returns the current byte length of the in memory buffer.
+    pub fn buffer_len(&self) -> usize {
+        self.writer.buffer_len()
+    }
+
+    /// Returns the arrow schema documents are parsed into.
+    pub fn arrow_schema(&self) -> ArrowSchemaRef {
+        self.writer.arrow_schema()
+    }
+
+    /// Schema of the columns of a document, in the order of its header
+    fn document_schema(
+        &self,
+        table_schema: &ArrowSchema,
+        document: &Bytes,
+    ) -> Result<ArrowSchemaRef, DeltaWriterError> {
+        let (header, _) = self.format.infer_schema(Cursor::new(document), Some(0))?;
+        let fields = header
+            .fields()
+            .iter()
+            .map(|column| {
+                table_schema
+                    .field_with_name(column.name())
+                    .cloned()
+                    .map_err(|_| {
+                        DeltaWriterError::InvalidRecord(format!(
+                            "CSV column {} is not in the table schema",
+                            column.name()
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Arc::new(ArrowSchema::new(fields)))
+    }
+}
+
+/// Reorder the columns of `batch` to `schema`, filling missing columns with nulls
+fn align_to_schema(
+    batch: RecordBatch,
+    schema: &ArrowSchemaRef,
+) -> Result<RecordBatch, DeltaWriterError> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) => column.clone(),
+            None => new_null_array(field.data_type(), batch.num_rows()),
+        })
+        .collect();
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+#[async_trait::async_trait]
+impl DeltaWriter<Bytes> for CsvWriter {
+    /// Write a CSV document into the internal write buffers with the default write mode
+    async fn write(&mut self, values: Bytes) -> Result<(), DeltaTableError> {
+        self.write_with_mode(values, WriteMode::Default).await
+    }
+
+    /// Parses the document into record batches of the table schema and writes them to the
+    /// buffers of their partitions.
+    async fn write_with_mode(
+        &mut self,
+        values: Bytes,
+        mode: WriteMode,
+    ) -> Result<(), DeltaTableError> {
+        let table_schema = self.writer.arrow_schema();
+        let document_schema = if self.has_header {
+            self.document_schema(&table_schema, &values)?
+        } else {
+            table_schema.clone()
+        };
+        let reader = ReaderBuilder::new(document_schema)
+            .with_format(self.format.clone())
+            .with_batch_size(self.batch_size)
+            .build(Cursor::new(values))?;
+        for batch in reader {
+            let batch = align_to_schema(batch?, &table_schema)?;
+            self.writer.write_with_mode(batch, mode).await?;
+        }
+        Ok(())
+    }
+
+    /// Writes the existing parquet bytes to storage and resets internal state to handle another
+    /// file.
+    async fn flush(&mut self) -> Result<Vec<Add>, DeltaTableError> {
+        self.writer.flush().await
+    }
+
+    /// Flush the internal write buffers to files in the delta table folder structure.
+    /// and commit the changes to the Delta log, creating a new table version.
+    async fn flush_and_commit(&mut self, table: &mut DeltaTable) -> Result<i64, DeltaTableError> {
+        self.writer.flush_and_commit(table).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::writer::test_utils::create_initialized_table;
+
+    #[tokio::test]
+    async fn test_write_with_header() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let partition_cols = vec!["modified".to_string()];
+        let mut table =
+            create_initialized_table(table_dir.path().to_str().unwrap(), &partition_cols).await;
+
+        let mut writer = CsvWriter::for_table(&table).unwrap();
+        // columns in a different order than the table schema, `id` is missing
+        writer
+            .write(Bytes::from(
+                "modified,value\n2021-02-01,1\n2021-02-01,2\n2021-02-02,3\n",
+            ))
+            .await
+            .unwrap();
+        let adds = writer.flush().await.unwrap();
+        assert_eq!(adds.len(), 2);
+        for add in &adds {
+            assert!(add.path.starts_with("modified=2021-02-0"));
+            let stats = add.get_stats().unwrap().unwrap();
+            assert_eq!(stats.null_count["id"].as_value(), Some(stats.num_records));
+        }
+
+        let mut writer = CsvWriter::for_table(&table).unwrap();
+        let result = writer
+            .write(Bytes::from("modified,unknown\n2021-02-01,1\n"))
+            .await;
+        assert!(result.is_err());
+
+        writer
+            .write(Bytes::from("id,value,modified\nA,1,2021-02-01\n"))
+            .await
+            .unwrap();
+        assert_eq!(writer.flush_and_commit(&mut table).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_without_header() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let table = create_initialized_table(table_dir.path().to_str().unwrap(), &[]).await;
+
+        let mut writer = CsvWriter::for_table(&table)
+            .unwrap()
+            .with_header(false)
+            .with_delimiter(b';');
+        writer
+            .write(Bytes::from("A;1;2021-02-01\nB;2;2021-02-02\n"))
+            .await
+            .unwrap();
+        let adds = writer.flush().await.unwrap();
+        assert_eq!(adds.len(), 1);
+        assert_eq!(adds[0].get_stats().unwrap().unwrap().num_records, 2);
+
+        // a value which cannot be parsed as the column type fails the write
+        let result = writer.write(Bytes::from("A;x;2021-02-01\n")).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! Routing of records rejected by the [`JsonWriter`](super::JsonWriter) and
+//! [`RecordBatchWriter`](super::RecordBatchWriter).
+//!
+//! By default a writer fails the whole `write` call when records cannot be written. With a
+//! [`DeadLetterPolicy`] the rejected records are instead skipped, handed to a callback or written
+//! to a quarantine Delta table, and the remaining records are written as usual.
+//!
+//! Records are rejected when they cannot be encoded to parquet (`JsonWriter` only) and, with the
+//! `datafusion` feature, when they violate a `NOT NULL` column, column invariant, check
+//! constraint or generated column expression of the table.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_array::{
+    Array, BooleanArray, Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray,
+};
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use delta_kernel::engine::arrow_conversion::TryIntoArrow as _;
+use delta_kernel::table_configuration::TableConfiguration;
+use serde_json::Value;
+use tracing::*;
+use url::Url;
+
+use crate::DeltaTable;
+use crate::errors::DeltaResult;
+use crate::kernel::{DataType, PrimitiveType, StructField, StructType};
+use crate::protocol::SaveMode;
+use crate::table::builder::DeltaTableBuilder;
+use crate::writer::{DeltaWriter, RecordBatchWriter};
+
+/// A record rejected by a writer
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    /// The rejected record
+    pub record: Value,
+    /// Why the record was rejected
+    pub reason: String,
+    /// Hive style partition path the record was written to, `None` for unpartitioned tables
+    pub partition: Option<String>,
+    /// Offset of the record in its source, see `set_source_offset` on the writers
+    pub source_offset: Option<i64>,
+}
+
+/// Callback receiving the records rejected by a single `write` call
+pub type DeadLetterCallback = Arc<dyn Fn(Vec<DeadLetter>) + Send + Sync>;
+
+/// What a writer does with the records it rejects
+#[derive(Clone, Default)]
+pub enum DeadLetterPolicy {
+    /// Fail the `write` call, the default
+    #[default]
+    Fail,
+    /// Drop rejected records, only logging them
+    Skip,
+    /// Hand rejected records to a callback
+    Callback(DeadLetterCallback),
+    /// Write rejected records to a quarantine table when the writer is flushed.
+    ///
+    /// The table must have the schema of [`dead_letter_table_schema`], see
+    /// [`create_dead_letter_table`].
+    Table(Box<DeltaTable>),
+}
+
+impl std::fmt::Debug for DeadLetterPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fail => write!(f, "Fail"),
+            Self::Skip => write!(f, "Skip"),
+            Self::Callback(_) => write!(f, "Callback"),
+            Self::Table(table) => write!(f, "Table({})", table.table_url()),
+        }
+    }
+}
+
+/// Schema of a quarantine table for [`DeadLetterPolicy::Table`]
+pub fn dead_letter_table_schema() -> Vec<StructField> {
+    vec![
+        StructField::new("record", DataType::Primitive(PrimitiveType::String), false),
+        StructField::new("reason", DataType::Primitive(PrimitiveType::String), false),
+        StructField::new(
+            "partition",
+            DataType::Primitive(PrimitiveType::String),
+            true,
+        ),
+        StructField::new(
+            "source_offset",
+            DataType::Primitive(PrimitiveType::Long),
+            true,
+        ),
+        StructField::new(
+            "rejected_at",
+            DataType::Primitive(PrimitiveType::Timestamp),
+            false,
+        ),
+    ]
+}
+
+/// Create the quarantine table at `table_url` if it does not exist yet, and load it.
+pub async fn create_dead_letter_table(
+    table_url: Url,
+    storage_options: HashMap<String, String>,
+) -> DeltaResult<DeltaTable> {
+    DeltaTableBuilder::from_url(table_url)?
+        .with_storage_options(storage_options)
+        .build()?
+        .create()
+        .with_columns(dead_letter_table_schema())
+        .with_save_mode(SaveMode::Ignore)
+        .await
+}
+
+/// Applies a [`DeadLetterPolicy`] on behalf of a writer
+#[derive(Debug, Default)]
+pub(crate) struct DeadLetterSink {
+    policy: DeadLetterPolicy,
+    /// Dead letters waiting for the next flush into the quarantine table
+    pending: Vec<DeadLetter>,
+    /// Offset of the first record of the next write
+    source_offset: Option<i64>,
+    /// Validator for the last seen batch schema
+    #[cfg(feature = "datafusion")]
+    validator: Option<(
+        ArrowSchemaRef,
+        Option<Arc<crate::delta_datafusion::RowValidator>>,
+    )>,
+}
+
+impl DeadLetterSink {
+    pub(crate) fn new(policy: DeadLetterPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Whether rejected records are routed instead of failing the write
+    pub(crate) fn is_enabled(&self) -> bool {
+        !matches!(self.policy, DeadLetterPolicy::Fail)
+    }
+
+    pub(crate) fn set_source_offset(&mut self, offset: i64) {
+        self.source_offset = Some(offset);
+    }
+
+    /// Offset of the record at `position` in the current write
+    pub(crate) fn source_offset(&self, position: usize) -> Option<i64> {
+        self.source_offset.map(|offset| offset + position as i64)
+    }
+
+    /// Forget the source offset once a write is done, so it is not reused for the next one
+    pub(crate) fn finish_write(&mut self) {
+        self.source_offset = None;
+    }
+
+    /// Route the records rejected by a write according to the policy.
+    pub(crate) fn route(&mut self, dead_letters: Vec<DeadLetter>) {
+        if dead_letters.is_empty() {
+            return;
+        }
+        match &self.policy {
+            DeadLetterPolicy::Fail => {
+                debug_assert!(false, "records are never routed with the Fail policy")
+            }
+            DeadLetterPolicy::Skip => {
+                warn!(
+                    "Skipping {} rejected records, first reason: {}",
+                    dead_letters.len(),
+                    dead_letters[0].reason
+                );
+            }
+            DeadLetterPolicy::Callback(callback) => callback(dead_letters),
+            DeadLetterPolicy::Table(_) => self.pending.extend(dead_letters),
+        }
+    }
+
+    /// Commit pending dead letters to the quarantine table.
+    pub(crate) async fn flush(&mut self) -> DeltaResult<()> {
+        let DeadLetterPolicy::Table(table) = &mut self.policy else {
+            return Ok(());
+        };
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = dead_letters_to_batch(&self.pending)?;
+        table.update_state().await?;
+        let mut writer = RecordBatchWriter::for_table(table)?;
+        writer.write(batch).await?;
+        let version = writer.flush_and_commit(table).await?;
+        info!(
+            "Wrote {} rejected records to {} at version {version}",
+            self.pending.len(),
+            table.table_url()
+        );
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Mask of the rows of `batch` passing the table's validation rules and a description of the
+    /// rules, `None` when nothing needs to be checked.
+    #[cfg(feature = "datafusion")]
+    pub(crate) fn valid_rows(
+        &mut self,
+        table_configuration: Option<&TableConfiguration>,
+        batch: &RecordBatch,
+    ) -> DeltaResult<Option<(BooleanArray, String)>> {
+        let Some(table_configuration) = table_configuration.filter(|_| self.is_enabled()) else {
+            return Ok(None);
+        };
+        let validator = match &self.validator {
+            Some((schema, validator)) if schema == &batch.schema() => validator.clone(),
+            _ => {
+                let session = crate::delta_datafusion::create_session().state();
+                let validator = crate::delta_datafusion::RowValidator::try_new(
+                    &session,
+                    batch.schema(),
+                    table_configuration,
+                )?
+                .map(Arc::new);
+                self.validator = Some((batch.schema(), validator.clone()));
+                validator
+            }
+        };
+        let Some(validator) = validator else {
+            return Ok(None);
+        };
+        Ok(Some((
+            validator.valid_rows(batch)?,
+            format!("failed validation check: {}", validator.description()),
+        )))
+    }
+
+    #[cfg(not(feature = "datafusion"))]
+    pub(crate) fn valid_rows(
+        &mut self,
+        _table_configuration: Option<&TableConfiguration>,
+        _batch: &RecordBatch,
+    ) -> DeltaResult<Option<(BooleanArray, String)>> {
+        Ok(None)
+    }
+}
+
+/// Convert the rows of `batch` to JSON records
+pub(crate) fn batch_to_records(batch: &RecordBatch) -> DeltaResult<Vec<Value>> {
+    let mut writer = arrow_json::ArrayWriter::new(Vec::new());
+    writer.write(batch)?;
+    writer.finish()?;
+    let buffer = writer.into_inner();
+    if buffer.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_slice(&buffer)?)
+}
+
+fn dead_letters_to_batch(dead_letters: &[DeadLetter]) -> DeltaResult<RecordBatch> {
+    let rejected_at = chrono::Utc::now().timestamp_micros();
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(StringArray::from_iter_values(
+            dead_letters.iter().map(|d| d.record.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            dead_letters.iter().map(|d| d.reason.as_str()),
+        )),
+        Arc::new(StringArray::from_iter(
+            dead_letters.iter().map(|d| d.partition.as_deref()),
+        )),
+        Arc::new(Int64Array::from_iter(
+            dead_letters.iter().map(|d| d.source_offset),
+        )),
+        Arc::new(
+            TimestampMicrosecondArray::from(vec![rejected_at; dead_letters.len()])
+                .with_timezone("UTC"),
+        ),
+    ];
+    let schema: ArrowSchemaRef =
+        Arc::new((&StructType::try_new(dead_letter_table_schema())?).try_into_arrow()?);
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use crate::writer::test_utils::get_record_batch;
+
+    fn dead_letter(reason: &str) -> DeadLetter {
+        DeadLetter {
+            record: serde_json::json!({"id": "A"}),
+            reason: reason.to_string(),
+            partition: None,
+            source_offset: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_route_callback() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let captured = received.clone();
+        let mut sink = DeadLetterSink::new(DeadLetterPolicy::Callback(Arc::new(move |letters| {
+            captured.lock().unwrap().extend(letters)
+        })));
+        assert!(sink.is_enabled());
+        sink.route(vec![dead_letter("bad")]);
+        assert_eq!(*received.lock().unwrap(), vec![dead_letter("bad")]);
+    }
+
+    #[test]
+    fn test_source_offset() {
+        let mut sink = DeadLetterSink::new(DeadLetterPolicy::Skip);
+        assert_eq!(sink.source_offset(3), None);
+        sink.set_source_offset(100);
+        assert_eq!(sink.source_offset(3), Some(103));
+        sink.finish_write();
+        assert_eq!(sink.source_offset(3), None);
+    }
+
+    #[test]
+    fn test_batch_to_records() {
+        let batch = get_record_batch(None, false);
+        let records = batch_to_records(&batch).unwrap();
+        assert_eq!(records.len(), batch.num_rows());
+        assert_eq!(records[0]["id"], Value::from("A"));
+    }
+
+    #[tokio::test]
+    async fn test_flush_to_table() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let table_url = Url::from_directory_path(table_dir.path()).unwrap();
+        let table = create_dead_letter_table(table_url.clone(), HashMap::new())
+            .await
+            .unwrap();
+
+        let mut sink = DeadLetterSink::new(DeadLetterPolicy::Table(Box::new(table)));
+        sink.route(vec![dead_letter("bad"), dead_letter("worse")]);
+        sink.flush().await.unwrap();
+
+        let table = crate::open_table(table_url).await.unwrap();
+        assert_eq!(table.version(), Some(1));
+        assert_eq!(table.snapshot().unwrap().log_data().num_files(), 1);
+    }
+}
@@ -10,19 +10,17 @@ use delta_kernel::expressions::Scalar;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use object_store::path::Path;
-use parquet::{
-    arrow::ArrowWriter, basic::Compression, errors::ParquetError,
-    file::properties::WriterProperties,
-};
+use parquet::{arrow::ArrowWriter, errors::ParquetError, file::properties::WriterProperties};
 use serde_json::Value;
 use tracing::*;
 use url::Url;
 use uuid::Uuid;
 
+use super::dead_letter::{DeadLetter, DeadLetterPolicy, DeadLetterSink};
 use super::stats::create_add;
 use super::utils::{
     arrow_schema_without_partitions, next_data_path, record_batch_from_message,
-    record_batch_without_partitions,
+    record_batch_without_partitions, table_writer_properties,
 };
 use super::{DeltaWriter, DeltaWriterError, WriteMode};
 use crate::DeltaTable;
@@ -44,6 +42,7 @@ pub struct JsonWriter {
     writer_properties: WriterProperties,
     partition_columns: Vec<String>,
     arrow_writers: HashMap<String, DataArrowWriter>,
+    dead_letters: DeadLetterSink,
 }
 
 /// Writes messages to an underlying arrow buffer.
@@ -198,10 +197,8 @@ impl JsonWriter {
             .load()
             .await?;
         // Initialize writer properties for the underlying arrow writer
-        let writer_properties = WriterProperties::builder()
-            // NOTE: Consider extracting config for writer properties and setting more than just compression
-            .set_compression(Compression::SNAPPY)
-            .build();
+        let writer_properties =
+            table_writer_properties(table.snapshot()?.metadata().configuration());
 
         Ok(Self {
             table,
@@ -209,6 +206,7 @@ impl JsonWriter {
             writer_properties,
             partition_columns: partition_columns.unwrap_or_default(),
             arrow_writers: HashMap::new(),
+            dead_letters: DeadLetterSink::default(),
         })
     }
 
@@ -219,10 +217,7 @@ impl JsonWriter {
         let partition_columns = metadata.partition_columns().clone();
 
         // Initialize writer properties for the underlying arrow writer
-        let writer_properties = WriterProperties::builder()
-            // NOTE: Consider extracting config for writer properties and setting more than just compression
-            .set_compression(Compression::SNAPPY)
-            .build();
+        let writer_properties = table_writer_properties(metadata.configuration());
 
         Ok(Self {
             table: table.clone(),
@@ -230,9 +225,26 @@ impl JsonWriter {
             partition_columns,
             schema_ref: None,
             arrow_writers: HashMap::new(),
+            dead_letters: DeadLetterSink::default(),
         })
     }
 
+    /// Sets the [`DeadLetterPolicy`] for records which cannot be written.
+    ///
+    /// With any policy but [`DeadLetterPolicy::Fail`], records failing the parquet encoding or
+    /// the table's constraints and invariants are routed to the policy and `write` succeeds with
+    /// the remaining records.
+    pub fn with_dead_letter_policy(mut self, policy: DeadLetterPolicy) -> Self {
+        self.dead_letters = DeadLetterSink::new(policy);
+        self
+    }
+
+    /// Sets the offset in the source (e.g. a Kafka partition) of the first record of the next
+    /// `write`, so rejected records can be traced back to their source.
+    pub fn set_source_offset(&mut self, offset: i64) {
+        self.dead_letters.set_source_offset(offset);
+    }
+
     /// Returns the current byte length of the in memory buffer.
     /// This may be used by the caller to decide when to finalize the file write.
     pub fn buffer_len(&self) -> usize {
@@ -272,18 +284,19 @@ impl JsonWriter {
         )
     }
 
+    /// Divides the records by partition, keeping the position of each record in `records`.
     fn divide_by_partition_values(
         &self,
         records: Vec<Value>,
-    ) -> Result<HashMap<String, Vec<Value>>, DeltaWriterError> {
-        let mut partitioned_records: HashMap<String, Vec<Value>> = HashMap::new();
+    ) -> Result<HashMap<String, Vec<(usize, Value)>>, DeltaWriterError> {
+        let mut partitioned_records: HashMap<String, Vec<(usize, Value)>> = HashMap::new();
 
-        for record in records {
+        for (position, record) in records.into_iter().enumerate() {
             let partition_value = self.json_to_partition_values(&record)?;
             match partitioned_records.get_mut(&partition_value) {
-                Some(vec) => vec.push(record),
+                Some(vec) => vec.push((position, record)),
                 None => {
-                    partitioned_records.insert(partition_value, vec![record]);
+                    partitioned_records.insert(partition_value, vec![(position, record)]);
                 }
             };
         }
@@ -291,6 +304,59 @@ impl JsonWriter {
         Ok(partitioned_records)
     }
 
+    /// Hive style partition path of a record, `None` for unpartitioned tables
+    fn partition_path(
+        &self,
+        arrow_schema: Arc<ArrowSchema>,
+        record: &Value,
+    ) -> Result<Option<String>, DeltaWriterError> {
+        if self.partition_columns.is_empty() {
+            return Ok(None);
+        }
+        let record_batch = record_batch_from_message(arrow_schema, std::slice::from_ref(record))?;
+        let partition_values = extract_partition_values(&self.partition_columns, &record_batch)?;
+        Ok(Some(partition_values.hive_partition_path()))
+    }
+
+    /// Removes the records violating the table's validation rules from `records` and returns
+    /// them as dead letters.
+    fn split_invalid_records(
+        &mut self,
+        arrow_schema: Arc<ArrowSchema>,
+        records: &mut Vec<(usize, Value)>,
+    ) -> Result<Vec<DeadLetter>, DeltaTableError> {
+        let table_configuration = self.table.snapshot()?.snapshot().table_configuration();
+        let values = records.iter().map(|(_, value)| value.clone()).collect_vec();
+        let record_batch = record_batch_from_message(arrow_schema.clone(), &values)?;
+        let Some((valid, reason)) = self
+            .dead_letters
+            .valid_rows(Some(table_configuration), &record_batch)?
+        else {
+            return Ok(Vec::new());
+        };
+        if valid.true_count() == records.len() {
+            return Ok(Vec::new());
+        }
+
+        let partition = self.partition_path(arrow_schema, &records[0].1)?;
+        let mut dead_letters = Vec::new();
+        let mut kept = Vec::with_capacity(records.len());
+        for ((position, record), is_valid) in records.drain(..).zip(valid.values().iter()) {
+            if is_valid {
+                kept.push((position, record));
+            } else {
+                dead_letters.push(DeadLetter {
+                    record,
+                    reason: reason.clone(),
+                    partition: partition.clone(),
+                    source_offset: self.dead_letters.source_offset(position),
+                });
+            }
+        }
+        *records = kept;
+        Ok(dead_letters)
+    }
+
     fn json_to_partition_values(&self, value: &Value) -> Result<String, DeltaWriterError> {
         if let Some(obj) = value.as_object() {
             let key: Vec<String> = self
@@ -323,61 +389,20 @@ impl DeltaWriter<Vec<Value>> for JsonWriter {
                 "The JsonWriter does not currently support non-default write modes, falling back to default mode"
             );
         }
-        let mut partial_writes: Vec<(Value, ParquetError)> = Vec::new();
-        let arrow_schema = self.arrow_schema();
-        let divided = self.divide_by_partition_values(values)?;
-        let partition_columns = self.partition_columns.clone();
-        let writer_properties = self.writer_properties.clone();
-
-        for (key, values) in divided {
-            match self.arrow_writers.get_mut(&key) {
-                Some(writer) => {
-                    let result = writer
-                        .write_values(&partition_columns, arrow_schema.clone(), values)
-                        .await;
-                    collect_partial_write_failure(&mut partial_writes, result)?;
-                }
-                None => {
-                    let schema = arrow_schema_without_partitions(&arrow_schema, &partition_columns);
-                    let mut writer = DataArrowWriter::new(schema, writer_properties.clone())?;
-                    let result = writer
-                        .write_values(&partition_columns, arrow_schema.clone(), values)
-                        .await;
-                    collect_partial_write_failure(&mut partial_writes, result)?;
-                    self.arrow_writers.insert(key, writer);
-                }
-            }
-        }
-
-        if !partial_writes.is_empty() {
-            return Err(DeltaWriterError::PartialParquetWrite {
-                sample_error: match &partial_writes[0].1 {
-                    ParquetError::General(msg) => ParquetError::General(msg.to_owned()),
-                    ParquetError::ArrowError(msg) => ParquetError::ArrowError(msg.to_owned()),
-                    ParquetError::EOF(msg) => ParquetError::EOF(msg.to_owned()),
-                    ParquetError::External(err) => ParquetError::General(err.to_string()),
-                    ParquetError::IndexOutOfBound(u, v) => {
-                        ParquetError::IndexOutOfBound(u.to_owned(), v.to_owned())
-                    }
-                    ParquetError::NYI(msg) => ParquetError::NYI(msg.to_owned()),
-                    // ParquetError is non exhaustive, so have a fallback
-                    e => ParquetError::General(e.to_string()),
-                },
-                skipped_values: partial_writes,
-            }
-            .into());
-        }
-
-        Ok(())
+        let result = self.write_partitions(values).await;
+        self.dead_letters.finish_write();
+        result
     }
 
     /// Writes the existing parquet bytes to storage and resets internal state to handle another
     /// file.
     ///
     /// This function returns the [Add] actions which should be committed to the [DeltaTable] for
-    /// the written data files
+    /// the written data files. Records rejected under [`DeadLetterPolicy::Table`] are committed to
+    /// the quarantine table.
     #[instrument(skip(self), fields(writer_count = 0))]
     async fn flush(&mut self) -> Result<Vec<Add>, DeltaTableError> {
+        self.dead_letters.flush().await?;
         let writers = std::mem::take(&mut self.arrow_writers);
         let mut actions = Vec::with_capacity(writers.len());
 
@@ -419,6 +444,101 @@ impl DeltaWriter<Vec<Value>> for JsonWriter {
     }
 }
 
+impl JsonWriter {
+    /// Writes the values to the writers of their partitions, routing rejected records to the
+    /// dead letter policy.
+    async fn write_partitions(&mut self, values: Vec<Value>) -> Result<(), DeltaTableError> {
+        let mut partial_writes: Vec<(Value, ParquetError)> = Vec::new();
+        let mut dead_letters: Vec<DeadLetter> = Vec::new();
+        let arrow_schema = self.arrow_schema();
+        let divided = self.divide_by_partition_values(values)?;
+        let partition_columns = self.partition_columns.clone();
+        let writer_properties = self.writer_properties.clone();
+
+        for (key, mut records) in divided {
+            if self.dead_letters.is_enabled() {
+                dead_letters
+                    .extend(self.split_invalid_records(arrow_schema.clone(), &mut records)?);
+                if records.is_empty() {
+                    continue;
+                }
+            }
+            let (positions, values): (Vec<usize>, Vec<Value>) = records.into_iter().unzip();
+            // keep the records to find the positions of records failing the parquet encoding
+            let routed_values = self.dead_letters.is_enabled().then(|| values.clone());
+
+            let result = match self.arrow_writers.get_mut(&key) {
+                Some(writer) => {
+                    writer
+                        .write_values(&partition_columns, arrow_schema.clone(), values)
+                        .await
+                }
+                None => {
+                    let schema = arrow_schema_without_partitions(&arrow_schema, &partition_columns);
+                    let mut writer = DataArrowWriter::new(schema, writer_properties.clone())?;
+                    let result = writer
+                        .write_values(&partition_columns, arrow_schema.clone(), values)
+                        .await;
+                    if matches!(
+                        result,
+                        Ok(()) | Err(DeltaWriterError::PartialParquetWrite { .. })
+                    ) {
+                        self.arrow_writers.insert(key, writer);
+                    }
+                    result
+                }
+            };
+
+            match result {
+                Err(DeltaWriterError::PartialParquetWrite { skipped_values, .. })
+                    if routed_values.is_some() =>
+                {
+                    let values = routed_values.unwrap_or_default();
+                    let partition = self.partition_path(arrow_schema.clone(), &values[0])?;
+                    // skipped values are an in-order subsequence of the written values
+                    let mut written = positions.iter().zip(values.iter());
+                    for (value, error) in skipped_values {
+                        let position = written
+                            .find(|(_, written_value)| **written_value == value)
+                            .map(|(position, _)| *position);
+                        dead_letters.push(DeadLetter {
+                            record: value,
+                            reason: format!("failed to write to parquet: {error}"),
+                            partition: partition.clone(),
+                            source_offset: position
+                                .and_then(|position| self.dead_letters.source_offset(position)),
+                        });
+                    }
+                }
+                result => collect_partial_write_failure(&mut partial_writes, result)?,
+            }
+        }
+
+        self.dead_letters.route(dead_letters);
+
+        if !partial_writes.is_empty() {
+            return Err(DeltaWriterError::PartialParquetWrite {
+                sample_error: match &partial_writes[0].1 {
+                    ParquetError::General(msg) => ParquetError::General(msg.to_owned()),
+                    ParquetError::ArrowError(msg) => ParquetError::ArrowError(msg.to_owned()),
+                    ParquetError::EOF(msg) => ParquetError::EOF(msg.to_owned()),
+                    ParquetError::External(err) => ParquetError::General(err.to_string()),
+                    ParquetError::IndexOutOfBound(u, v) => {
+                        ParquetError::IndexOutOfBound(u.to_owned(), v.to_owned())
+                    }
+                    ParquetError::NYI(msg) => ParquetError::NYI(msg.to_owned()),
+                    // ParquetError is non exhaustive, so have a fallback
+                    e => ParquetError::General(e.to_string()),
+                },
+                skipped_values: partial_writes,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
 fn collect_partial_write_failure(
     partial_writes: &mut Vec<(Value, ParquetError)>,
     writer_result: Result<(), DeltaWriterError>,
@@ -613,6 +733,54 @@ mod tests {
         ));
     }
 
+    #[cfg(feature = "datafusion")]
+    #[tokio::test]
+    async fn test_dead_letter_callback() {
+        use std::sync::Mutex;
+
+        let table_dir = tempfile::tempdir().unwrap();
+        let table = get_test_table(&table_dir)
+            .await
+            .add_constraint()
+            .with_constraint("value_lt_100", "value < 100")
+            .await
+            .unwrap();
+
+        let rejected = Arc::new(Mutex::new(Vec::new()));
+        let captured = rejected.clone();
+        let mut writer = JsonWriter::for_table(&table)
+            .unwrap()
+            .with_dead_letter_policy(DeadLetterPolicy::Callback(Arc::new(move |letters| {
+                captured.lock().unwrap().extend(letters)
+            })));
+
+        writer.set_source_offset(10);
+        writer
+            .write(vec![
+                serde_json::json!({"id": "A", "value": 42, "modified": "2021-02-01"}),
+                serde_json::json!({"id": "B", "value": 500, "modified": "2021-02-01"}),
+                serde_json::json!({"id": "C", "value": 7, "modified": "2021-02-02"}),
+            ])
+            .await
+            .unwrap();
+
+        let rejected = rejected.lock().unwrap().clone();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].record["id"], Value::from("B"));
+        assert_eq!(rejected[0].source_offset, Some(11));
+        assert_eq!(rejected[0].partition, None);
+
+        let add_actions = writer.flush().await.unwrap();
+        assert_eq!(add_actions.len(), 1);
+        assert!(
+            add_actions[0]
+                .stats
+                .as_ref()
+                .unwrap()
+                .contains("\"numRecords\":2")
+        );
+    }
+
     // The following sets of tests are related to #1386 and mergeSchema support
     // <https://github.com/delta-io/delta-rs/issues/1386>
     mod schema_evolution {
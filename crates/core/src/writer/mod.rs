@@ -12,13 +12,23 @@ use crate::kernel::transaction::{CommitBuilder, CommitProperties};
 use crate::kernel::{Action, Add};
 use crate::protocol::{ColumnCountStat, DeltaOperation, SaveMode};
 
+#[cfg(feature = "avro")]
+pub use avro::AvroWriter;
+pub use csv::CsvWriter;
+pub use dead_letter::{DeadLetter, DeadLetterPolicy};
 pub use json::JsonWriter;
 pub use record_batch::RecordBatchWriter;
 pub use stats::create_add;
+pub use streaming::StreamingSink;
 
+#[cfg(feature = "avro")]
+pub mod avro;
+pub mod csv;
+pub mod dead_letter;
 pub mod json;
 pub mod record_batch;
 pub(crate) mod stats;
+pub mod streaming;
 pub mod utils;
 
 #[cfg(test)]
@@ -7,26 +7,30 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use arrow_arith::boolean::not;
 use arrow_array::{Array, ArrayRef, RecordBatch, UInt32Array, new_null_array};
 use arrow_ord::partition::partition;
 use arrow_row::{RowConverter, SortField};
 use arrow_schema::{ArrowError, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
+use arrow_select::filter::filter_record_batch;
 use arrow_select::take::take;
 use bytes::Bytes;
 use delta_kernel::engine::arrow_conversion::{TryIntoArrow, TryIntoKernel};
 use delta_kernel::expressions::Scalar;
+use delta_kernel::table_configuration::TableConfiguration;
 use delta_kernel::table_properties::DataSkippingNumIndexedCols;
 use indexmap::IndexMap;
 use object_store::{ObjectStore, path::Path};
+use parquet::file::properties::WriterProperties;
 use parquet::{arrow::ArrowWriter, errors::ParquetError};
-use parquet::{basic::Compression, file::properties::WriterProperties};
 use tracing::log::*;
 use uuid::Uuid;
 
+use super::dead_letter::{DeadLetter, DeadLetterPolicy, DeadLetterSink, batch_to_records};
 use super::stats::create_add;
 use super::utils::{
     ShareableBuffer, arrow_schema_without_partitions, next_data_path,
-    record_batch_without_partitions,
+    record_batch_without_partitions, table_writer_properties,
 };
 use super::{DeltaWriter, DeltaWriterError, WriteMode};
 use crate::DeltaTable;
@@ -51,6 +55,9 @@ pub struct RecordBatchWriter {
     num_indexed_cols: DataSkippingNumIndexedCols,
     stats_columns: Option<Vec<String>>,
     commit_properties: Option<CommitProperties>,
+    /// Configuration of the table, used to validate rows rejected under a dead letter policy
+    table_configuration: Option<TableConfiguration>,
+    dead_letters: DeadLetterSink,
 }
 
 impl std::fmt::Debug for RecordBatchWriter {
@@ -72,17 +79,13 @@ impl RecordBatchWriter {
         let delta_table = DeltaTableBuilder::from_url(table_url)?
             .with_storage_options(storage_options.unwrap_or_default())
             .build()?;
-        // Initialize writer properties for the underlying arrow writer
-        let writer_properties = WriterProperties::builder()
-            // NOTE: Consider extracting config for writer properties and setting more than just compression
-            .set_compression(Compression::SNAPPY)
-            .build();
-
         // if metadata fails to load, use an empty hashmap and default values for num_indexed_cols and stats_columns
         let configuration = delta_table.snapshot().map_or_else(
             |_| HashMap::new(),
             |snapshot| snapshot.metadata().configuration().clone(),
         );
+        // Initialize writer properties for the underlying arrow writer
+        let writer_properties = table_writer_properties(&configuration);
 
         Ok(Self {
             storage: delta_table.object_store(),
@@ -106,6 +109,11 @@ impl RecordBatchWriter {
                 .get("delta.dataSkippingStatsColumns")
                 .map(|v| v.split(',').map(|s| s.to_string()).collect()),
             commit_properties: None,
+            table_configuration: delta_table
+                .snapshot()
+                .ok()
+                .map(|snapshot| snapshot.snapshot().table_configuration().clone()),
+            dead_letters: DeadLetterSink::default(),
         })
     }
 
@@ -127,12 +135,10 @@ impl RecordBatchWriter {
         let arrow_schema_ref = Arc::new(arrow_schema);
         let partition_columns = metadata.partition_columns().clone();
 
-        // Initialize writer properties for the underlying arrow writer
-        let writer_properties = WriterProperties::builder()
-            // NOTE: Consider extracting config for writer properties and setting more than just compression
-            .set_compression(Compression::SNAPPY)
-            .build();
         let configuration = table.snapshot()?.metadata().configuration().clone();
+        // Initialize writer properties for the underlying arrow writer
+        let writer_properties = table_writer_properties(&configuration);
+        let table_configuration = table.snapshot()?.snapshot().table_configuration().clone();
 
         Ok(Self {
             storage: table.object_store(),
@@ -156,9 +162,26 @@ impl RecordBatchWriter {
                 .get("delta.dataSkippingStatsColumns")
                 .map(|v| v.split(',').map(|s| s.to_string()).collect()),
             commit_properties: None,
+            table_configuration: Some(table_configuration),
+            dead_letters: DeadLetterSink::default(),
         })
     }
 
+    /// Sets the [`DeadLetterPolicy`] for rows which cannot be written.
+    ///
+    /// With any policy but [`DeadLetterPolicy::Fail`], rows violating the table's constraints and
+    /// invariants are routed to the policy and `write` succeeds with the remaining rows.
+    pub fn with_dead_letter_policy(mut self, policy: DeadLetterPolicy) -> Self {
+        self.dead_letters = DeadLetterSink::new(policy);
+        self
+    }
+
+    /// Sets the offset in the source (e.g. a Kafka partition) of the first row of the next
+    /// `write`, so rejected rows can be traced back to their source.
+    pub fn set_source_offset(&mut self, offset: i64) {
+        self.dead_letters.set_source_offset(offset);
+    }
+
     /// Returns the current byte length of the in memory buffer.
     /// This may be used by the caller to decide when to finalize the file write.
     pub fn buffer_len(&self) -> usize {
@@ -237,6 +260,59 @@ impl RecordBatchWriter {
             values,
         )
     }
+
+    /// Removes the rows violating the table's validation rules from `values` and routes them
+    /// to the dead letter policy.
+    fn route_invalid_rows(&mut self, values: RecordBatch) -> Result<RecordBatch, DeltaTableError> {
+        let Some((valid, reason)) = self
+            .dead_letters
+            .valid_rows(self.table_configuration.as_ref(), &values)?
+        else {
+            return Ok(values);
+        };
+        if valid.true_count() == values.num_rows() {
+            return Ok(values);
+        }
+
+        let invalid = not(&valid)?;
+        let records = batch_to_records(&filter_record_batch(&values, &invalid)?)?;
+        let positions = invalid.values().set_indices();
+        let dead_letters = records
+            .into_iter()
+            .zip(positions)
+            .map(|(record, position)| {
+                Ok(DeadLetter {
+                    record,
+                    reason: reason.clone(),
+                    partition: self.partition_path(&values, position)?,
+                    source_offset: self.dead_letters.source_offset(position),
+                })
+            })
+            .collect::<Result<Vec<_>, DeltaWriterError>>()?;
+        self.dead_letters.route(dead_letters);
+
+        Ok(filter_record_batch(&values, &valid)?)
+    }
+
+    /// Hive style partition path of a row, `None` for unpartitioned tables
+    fn partition_path(
+        &self,
+        values: &RecordBatch,
+        row: usize,
+    ) -> Result<Option<String>, DeltaWriterError> {
+        if self.partition_columns.is_empty() {
+            return Ok(None);
+        }
+        let mut partition_values = IndexMap::new();
+        for name in &self.partition_columns {
+            let value = values
+                .column_by_name(name)
+                .and_then(|column| Scalar::from_array(column.as_ref(), row))
+                .ok_or_else(|| DeltaWriterError::MissingPartitionColumn(name.clone()))?;
+            partition_values.insert(name.clone(), value);
+        }
+        Ok(Some(partition_values.hive_partition_path()))
+    }
 }
 
 #[async_trait::async_trait]
@@ -262,6 +338,10 @@ impl DeltaWriter<RecordBatch> for RecordBatchWriter {
         // on its flush_and_commit
         self.should_evolve = mode == WriteMode::MergeSchema;
 
+        let values = self.route_invalid_rows(values);
+        self.dead_letters.finish_write();
+        let values = values?;
+
         for result in self.divide_by_partition_values(&values)? {
             let maybe_evolved_schema = self
                 .write_partition(result.record_batch, &result.partition_values, mode)
@@ -272,7 +352,10 @@ impl DeltaWriter<RecordBatch> for RecordBatchWriter {
     }
 
     /// Writes the existing parquet bytes to storage and resets internal state to handle another file.
+    ///
+    /// Rows rejected under [`DeadLetterPolicy::Table`] are committed to the quarantine table.
     async fn flush(&mut self) -> Result<Vec<Add>, DeltaTableError> {
+        self.dead_letters.flush().await?;
         let writers = std::mem::take(&mut self.arrow_writers);
         let mut actions = Vec::with_capacity(writers.len());
 
@@ -1133,6 +1216,72 @@ mod tests {
 
         use futures::TryStreamExt;
 
+        #[tokio::test]
+        async fn test_dead_letter_table() {
+            use crate::writer::dead_letter::create_dead_letter_table;
+
+            let table_dir = tempfile::tempdir().unwrap();
+            let table_path = table_dir.path().to_str().unwrap();
+            let partition_cols = vec!["modified".to_string()];
+            let mut table = create_initialized_table(table_path, &partition_cols)
+                .await
+                .add_constraint()
+                .with_constraint("value_lt_10", "value < 10")
+                .await
+                .unwrap();
+
+            let quarantine_dir = tempfile::tempdir().unwrap();
+            let quarantine_url = url::Url::from_directory_path(quarantine_dir.path()).unwrap();
+            let quarantine = create_dead_letter_table(quarantine_url.clone(), HashMap::new())
+                .await
+                .unwrap();
+
+            let mut writer = RecordBatchWriter::for_table(&table)
+                .unwrap()
+                .with_dead_letter_policy(DeadLetterPolicy::Table(Box::new(quarantine)));
+            writer.set_source_offset(100);
+            writer.write(get_record_batch(None, false)).await.unwrap();
+            writer.flush_and_commit(&mut table).await.unwrap();
+
+            let ctx = datafusion::prelude::SessionContext::new();
+            ctx.register_table("data", table.table_provider().await.unwrap())
+                .unwrap();
+            let rows = ctx
+                .sql("SELECT count(*) FROM data")
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            let count = rows[0]
+                .column(0)
+                .as_any()
+                .downcast_ref::<arrow_array::Int64Array>()
+                .unwrap()
+                .value(0);
+            assert_eq!(count, 9);
+
+            let quarantine = crate::open_table(quarantine_url).await.unwrap();
+            ctx.register_table("quarantine", quarantine.table_provider().await.unwrap())
+                .unwrap();
+            let rows = ctx
+                .sql("SELECT partition, source_offset FROM quarantine ORDER BY source_offset")
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            let expected = [
+                "+---------------------+---------------+",
+                "| partition           | source_offset |",
+                "+---------------------+---------------+",
+                "| modified=2021-02-01 | 109           |",
+                "| modified=2021-02-01 | 110           |",
+                "+---------------------+---------------+",
+            ];
+            datafusion::assert_batches_eq!(expected, &rows);
+        }
+
         #[tokio::test]
         async fn test_write_data_skipping_stats_columns() {
             let batch = get_record_batch(None, false);
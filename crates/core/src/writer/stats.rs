@@ -4,6 +4,16 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{collections::HashMap, ops::AddAssign};
 
+use arrow::compute::cast;
+use arrow_arith::aggregate::{
+    max as arrow_max, max_boolean, max_string, min as arrow_min, min_boolean, min_string,
+};
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int32Type, Int64Type,
+};
+use arrow_array::{Array, ArrayRef, AsArray, RecordBatch, make_array};
+use arrow_buffer::NullBuffer;
+use arrow_schema::{DataType as ArrowDataType, TimeUnit as ArrowTimeUnit};
 use delta_kernel::expressions::Scalar;
 use delta_kernel::table_properties::DataSkippingNumIndexedCols;
 use indexmap::IndexMap;
@@ -22,6 +32,9 @@ use super::*;
 use crate::kernel::{Add, scalars::ScalarExt};
 use crate::protocol::{ColumnValueStat, Stats};
 
+/// Number of characters kept for string min / max statistics, longer values are truncated
+const STATS_STRING_PREFIX_LENGTH: usize = 32;
+
 /// Creates an [`Add`] log action struct.
 pub fn create_add(
     partition_values: &IndexMap<String, Scalar>,
@@ -117,6 +130,30 @@ fn stats_from_file_metadata(
     )
 }
 
+/// Parse the column names configured in `delta.dataSkippingStatsColumns` into their path parts.
+pub(crate) fn parse_stats_columns(
+    stats_columns: &[impl AsRef<str>],
+) -> Result<Vec<Vec<String>>, DeltaWriterError> {
+    let dialect = sqlparser::dialect::GenericDialect {};
+    stats_columns
+        .iter()
+        .map(|v| {
+            match sqlparser::parser::Parser::new(&dialect)
+                .try_with_sql(v.as_ref())
+                .map_err(|e| DeltaTableError::generic(e.to_string()))?
+                .parse_multipart_identifier()
+            {
+                Ok(parts) => Ok(parts.into_iter().map(|v| v.value).collect_vec()),
+                Err(e) => Err(DeltaWriterError::DeltaTable(
+                    DeltaTableError::GenericError {
+                        source: Box::new(e),
+                    },
+                )),
+            }
+        })
+        .collect()
+}
+
 fn stats_from_metadata(
     partition_values: &IndexMap<String, Scalar>,
     schema_descriptor: Arc<SchemaDescriptor>,
@@ -124,37 +161,152 @@ fn stats_from_metadata(
     num_rows: i64,
     num_indexed_cols: DataSkippingNumIndexedCols,
     stats_columns: &Option<Vec<impl AsRef<str>>>,
+) -> Result<Stats, DeltaWriterError> {
+    stats_from_columns(
+        partition_values,
+        &schema_descriptor,
+        num_rows,
+        num_indexed_cols,
+        stats_columns,
+        |idx, column_descr| {
+            row_group_metadata
+                .iter()
+                .flat_map(|g| {
+                    g.column(idx).statistics().into_iter().filter_map(|s| {
+                        if is_binary_column(column_descr) {
+                            warn!(
+                                "Skipping column {} because it's a binary field.",
+                                &column_descr.name().to_string()
+                            );
+                            None
+                        } else {
+                            Some(AggregatedStats::from((s, column_descr.logical_type_ref())))
+                        }
+                    })
+                })
+                .reduce(|mut left, right| {
+                    left += right;
+                    left
+                })
+        },
+    )
+}
+
+/// Statistics of a file computed from its decoded record batches.
+///
+/// Used for files written without column statistics in their footer. Leaf columns are
+/// resolved by their Parquet path, so column selection and nesting match the statistics
+/// read from a footer. Columns nested in lists or maps get no statistics.
+pub(crate) struct RecordBatchStats {
+    schema_descriptor: Arc<SchemaDescriptor>,
+    columns: Vec<Option<AggregatedStats>>,
+    num_rows: i64,
+}
+
+impl RecordBatchStats {
+    pub(crate) fn new(schema_descriptor: Arc<SchemaDescriptor>) -> Self {
+        let columns = (0..schema_descriptor.num_columns()).map(|_| None).collect();
+        Self {
+            schema_descriptor,
+            columns,
+            num_rows: 0,
+        }
+    }
+
+    /// Add the values of a batch read from the file to the statistics
+    pub(crate) fn update(&mut self, batch: &RecordBatch) -> Result<(), DeltaWriterError> {
+        self.num_rows += batch.num_rows() as i64;
+        for (idx, column_descr) in self.schema_descriptor.columns().iter().enumerate() {
+            if column_descr.max_rep_level() > 0 || is_binary_column(column_descr) {
+                continue;
+            }
+            let Some(array) = leaf_array(batch, column_descr.path().parts())? else {
+                continue;
+            };
+            let stats = AggregatedStats {
+                min: StatsScalar::try_from_array(&array, true)?,
+                max: StatsScalar::try_from_array(&array, false)?,
+                null_count: array.null_count() as u64,
+            };
+            match &mut self.columns[idx] {
+                Some(aggregated) => *aggregated += stats,
+                empty => *empty = Some(stats),
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(
+        mut self,
+        partition_values: &IndexMap<String, Scalar>,
+        num_indexed_cols: DataSkippingNumIndexedCols,
+        stats_columns: &Option<Vec<impl AsRef<str>>>,
+    ) -> Result<Stats, DeltaWriterError> {
+        stats_from_columns(
+            partition_values,
+            &self.schema_descriptor,
+            self.num_rows,
+            num_indexed_cols,
+            stats_columns,
+            |idx, _| self.columns[idx].take(),
+        )
+    }
+}
+
+/// Find the array of a leaf column in a batch, with the nulls of its parent structs applied
+fn leaf_array(batch: &RecordBatch, path: &[String]) -> Result<Option<ArrayRef>, DeltaWriterError> {
+    let Some((name, rest)) = path.split_first() else {
+        return Ok(None);
+    };
+    let Some(mut array) = batch.column_by_name(name).cloned() else {
+        return Ok(None);
+    };
+    for name in rest {
+        let Some(parent) = array.as_struct_opt() else {
+            return Ok(None);
+        };
+        let Some(child) = parent.column_by_name(name) else {
+            return Ok(None);
+        };
+        let nulls = NullBuffer::union(parent.nulls(), child.logical_nulls().as_ref());
+        array = make_array(child.to_data().into_builder().nulls(nulls).build()?);
+    }
+    Ok(Some(array))
+}
+
+/// Whether a column holds binary data, for which no min / max statistics are collected
+fn is_binary_column(column_descr: &ColumnDescriptor) -> bool {
+    matches!(column_descr.physical_type(), Type::BYTE_ARRAY)
+        && matches!(column_descr.logical_type_ref(), Some(LogicalType::String)).not()
+}
+
+fn stats_from_columns(
+    partition_values: &IndexMap<String, Scalar>,
+    schema_descriptor: &SchemaDescriptor,
+    num_rows: i64,
+    num_indexed_cols: DataSkippingNumIndexedCols,
+    stats_columns: &Option<Vec<impl AsRef<str>>>,
+    mut column_stats: impl FnMut(usize, &ColumnDescriptor) -> Option<AggregatedStats>,
 ) -> Result<Stats, DeltaWriterError> {
     let mut min_values: HashMap<String, ColumnValueStat> = HashMap::new();
     let mut max_values: HashMap<String, ColumnValueStat> = HashMap::new();
     let mut null_count: HashMap<String, ColumnCountStat> = HashMap::new();
-    let dialect = sqlparser::dialect::GenericDialect {};
 
     let idx_to_iterate = if let Some(stats_cols) = stats_columns {
-        let stats_cols = stats_cols
-            .iter()
-            .map(|v| {
-                match sqlparser::parser::Parser::new(&dialect)
-                    .try_with_sql(v.as_ref())
-                    .map_err(|e| DeltaTableError::generic(e.to_string()))?
-                    .parse_multipart_identifier()
-                {
-                    Ok(parts) => Ok(parts.into_iter().map(|v| v.value).join(".")),
-                    Err(e) => Err(DeltaWriterError::DeltaTable(
-                        DeltaTableError::GenericError {
-                            source: Box::new(e),
-                        },
-                    )),
-                }
-            })
-            .collect::<Result<Vec<String>, DeltaWriterError>>()?;
+        let stats_cols = parse_stats_columns(stats_cols)?;
 
+        // A configured column selects all leaves below it, so nested fields can be referenced
+        // both by their full path and through any of their parent structs.
         schema_descriptor
             .columns()
             .iter()
             .enumerate()
             .filter_map(|(index, col)| {
-                if stats_cols.contains(&col.name().to_string()) {
+                let path = col.path().parts();
+                if stats_cols
+                    .iter()
+                    .any(|stats_col| path.starts_with(stats_col))
+                {
                     Some(index)
                 } else {
                     None
@@ -182,28 +334,7 @@ fn stats_from_metadata(
             continue;
         }
 
-        let maybe_stats: Option<AggregatedStats> = row_group_metadata
-            .iter()
-            .flat_map(|g| {
-                g.column(idx).statistics().into_iter().filter_map(|s| {
-                    let is_binary = matches!(&column_descr.physical_type(), Type::BYTE_ARRAY)
-                        && matches!(column_descr.logical_type_ref(), Some(LogicalType::String))
-                            .not();
-                    if is_binary {
-                        warn!(
-                            "Skipping column {} because it's a binary field.",
-                            &column_descr.name().to_string()
-                        );
-                        None
-                    } else {
-                        Some(AggregatedStats::from((s, column_descr.logical_type_ref())))
-                    }
-                })
-            })
-            .reduce(|mut left, right| {
-                left += right;
-                left
-            });
+        let maybe_stats = column_stats(idx, &column_descr);
 
         if let Some(stats) = maybe_stats {
             apply_min_max_for_column(
@@ -396,6 +527,105 @@ impl StatsScalar {
             }),
         }
     }
+
+    /// Compute the minimum or maximum of an array.
+    ///
+    /// Returns `None` if the array has no valid values or holds a type without statistics.
+    /// NaN is ignored like in the statistics written by parquet.
+    fn try_from_array(array: &ArrayRef, use_min: bool) -> Result<Option<Self>, DeltaWriterError> {
+        macro_rules! get_stat {
+            ($array: expr, $min: ident, $max: ident) => {
+                if use_min { $min($array) } else { $max($array) }
+            };
+        }
+        macro_rules! get_float_stat {
+            ($array: expr) => {
+                $array
+                    .iter()
+                    .flatten()
+                    .filter(|v| !v.is_nan())
+                    .reduce(|a, b| if use_min { a.min(b) } else { a.max(b) })
+            };
+        }
+
+        let value = match array.data_type() {
+            ArrowDataType::Boolean => {
+                get_stat!(array.as_boolean(), min_boolean, max_boolean).map(Self::Boolean)
+            }
+            ArrowDataType::Int8 | ArrowDataType::Int16 | ArrowDataType::Int32 => {
+                let array = cast(array, &ArrowDataType::Int32)?;
+                get_stat!(array.as_primitive::<Int32Type>(), arrow_min, arrow_max).map(Self::Int32)
+            }
+            ArrowDataType::Int64 => {
+                get_stat!(array.as_primitive::<Int64Type>(), arrow_min, arrow_max).map(Self::Int64)
+            }
+            ArrowDataType::Float32 => {
+                get_float_stat!(array.as_primitive::<Float32Type>()).map(Self::Float32)
+            }
+            ArrowDataType::Float64 => {
+                get_float_stat!(array.as_primitive::<Float64Type>()).map(Self::Float64)
+            }
+            ArrowDataType::Date32 => {
+                let epoch_start = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(); // creating from epoch should be infallible
+                get_stat!(array.as_primitive::<Date32Type>(), arrow_min, arrow_max)
+                    .map(|days| Self::Date(epoch_start + chrono::Duration::days(days as i64)))
+            }
+            ArrowDataType::Timestamp(unit, _) => {
+                let array = cast(array, &ArrowDataType::Int64)?;
+                let Some(v) = get_stat!(array.as_primitive::<Int64Type>(), arrow_min, arrow_max)
+                else {
+                    return Ok(None);
+                };
+                let timestamp = match unit {
+                    ArrowTimeUnit::Second => chrono::DateTime::from_timestamp(v, 0),
+                    ArrowTimeUnit::Millisecond => chrono::DateTime::from_timestamp_millis(v),
+                    ArrowTimeUnit::Microsecond => chrono::DateTime::from_timestamp_micros(v),
+                    ArrowTimeUnit::Nanosecond => Some(chrono::DateTime::from_timestamp_nanos(v)),
+                };
+                let timestamp = timestamp.ok_or(DeltaWriterError::StatsParsingFailed {
+                    debug_value: v.to_string(),
+                    logical_type: None,
+                })?;
+                Some(Self::Timestamp(timestamp.naive_utc()))
+            }
+            ArrowDataType::Decimal128(_, scale) => {
+                get_stat!(array.as_primitive::<Decimal128Type>(), arrow_min, arrow_max).map(|v| {
+                    // Spark serializes these as numbers
+                    Self::Decimal {
+                        value: v as f64 / 10.0_f64.powi(*scale as i32),
+                        scale: *scale as i32,
+                    }
+                })
+            }
+            ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => {
+                let array = cast(array, &ArrowDataType::Utf8)?;
+                get_stat!(array.as_string::<i32>(), min_string, max_string)
+                    .map(|v| Self::String(v.to_string()))
+            }
+            ArrowDataType::Dictionary(_, value_type) => {
+                return Self::try_from_array(&cast(array, value_type)?, use_min);
+            }
+            _ => None,
+        };
+        Ok(value)
+    }
+
+    /// Truncate long strings to [`STATS_STRING_PREFIX_LENGTH`] characters.
+    ///
+    /// A truncated minimum is still a lower bound for the column. A truncated maximum gets the
+    /// largest unicode code point appended so it remains an upper bound.
+    fn truncate(self, use_min: bool) -> Self {
+        match self {
+            Self::String(value) if value.chars().count() > STATS_STRING_PREFIX_LENGTH => {
+                let mut prefix: String = value.chars().take(STATS_STRING_PREFIX_LENGTH).collect();
+                if !use_min {
+                    prefix.push(char::MAX);
+                }
+                Self::String(prefix)
+            }
+            other => other,
+        }
+    }
 }
 
 /// Performs big endian sign extension
@@ -565,12 +795,12 @@ fn apply_min_max_for_column(
             let key = column_descr.name().to_string();
 
             if let Some(min) = statistics.min {
-                let min = ColumnValueStat::Value(min.into());
+                let min = ColumnValueStat::Value(min.truncate(true).into());
                 min_values.insert(key.clone(), min);
             }
 
             if let Some(max) = statistics.max {
-                let max = ColumnValueStat::Value(max.into());
+                let max = ColumnValueStat::Value(max.truncate(false).into());
                 max_values.insert(key.clone(), max);
             }
 
@@ -984,6 +1214,130 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_nested_stats_columns() {
+        use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray, StructArray};
+        use arrow_schema::{DataType, Field, Schema as ArrowSchema};
+        use parquet::arrow::ArrowWriter;
+
+        let nested_fields = vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ];
+        let nested = StructArray::new(
+            nested_fields.clone().into(),
+            vec![
+                Arc::new(Int32Array::from(vec![10, 20])) as ArrayRef,
+                Arc::new(StringArray::from(vec!["a".repeat(40), "b".repeat(40)])) as ArrayRef,
+            ],
+            None,
+        );
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("meta", DataType::Struct(nested_fields.into()), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2])), Arc::new(nested)],
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        let metadata = writer.close().unwrap();
+
+        let stats_for = |columns: &[&str]| {
+            let columns = Some(columns.iter().map(|c| c.to_string()).collect());
+            stats_from_parquet_metadata(
+                &IndexMap::new(),
+                &metadata,
+                DataSkippingNumIndexedCols::AllColumns,
+                &columns,
+            )
+            .unwrap()
+        };
+
+        // a struct column selects all of its children, but not same named top level columns
+        let stats = stats_for(&["meta"]);
+        assert_eq!(
+            serde_json::to_value(&stats.min_values).unwrap(),
+            json!({"meta": {"id": 10, "name": "a".repeat(32)}})
+        );
+        let max_name = format!("{}{}", "b".repeat(32), char::MAX);
+        assert_eq!(
+            serde_json::to_value(&stats.max_values).unwrap(),
+            json!({"meta": {"id": 20, "name": max_name}})
+        );
+
+        let stats = stats_for(&["meta.id"]);
+        assert_eq!(
+            serde_json::to_value(&stats.null_count).unwrap(),
+            json!({"meta": {"id": 0}})
+        );
+
+        let stats = stats_for(&["id"]);
+        assert_eq!(
+            serde_json::to_value(&stats.min_values).unwrap(),
+            json!({"id": 1})
+        );
+    }
+
+    #[test]
+    fn test_record_batch_stats_match_footer_stats() {
+        use arrow_array::{Float64Array, Int32Array, Int64Array, StringArray, StructArray};
+        use arrow_schema::Field;
+        use parquet::arrow::ArrowWriter;
+
+        let nested = StructArray::new(
+            vec![Field::new("id", ArrowDataType::Int32, true)].into(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef],
+            Some(NullBuffer::from(vec![true, false, true])),
+        );
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int64Array::from(vec![Some(3), None, Some(1)])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![Some("b"), Some("a"), None])) as ArrayRef,
+            ),
+            (
+                "value",
+                Arc::new(Float64Array::from(vec![1.5, f64::NAN, -2.0])) as ArrayRef,
+            ),
+            ("nested", Arc::new(nested) as ArrayRef),
+        ])
+        .unwrap();
+
+        let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        let metadata = writer.close().unwrap();
+        let expected = stats_from_parquet_metadata(
+            &IndexMap::new(),
+            &metadata,
+            DataSkippingNumIndexedCols::AllColumns,
+            &None,
+        )
+        .unwrap();
+
+        let mut stats = RecordBatchStats::new(metadata.file_metadata().schema_descr_ptr());
+        stats.update(&batch.slice(0, 2)).unwrap();
+        stats.update(&batch.slice(2, 1)).unwrap();
+        let stats = stats
+            .finish(
+                &IndexMap::new(),
+                DataSkippingNumIndexedCols::AllColumns,
+                &None::<Vec<String>>,
+            )
+            .unwrap();
+
+        assert_eq!(stats, expected);
+        assert_eq!(
+            serde_json::to_value(&stats.null_count).unwrap(),
+            json!({"id": 1, "name": 1, "value": 0, "nested": {"id": 1}})
+        );
+    }
+
     async fn load_table(
         table_url: &Url,
         options: HashMap<String, String>,
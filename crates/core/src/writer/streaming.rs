@@ -0,0 +1,403 @@
+//! Exactly-once streaming writes into a Delta table
+//!
+//! A [`StreamingSink`] buffers record batches read from a source such as a Kafka or Kinesis
+//! partition and commits them together with an application transaction (`txn` action) recording
+//! the offset of the last committed batch of every source. On restart the offsets are read back
+//! from the table, and batches at or before a committed offset are skipped, so a source replaying
+//! from its last acknowledged position never produces duplicates.
+//!
+//! ```ignore
+//! let mut sink = StreamingSink::try_new(table, "my-query")?
+//!     .with_max_buffer_bytes(64 * 1024 * 1024)
+//!     .with_checkpoint_interval(10);
+//! let start = sink.committed_offset("orders-0").await?.map_or(0, |offset| offset + 1);
+//! for (offset, batch) in consumer.read_from(start) {
+//!     sink.write("orders-0", offset, batch).await?;
+//! }
+//! sink.flush().await?;
+//! ```
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use arrow_array::RecordBatch;
+use tracing::*;
+
+use super::{DeltaWriter, RecordBatchWriter};
+use crate::DeltaTable;
+use crate::errors::DeltaResult;
+use crate::kernel::transaction::{CommitBuilder, CommitProperties};
+use crate::kernel::{Action, Transaction};
+use crate::protocol::{DeltaOperation, OutputMode};
+
+/// Default size of the buffered parquet data which triggers a commit
+pub const DEFAULT_MAX_BUFFER_BYTES: usize = 128 * 1024 * 1024;
+
+/// Default age of the oldest buffered batch which triggers a commit
+pub const DEFAULT_MAX_BUFFER_DURATION: Duration = Duration::from_secs(60);
+
+/// Writes batches from offset-addressable sources into a Delta table exactly once.
+///
+/// Each batch is tagged with the id of its source (the application id of the `txn` action,
+/// typically one per topic partition) and an offset, which must increase for every new batch of a
+/// source, e.g. the offset of the last record of the batch.
+pub struct StreamingSink {
+    table: DeltaTable,
+    writer: RecordBatchWriter,
+    query_id: String,
+    commit_properties: CommitProperties,
+    max_buffer_bytes: usize,
+    max_buffer_duration: Duration,
+    checkpoint_interval: Option<u64>,
+    #[cfg(feature = "datafusion")]
+    compaction_interval: Option<u64>,
+    /// Offsets recorded in the table, loaded on first use of a source
+    committed_offsets: HashMap<String, i64>,
+    /// Offsets of the buffered batches, committed on the next flush
+    pending_offsets: HashMap<String, i64>,
+    /// When the first batch since the last commit was buffered
+    buffered_since: Option<Instant>,
+    /// Number of commits of this sink, used for the checkpoint and compaction intervals
+    num_commits: u64,
+}
+
+impl std::fmt::Debug for StreamingSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingSink")
+            .field("table", &self.table.table_url())
+            .field("query_id", &self.query_id)
+            .field("pending_offsets", &self.pending_offsets)
+            .finish()
+    }
+}
+
+impl StreamingSink {
+    /// Create a sink writing to `table`, identified by `query_id` in the commit info.
+    pub fn try_new(table: DeltaTable, query_id: impl ToString) -> DeltaResult<Self> {
+        let writer = RecordBatchWriter::for_table(&table)?;
+        Ok(Self {
+            table,
+            writer,
+            query_id: query_id.to_string(),
+            commit_properties: CommitProperties::default(),
+            max_buffer_bytes: DEFAULT_MAX_BUFFER_BYTES,
+            max_buffer_duration: DEFAULT_MAX_BUFFER_DURATION,
+            checkpoint_interval: None,
+            #[cfg(feature = "datafusion")]
+            compaction_interval: None,
+            committed_offsets: HashMap::new(),
+            pending_offsets: HashMap::new(),
+            buffered_since: None,
+            num_commits: 0,
+        })
+    }
+
+    /// Commit once the buffered parquet data reaches this size.
+    pub fn with_max_buffer_bytes(mut self, max_buffer_bytes: usize) -> Self {
+        self.max_buffer_bytes = max_buffer_bytes;
+        self
+    }
+
+    /// Commit once the oldest buffered batch is this old.
+    ///
+    /// The age is checked on every write and by [`StreamingSink::flush_if_due`], which should be
+    /// called periodically when the source may be idle.
+    pub fn with_max_buffer_duration(mut self, max_buffer_duration: Duration) -> Self {
+        self.max_buffer_duration = max_buffer_duration;
+        self
+    }
+
+    /// Additional [`CommitProperties`] for the commits of the sink, e.g. application metadata.
+    pub fn with_commit_properties(mut self, commit_properties: CommitProperties) -> Self {
+        self.commit_properties = commit_properties;
+        self
+    }
+
+    /// Use a custom writer, e.g. one with a dead letter policy or custom writer properties.
+    pub fn with_writer(mut self, writer: RecordBatchWriter) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    /// Create a checkpoint after every `interval` commits of the sink, instead of following the
+    /// `delta.checkpointInterval` of the table.
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = Some(interval.max(1));
+        self
+    }
+
+    /// Compact the small files written by the sink after every `interval` commits of the sink.
+    #[cfg(feature = "datafusion")]
+    pub fn with_compaction_interval(mut self, interval: u64) -> Self {
+        self.compaction_interval = Some(interval.max(1));
+        self
+    }
+
+    /// The table written to, at the version of the last commit of the sink
+    pub fn table(&self) -> &DeltaTable {
+        &self.table
+    }
+
+    /// Offset of the last committed batch of `app_id`, `None` if nothing was committed yet.
+    ///
+    /// A restarted source should resume right after this offset.
+    pub async fn committed_offset(&mut self, app_id: &str) -> DeltaResult<Option<i64>> {
+        if let Some(offset) = self.committed_offsets.get(app_id) {
+            return Ok(Some(*offset));
+        }
+        let offset = self
+            .table
+            .snapshot()?
+            .transaction_version(self.table.log_store().as_ref(), app_id)
+            .await?;
+        if let Some(offset) = offset {
+            self.committed_offsets.insert(app_id.to_string(), offset);
+        }
+        Ok(offset)
+    }
+
+    /// Buffer the batch of `app_id` ending at `offset`, committing if the buffer is full or old
+    /// enough.
+    ///
+    /// Batches at or before the last committed or buffered offset of `app_id` are skipped.
+    /// Returns the version of the table if a commit happened.
+    pub async fn write(
+        &mut self,
+        app_id: &str,
+        offset: i64,
+        batch: RecordBatch,
+    ) -> DeltaResult<Option<i64>> {
+        let last_offset = match self.pending_offsets.get(app_id) {
+            Some(pending) => Some(*pending),
+            None => self.committed_offset(app_id).await?,
+        };
+        if last_offset.is_some_and(|last_offset| offset <= last_offset) {
+            debug!("Skipping batch of {app_id} at offset {offset}, already written");
+            return self.flush_if_due().await;
+        }
+
+        self.writer.write(batch).await?;
+        self.pending_offsets.insert(app_id.to_string(), offset);
+        self.buffered_since.get_or_insert_with(Instant::now);
+
+        if self.writer.buffer_len() >= self.max_buffer_bytes {
+            return self.flush().await;
+        }
+        self.flush_if_due().await
+    }
+
+    /// Commit if the oldest buffered batch is older than the maximum buffer duration.
+    pub async fn flush_if_due(&mut self) -> DeltaResult<Option<i64>> {
+        match self.buffered_since {
+            Some(since) if since.elapsed() >= self.max_buffer_duration => self.flush().await,
+            _ => Ok(None),
+        }
+    }
+
+    /// Commit the buffered batches and their offsets, returning the new version of the table, or
+    /// `None` if nothing was buffered.
+    ///
+    /// When the commit fails the buffered batches are dropped, and the sources must resume after
+    /// their [`StreamingSink::committed_offset`]. Checkpoints and compactions after a successful
+    /// commit do not fail the flush, their errors are logged.
+    pub async fn flush(&mut self) -> DeltaResult<Option<i64>> {
+        self.buffered_since = None;
+        if self.pending_offsets.is_empty() {
+            return Ok(None);
+        }
+        let pending_offsets = std::mem::take(&mut self.pending_offsets);
+        let result = self.commit(&pending_offsets).await;
+        match &result {
+            Ok(_) => self.committed_offsets.extend(pending_offsets),
+            // the table may be ahead of what the sink knows, read the offsets again
+            Err(_) => self.committed_offsets.clear(),
+        }
+        let version = result?;
+        self.num_commits += 1;
+        self.run_maintenance().await;
+        Ok(Some(version))
+    }
+
+    async fn commit(&mut self, offsets: &HashMap<String, i64>) -> DeltaResult<i64> {
+        let actions: Vec<Action> = self
+            .writer
+            .flush()
+            .await?
+            .into_iter()
+            .map(Action::Add)
+            .collect();
+
+        let last_updated = chrono::Utc::now().timestamp_millis();
+        let mut commit_properties = self.commit_properties.clone();
+        for (app_id, offset) in offsets {
+            commit_properties = commit_properties.with_application_transaction(
+                Transaction::new_with_last_update(app_id, *offset, Some(last_updated)),
+            );
+        }
+        if self.checkpoint_interval.is_some() {
+            commit_properties = commit_properties.with_create_checkpoint(false);
+        }
+
+        // like the batch id of a Spark streaming query, the epoch is the committed txn version,
+        // so it keeps increasing across restarts of the sink
+        let operation = DeltaOperation::StreamingUpdate {
+            output_mode: OutputMode::Append,
+            query_id: self.query_id.clone(),
+            epoch_id: offsets.values().copied().max().unwrap_or_default(),
+        };
+        let snapshot = self.table.snapshot()?;
+        let finalized = CommitBuilder::from(commit_properties)
+            .with_actions(actions)
+            .build(Some(snapshot), self.table.log_store(), operation)
+            .await?;
+        self.table.state = Some(finalized.snapshot());
+        info!(
+            "Streaming update {} committed version {} with offsets {offsets:?}",
+            self.query_id,
+            finalized.version()
+        );
+        Ok(finalized.version())
+    }
+
+    /// Create checkpoints and compact files according to the configured intervals
+    async fn run_maintenance(&mut self) {
+        if self
+            .checkpoint_interval
+            .is_some_and(|interval| self.num_commits.is_multiple_of(interval))
+            && let Err(err) =
+                crate::protocol::checkpoints::create_checkpoint(&self.table, None).await
+        {
+            warn!("Failed to create checkpoint after streaming update: {err}");
+        }
+
+        #[cfg(feature = "datafusion")]
+        if self
+            .compaction_interval
+            .is_some_and(|interval| self.num_commits.is_multiple_of(interval))
+        {
+            match self.table.clone().optimize().await {
+                Ok((table, metrics)) => {
+                    info!(
+                        "Compacted {} files into {} files",
+                        metrics.num_files_removed, metrics.num_files_added
+                    );
+                    self.table = table;
+                }
+                Err(err) => warn!("Failed to compact files after streaming update: {err}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::writer::test_utils::{create_initialized_table, get_record_batch};
+
+    /// An in-memory source of `(offset, batch)` pairs
+    fn source(num_batches: i64) -> Vec<(i64, RecordBatch)> {
+        (0..num_batches)
+            .map(|offset| (offset, get_record_batch(None, false)))
+            .collect()
+    }
+
+    fn num_files(sink: &StreamingSink) -> usize {
+        sink.table().snapshot().unwrap().log_data().num_files()
+    }
+
+    #[tokio::test]
+    async fn test_commit_offsets() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let table = create_initialized_table(table_dir.path().to_str().unwrap(), &[]).await;
+        let mut sink = StreamingSink::try_new(table, "test-query").unwrap();
+
+        assert_eq!(sink.committed_offset("source-0").await.unwrap(), None);
+        for (offset, batch) in source(3) {
+            assert_eq!(sink.write("source-0", offset, batch).await.unwrap(), None);
+        }
+        assert_eq!(sink.flush().await.unwrap(), Some(1));
+        assert_eq!(sink.committed_offset("source-0").await.unwrap(), Some(2));
+
+        let commit = sink.table().last_commit().await.unwrap();
+        assert_eq!(commit.operation.as_deref(), Some("STREAMING UPDATE"));
+        let parameters = commit.operation_parameters.unwrap();
+        assert_eq!(parameters["epochId"], serde_json::json!("2"));
+
+        // nothing buffered, nothing committed
+        assert_eq!(sink.flush().await.unwrap(), None);
+        assert_eq!(sink.table().version(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_skip_committed_offsets_on_restart() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let table = create_initialized_table(table_dir.path().to_str().unwrap(), &[]).await;
+        let mut sink = StreamingSink::try_new(table.clone(), "test-query").unwrap();
+        for (offset, batch) in source(2) {
+            sink.write("source-0", offset, batch).await.unwrap();
+        }
+        sink.flush().await.unwrap();
+        assert_eq!(num_files(&sink), 1);
+
+        // a restarted sink is handed the whole source again
+        let mut table = table;
+        table.update_state().await.unwrap();
+        let mut sink = StreamingSink::try_new(table, "test-query").unwrap();
+        assert_eq!(sink.committed_offset("source-0").await.unwrap(), Some(1));
+        for (offset, batch) in source(4) {
+            sink.write("source-0", offset, batch).await.unwrap();
+        }
+        // batches of other sources are not affected
+        sink.write("source-1", 0, get_record_batch(None, false))
+            .await
+            .unwrap();
+        assert_eq!(sink.flush().await.unwrap(), Some(2));
+        assert_eq!(num_files(&sink), 2);
+        assert_eq!(sink.committed_offset("source-0").await.unwrap(), Some(3));
+        assert_eq!(sink.committed_offset("source-1").await.unwrap(), Some(0));
+
+        let table = crate::open_table(sink.table().table_url().clone())
+            .await
+            .unwrap();
+        let snapshot = table.snapshot().unwrap();
+        let version = snapshot
+            .transaction_version(table.log_store().as_ref(), "source-0")
+            .await
+            .unwrap();
+        assert_eq!(version, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_flush_on_buffer_limits() {
+        let table_dir = tempfile::tempdir().unwrap();
+        let table = create_initialized_table(table_dir.path().to_str().unwrap(), &[]).await;
+        let mut sink = StreamingSink::try_new(table, "test-query")
+            .unwrap()
+            .with_max_buffer_bytes(1)
+            .with_checkpoint_interval(2);
+
+        for (offset, batch) in source(2) {
+            let version = sink.write("source-0", offset, batch).await.unwrap();
+            assert_eq!(version, Some(offset + 1));
+        }
+        let checkpoint = sink
+            .table()
+            .log_store()
+            .object_store(None)
+            .head(&object_store::path::Path::from(
+                "_delta_log/00000000000000000002.checkpoint.parquet",
+            ))
+            .await;
+        assert!(checkpoint.is_ok());
+
+        let mut sink = sink
+            .with_max_buffer_bytes(usize::MAX)
+            .with_max_buffer_duration(Duration::ZERO);
+        assert_eq!(sink.flush_if_due().await.unwrap(), None);
+        let version = sink
+            .write("source-0", 2, get_record_batch(None, false))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(3));
+    }
+}
@@ -67,6 +67,19 @@ pub fn get_arrow_schema(part: &Option<String>) -> Arc<ArrowSchema> {
     }
 }
 
+/// A batch matching [`get_arrow_schema`] with one `(id, value, modified)` tuple per row
+pub fn get_record_batch_from_rows(rows: &[(&str, i32, &str)]) -> RecordBatch {
+    RecordBatch::try_new(
+        get_arrow_schema(&None),
+        vec![
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|row| row.0))),
+            Arc::new(Int32Array::from_iter_values(rows.iter().map(|row| row.1))),
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|row| row.2))),
+        ],
+    )
+    .unwrap()
+}
+
 fn data_with_null() -> (Int32Array, StringArray, StringArray) {
     let base_int = Int32Array::from(vec![
         Some(1),
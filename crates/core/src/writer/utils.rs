@@ -1,6 +1,7 @@
 //! Handle JSON messages when writing to delta tables
 //!
 
+use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Arc;
 
@@ -16,8 +17,22 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use crate::errors::DeltaResult;
+use crate::table::config::parse_parquet_writer_settings;
 use crate::writer::DeltaWriterError;
 
+/// Default writer properties of the writers in this module for a table with `configuration`,
+/// including the parquet writer settings persisted in it.
+pub(crate) fn table_writer_properties(configuration: &HashMap<String, String>) -> WriterProperties {
+    let builder = WriterProperties::builder().set_compression(Compression::SNAPPY);
+    parse_parquet_writer_settings(
+        configuration
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str())),
+    )
+    .apply(builder)
+    .build()
+}
+
 /// Generate the name of the file to be written
 /// prefix: The location of the file to be written
 /// part_count: Used the indicate that single logical partition was split into multiple physical files
@@ -0,0 +1,70 @@
+use deltalake_core::errors::DeltaTableError;
+use deltalake_test::utils::*;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_compute_stats_local() -> TestResult {
+    let storage = Box::<LocalStorageIntegration>::default();
+    let context = IntegrationContext::new(storage)?;
+    context.load_table(TestTables::Simple).await?;
+
+    let table = context.table_builder(TestTables::Simple).load().await?;
+    let version = table.snapshot()?.version();
+    let active = table.snapshot()?.log_data().num_files();
+    assert!(
+        table
+            .snapshot()?
+            .log_data()
+            .iter()
+            .all(|file| file.num_records().is_none())
+    );
+
+    // All files of the table were written without statistics
+    let (table, metrics) = table.compute_stats().await?;
+    assert_eq!(version + 1, table.snapshot()?.version());
+    assert_eq!(active, table.snapshot()?.log_data().num_files());
+    assert_eq!(active as u64, metrics.num_files_updated);
+    assert_eq!(
+        metrics.num_files_updated,
+        metrics.num_files_from_footer + metrics.num_files_scanned
+    );
+
+    assert!(
+        table
+            .snapshot()?
+            .log_data()
+            .iter()
+            .all(|file| file.num_records().is_some())
+    );
+
+    let commit_info: Vec<_> = table.history(Some(1)).await?.collect();
+    assert_eq!(commit_info[0].operation, Some("COMPUTE STATS".into()));
+
+    // An additional run has nothing left to update
+    let (table, metrics) = table.compute_stats().await?;
+    assert_eq!(version + 1, table.snapshot()?.version());
+    assert_eq!(0, metrics.num_files_updated);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_compute_stats_fails_for_concurrent_delete() -> TestResult {
+    // Files re-added from an outdated snapshot were removed by later commits
+    let storage = Box::<LocalStorageIntegration>::default();
+    let context = IntegrationContext::new(storage)?;
+    context.load_table(TestTables::Simple).await?;
+
+    let table = context
+        .table_builder(TestTables::Simple)
+        .with_version(2)
+        .load()
+        .await?;
+
+    let res = table.compute_stats().await;
+    assert!(matches!(res, Err(DeltaTableError::Transaction { .. })));
+
+    Ok(())
+}
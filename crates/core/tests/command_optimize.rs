@@ -14,9 +14,9 @@ use deltalake_core::logstore::ObjectStoreRef;
 use deltalake_core::operations::optimize::{
     MetricDetails, Metrics, OptimizeType, create_merge_plan,
 };
-use deltalake_core::protocol::DeltaOperation;
+use deltalake_core::protocol::{DeltaOperation, SaveMode};
 use deltalake_core::writer::{DeltaWriter, RecordBatchWriter};
-use deltalake_core::{DeltaTable, PartitionFilter, Path};
+use deltalake_core::{DeltaTable, DeltaTableBuilder, PartitionFilter, Path, TableProperty};
 use futures::TryStreamExt;
 use object_store::ObjectStore;
 use parquet::arrow::ParquetRecordBatchStreamBuilder;
@@ -267,6 +267,117 @@ async fn test_optimize_with_partitions() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_optimize_with_partitions_without_files() -> Result<(), Box<dyn Error>> {
+    let context = setup_test(true).await?;
+    let mut dt = context.table;
+    let mut writer = RecordBatchWriter::for_table(&dt)?;
+
+    for (x, partition) in [(1, "2022-05-22"), (2, "2022-05-23"), (3, "2022-05-22")] {
+        write(
+            &mut writer,
+            &mut dt,
+            tuples_to_batch(vec![(x, 1), (x, 2)], partition)?,
+        )
+        .await?;
+    }
+
+    let version = dt.version().unwrap();
+    let dt = DeltaTableBuilder::from_url(dt.table_url().clone())?
+        .without_files()
+        .load()
+        .await?;
+    let filter = vec![PartitionFilter::try_from(("date", "=", "2022-05-22"))?];
+    let (dt, metrics) = dt.optimize().with_filters(&filter).await?;
+
+    assert_eq!(version + 1, dt.version().unwrap());
+    assert!(!dt.config.require_files);
+    assert_eq!(metrics.num_files_added, 1);
+    assert_eq!(metrics.num_files_removed, 2);
+    assert_eq!(metrics.total_considered_files, 2);
+
+    let partition_adds = dt
+        .get_active_add_actions_by_partitions(&filter)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(partition_adds.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auto_compact_post_commit_hook() -> Result<(), Box<dyn Error>> {
+    let context = setup_test(true).await?;
+    let mut dt = context
+        .table
+        .set_tbl_properties()
+        .with_properties(
+            [
+                (TableProperty::AutoOptimizeAutoCompact, "true"),
+                (TableProperty::AutoOptimizeAutoCompactMinNumFiles, "3"),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value.to_string()))
+            .collect(),
+        )
+        .await?;
+    let mut writer = RecordBatchWriter::for_table(&dt)?;
+
+    write(
+        &mut writer,
+        &mut dt,
+        tuples_to_batch(vec![(1, 2), (1, 3), (1, 4)], "2022-05-22")?,
+    )
+    .await?;
+    write(
+        &mut writer,
+        &mut dt,
+        tuples_to_batch(vec![(2, 1), (2, 3), (2, 3)], "2022-05-23")?,
+    )
+    .await?;
+    write(
+        &mut writer,
+        &mut dt,
+        tuples_to_batch(vec![(3, 1), (3, 3), (3, 3)], "2022-05-22")?,
+    )
+    .await?;
+    // below the threshold of small files per partition
+    assert_eq!(dt.snapshot()?.log_data().num_files(), 3);
+    let version = dt.version().unwrap();
+
+    writer
+        .write(tuples_to_batch(vec![(4, 1), (4, 3), (4, 3)], "2022-05-22")?)
+        .await?;
+    let adds = writer.flush().await?;
+    let operation = DeltaOperation::Write {
+        mode: SaveMode::Append,
+        partition_by: None,
+        predicate: None,
+    };
+    let commit = CommitBuilder::from(CommitProperties::default())
+        .with_actions(adds.into_iter().map(Action::Add).collect())
+        .build(Some(dt.snapshot()?), dt.log_store(), operation)
+        .await?;
+
+    assert_eq!(commit.version(), version + 1);
+    assert_eq!(commit.metrics.num_auto_compact_files_added, 1);
+    assert_eq!(commit.metrics.num_auto_compact_files_removed, 3);
+    assert_eq!(commit.snapshot().version(), version + 2);
+    assert_eq!(commit.snapshot().log_data().num_files(), 2);
+
+    dt.update_state().await?;
+    let commit_info: Vec<_> = dt.history(Some(1)).await?.collect();
+    assert_eq!(commit_info[0].operation.as_deref(), Some("OPTIMIZE"));
+    let filter = vec![PartitionFilter::try_from(("date", "=", "2022-05-23"))?];
+    let partition_adds = dt
+        .get_active_add_actions_by_partitions(&filter)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(partition_adds.len(), 1);
+
+    Ok(())
+}
+
 #[tokio::test]
 /// Validate that optimize fails when a remove action occurs
 async fn test_conflict_for_remove_actions() -> Result<(), Box<dyn Error>> {
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use deltalake_core::logstore::object_store::ObjectStoreScheme;
 use deltalake_core::logstore::object_store::gcp::{GoogleCloudStorageBuilder, GoogleConfigKey};
-use deltalake_core::logstore::{LogStore, LogStoreFactory, default_logstore, logstore_factories};
+use deltalake_core::logstore::{LogStore, LogStoreFactory, logstore_factories};
 use deltalake_core::logstore::{
     ObjectStoreFactory, ObjectStoreRef, StorageConfig, object_store_factories,
 };
@@ -14,8 +14,11 @@ use url::Url;
 
 mod config;
 pub mod error;
+pub mod logstore;
 mod storage;
 
+pub use logstore::GcsLogStore;
+
 trait GcpOptions {
     fn as_gcp_options(&self) -> HashMap<GoogleConfigKey, String>;
 }
@@ -75,7 +78,7 @@ impl LogStoreFactory for GcpFactory {
         location: &Url,
         options: &StorageConfig,
     ) -> DeltaResult<Arc<dyn LogStore>> {
-        Ok(default_logstore(
+        Ok(logstore::gcs_logstore(
             prefixed_store,
             root_store,
             location,
@@ -110,6 +113,6 @@ mod tests {
         let logstore = factory
             .with_options(prefixed, store, &location, &StorageConfig::default())
             .unwrap();
-        assert_eq!(logstore.name(), "DefaultLogStore");
+        assert_eq!(logstore.name(), "GcsLogStore");
     }
 }
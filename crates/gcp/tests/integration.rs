@@ -1,8 +1,15 @@
 #![cfg(feature = "integration_test")]
 
+use bytes::Bytes;
+use deltalake_core::DeltaTableBuilder;
+use deltalake_core::kernel::transaction::TransactionError;
+use deltalake_core::logstore::CommitOrBytes;
 use deltalake_test::read::read_table_paths;
+use deltalake_test::utils::TestTables;
 use deltalake_test::{IntegrationContext, TestResult, test_concurrent_writes, test_read_tables};
 use serial_test::serial;
+use url::Url;
+use uuid::Uuid;
 
 mod context;
 use context::*;
@@ -36,3 +43,28 @@ async fn test_concurrency_gcp() -> TestResult {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+#[ignore = "The GCP tests currently hang"]
+async fn test_conflicting_commit_gcp() -> TestResult {
+    let context = IntegrationContext::new(Box::new(GcpIntegration::default()))?;
+    let table_uri = context.uri_for_table(TestTables::Custom("conflicting_commit".into()));
+    let log_store = DeltaTableBuilder::from_url(Url::parse(&table_uri)?)?
+        .with_allow_http(true)
+        .build_storage()?;
+    assert_eq!(log_store.name(), "GcsLogStore");
+
+    let commit = || CommitOrBytes::LogBytes(Bytes::from_static(b"{}"));
+    log_store
+        .write_commit_entry(0, commit(), Uuid::new_v4())
+        .await?;
+    let result = log_store
+        .write_commit_entry(0, commit(), Uuid::new_v4())
+        .await;
+    assert!(matches!(
+        result,
+        Err(TransactionError::VersionAlreadyExists(0))
+    ));
+    Ok(())
+}
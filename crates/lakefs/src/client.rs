@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use dashmap::DashMap;
 use deltalake_core::DeltaResult;
 use deltalake_core::kernel::transaction::TransactionError;
@@ -9,7 +11,7 @@ use tracing::debug;
 use url::Url;
 use uuid::Uuid;
 
-use crate::errors::LakeFSOperationError;
+use crate::errors::{LakeFSConfigError, LakeFSOperationError};
 
 #[derive(Debug, Clone)]
 pub struct LakeFSConfig {
@@ -26,6 +28,24 @@ impl LakeFSConfig {
             password,
         }
     }
+
+    /// Build the configuration from S3 style storage options, i.e. the options after they
+    /// have been normalized into [`AmazonS3ConfigKey`](object_store::aws::AmazonS3ConfigKey) names.
+    pub fn try_from_options(options: &HashMap<String, String>) -> Result<Self, LakeFSConfigError> {
+        let host = options
+            .get("aws_endpoint")
+            .ok_or(LakeFSConfigError::EndpointMissing)?
+            .to_string();
+        let username = options
+            .get("aws_access_key_id")
+            .ok_or(LakeFSConfigError::UsernameCredentialMissing)?
+            .to_string();
+        let password = options
+            .get("aws_secret_access_key")
+            .ok_or(LakeFSConfigError::PasswordCredentialMissing)?
+            .to_string();
+        Ok(Self::new(host, username, password))
+    }
 }
 
 /// Slim LakeFS client for lakefs branch operations.
@@ -305,12 +325,80 @@ impl LakeFSClient {
         debug!("{}", format!("LakeFS Transaction `{id}` has been removed."));
     }
 
+    /// List all tags of a repository.
+    pub async fn list_tags(&self, repo: &str) -> DeltaResult<Vec<LakeFSTag>> {
+        let request_url = format!("{}/api/v1/repositories/{repo}/tags", self.config.host);
+
+        #[derive(Deserialize, Debug)]
+        struct Pagination {
+            has_more: bool,
+            next_offset: String,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct TagsResponse {
+            pagination: Pagination,
+            results: Vec<LakeFSTag>,
+        }
+
+        let mut tags = Vec::new();
+        let mut after = String::new();
+        loop {
+            debug!("Listing LakeFS tags in repo: {repo} after `{after}`");
+            let response = self
+                .http_client
+                .get(&request_url)
+                .query(&[("after", after.as_str()), ("amount", "1000")])
+                .basic_auth(&self.config.username, Some(&self.config.password))
+                .send()
+                .await
+                .map_err(|e| LakeFSOperationError::HttpRequestFailed { source: e })?;
+
+            match response.status() {
+                StatusCode::OK => {
+                    let page: TagsResponse = response
+                        .json()
+                        .await
+                        .map_err(|e| LakeFSOperationError::HttpRequestFailed { source: e })?;
+                    tags.extend(page.results);
+                    if !page.pagination.has_more {
+                        return Ok(tags);
+                    }
+                    after = page.pagination.next_offset;
+                }
+                StatusCode::UNAUTHORIZED => {
+                    return Err(LakeFSOperationError::UnauthorizedAction.into());
+                }
+                status_code => {
+                    let body = response.text().await.unwrap_or_default();
+
+                    let error = LakeFSErrorResponse {
+                        message: format!(
+                            "Unknown error occurred while listing tags. Response code was {}, body: {}",
+                            status_code, body,
+                        )
+                        .to_string(),
+                    };
+                    return Err(LakeFSOperationError::ListTagsFailed(error.message).into());
+                }
+            }
+        }
+    }
+
+    /// Split a LakeFS url into repository, reference and table path.
+    ///
+    /// The reference is either the branch of `lakefs://repo/branch/table` or the tag/commit
+    /// of `lakefs://repo@ref/table`.
     pub fn decompose_url(&self, url: String) -> (String, String, String) {
         let url_path = url
             .strip_prefix("lakefs://")
             .unwrap()
             .split("/")
             .collect::<Vec<&str>>();
+        if let Some((repo, reference)) = url_path[0].split_once('@') {
+            let table = url_path[1..].join("/");
+            return (repo.to_owned(), reference.to_owned(), table);
+        }
         let repo = url_path[0].to_owned();
         let branch = url_path[1].to_owned();
         let table = url_path[2..].join("/");
@@ -319,6 +407,15 @@ impl LakeFSClient {
     }
 }
 
+/// A LakeFS tag and the commit it points at.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LakeFSTag {
+    /// Name of the tag
+    pub id: String,
+    /// Commit the tag points at
+    pub commit_id: String,
+}
+
 #[derive(Deserialize, Debug)]
 struct LakeFSErrorResponse {
     message: String,
@@ -475,6 +572,61 @@ mod tests {
         assert_eq!(repo, "test_repo");
         assert_eq!(branch, "test_branch");
         assert_eq!(table, "data/test_table");
+
+        let (repo, reference, table) =
+            client.decompose_url("lakefs://test_repo@v1.0/data/test_table".to_string());
+        assert_eq!(repo, "test_repo");
+        assert_eq!(reference, "v1.0");
+        assert_eq!(table, "data/test_table");
+    }
+
+    #[test]
+    fn test_list_tags() {
+        let mut server = mockito::Server::new();
+        let first_page = server
+            .mock("GET", "/api/v1/repositories/test_repo/tags")
+            .match_query(mockito::Matcher::UrlEncoded("after".into(), "".into()))
+            .with_status(StatusCode::OK.as_u16().into())
+            .with_body(
+                r#"{"pagination": {"has_more": true, "next_offset": "v1", "results": 1, "max_per_page": 1000},
+                    "results": [{"id": "v1", "commit_id": "c1"}]}"#,
+            )
+            .create();
+        let second_page = server
+            .mock("GET", "/api/v1/repositories/test_repo/tags")
+            .match_query(mockito::Matcher::UrlEncoded("after".into(), "v1".into()))
+            .with_status(StatusCode::OK.as_u16().into())
+            .with_body(
+                r#"{"pagination": {"has_more": false, "next_offset": "", "results": 1, "max_per_page": 1000},
+                    "results": [{"id": "v2", "commit_id": "c2"}]}"#,
+            )
+            .create();
+
+        let config = LakeFSConfig::new(
+            server.url(),
+            "test_user".to_string(),
+            "test_pass".to_string(),
+        );
+        let client = LakeFSClient::with_config(config);
+
+        let tags = rt()
+            .block_on(async { client.list_tags("test_repo").await })
+            .unwrap();
+        assert_eq!(
+            tags,
+            vec![
+                LakeFSTag {
+                    id: "v1".to_string(),
+                    commit_id: "c1".to_string()
+                },
+                LakeFSTag {
+                    id: "v2".to_string(),
+                    commit_id: "c2".to_string()
+                },
+            ]
+        );
+        first_page.assert();
+        second_page.assert();
     }
 
     #[test]
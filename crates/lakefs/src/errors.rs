@@ -48,6 +48,14 @@ pub enum LakeFSOperationError {
     /// LakeFS delete branch has failed
     #[error("Transaction ID ({0}) not found. Something went wrong.")]
     TransactionIdNotFound(String),
+
+    /// LakeFS list tags has failed
+    #[error("LakeFS list tags failed. Reason: {0}")]
+    ListTagsFailed(String),
+
+    /// Write attempted against a tag or commit reference
+    #[error("LakeFS reference `{0}` is read-only. Write to a branch instead.")]
+    ReadOnlyReference(String),
 }
 
 impl From<LakeFSOperationError> for TransactionError {
@@ -327,6 +327,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pre_execute_read_only_reference() {
+        register_handlers(None);
+        let handler = LakeFSCustomExecuteHandler {};
+        let operation_id = Uuid::new_v4();
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/api/v1/repositories/repo/branches")
+            .expect(0)
+            .create();
+
+        let location = Url::parse("lakefs://repo@v1.0/table").unwrap();
+        let raw_options = hashmap! {
+            "ACCESS_KEY_ID".to_string() => "options_key".to_string(),
+            "ENDPOINT_URL".to_string() => server.url(),
+            "SECRET_ACCESS_KEY".to_string() => "options_key".to_string(),
+            "REGION".to_string() => "options_key".to_string()
+        };
+        let lakefs_store = logstore_for(
+            &location,
+            StorageConfig::parse_options(raw_options).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            lakefs_store.config().location().as_str(),
+            "lakefs://repo/v1.0/table/"
+        );
+
+        let result =
+            rt().block_on(async { handler.pre_execute(&lakefs_store, operation_id).await });
+        mock.assert();
+        assert!(
+            matches!(
+                &result,
+                Err(DeltaTableError::Transaction { source })
+                    if source.to_string().contains("read-only")
+            ),
+            "{result:?}"
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_error_with_invalid_log_store() {
         let location = Url::parse("memory:///table").unwrap();
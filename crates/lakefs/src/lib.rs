@@ -1,22 +1,27 @@
 //! LakeFS and similar tooling for delta-rs
 //!
 //! This module also contains the [LakeFSLogStore] implementation for delta operations executed in transaction branches
-//! where deltalake commits only happen when the branch can be safely merged.
+//! where deltalake commits only happen when the branch can be safely merged, and the
+//! [LakeFSTransaction] to group operations on several tables into one atomic merge.
 
 pub mod client;
 pub mod errors;
 pub mod execute;
 pub mod logstore;
+pub mod refs;
 pub mod storage;
+pub mod transaction;
 use deltalake_core::DeltaResult;
 use deltalake_core::logstore::{LogStore, LogStoreFactory, logstore_factories};
 use deltalake_core::logstore::{ObjectStoreRef, StorageConfig, object_store_factories};
 pub use execute::LakeFSCustomExecuteHandler;
 use logstore::lakefs_logstore;
+pub use refs::{LakeFSReference, tags_by_version};
 use std::sync::Arc;
 use storage::LakeFSObjectStoreFactory;
 use storage::S3StorageOptionsConversion;
 use tracing::debug;
+pub use transaction::LakeFSTransaction;
 use url::Url;
 
 #[derive(Clone, Debug, Default)]
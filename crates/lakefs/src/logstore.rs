@@ -17,7 +17,8 @@ use uuid::Uuid;
 
 use super::client::LakeFSClient;
 use crate::client::LakeFSConfig;
-use crate::errors::LakeFSConfigError;
+use crate::errors::LakeFSOperationError;
+use crate::refs::LakeFSReference;
 
 /// Return the [LakeFSLogStore] implementation with the provided configuration options
 pub fn lakefs_logstore(
@@ -26,23 +27,21 @@ pub fn lakefs_logstore(
     location: &Url,
     options: &StorageConfig,
 ) -> DeltaResult<Arc<dyn LogStore>> {
-    let host = options
-        .raw
-        .get("aws_endpoint")
-        .ok_or(LakeFSConfigError::EndpointMissing)?
-        .to_string();
-    let username = options
-        .raw
-        .get("aws_access_key_id")
-        .ok_or(LakeFSConfigError::UsernameCredentialMissing)?
-        .to_string();
-    let password = options
-        .raw
-        .get("aws_secret_access_key")
-        .ok_or(LakeFSConfigError::PasswordCredentialMissing)?
-        .to_string();
-
-    let client = LakeFSClient::with_config(LakeFSConfig::new(host, username, password));
+    let client = LakeFSClient::with_config(LakeFSConfig::try_from_options(&options.raw)?);
+    if let Some(reference) = LakeFSReference::from_url(location) {
+        // The prefixed store was derived from the `repo@ref` url, which has no ref in its path
+        let location = reference.resolved_url()?;
+        let store = Arc::new(options.decorate_store(root_store.clone(), &location)?);
+        return Ok(Arc::new(
+            LakeFSLogStore::new(
+                store,
+                root_store,
+                LogStoreConfig::new(&location, options.clone()),
+                client,
+            )
+            .with_read_only_reference(reference.reference),
+        ));
+    }
     Ok(Arc::new(LakeFSLogStore::new(
         store,
         root_store,
@@ -58,6 +57,8 @@ pub(crate) struct LakeFSLogStore {
     root_registry: DefaultObjectStoreRegistry,
     config: LogStoreConfig,
     pub(crate) client: LakeFSClient,
+    /// Tag or commit the table was loaded at, tables at such references can't be written to
+    read_only_reference: Option<String>,
 }
 
 impl LakeFSLogStore {
@@ -85,6 +86,20 @@ impl LakeFSLogStore {
             root_registry,
             config,
             client,
+            read_only_reference: None,
+        }
+    }
+
+    /// Mark the log store as pointing at an immutable tag or commit
+    pub(crate) fn with_read_only_reference(mut self, reference: String) -> Self {
+        self.read_only_reference = Some(reference);
+        self
+    }
+
+    fn ensure_writable(&self) -> Result<(), LakeFSOperationError> {
+        match &self.read_only_reference {
+            Some(reference) => Err(LakeFSOperationError::ReadOnlyReference(reference.clone())),
+            None => Ok(()),
         }
     }
 
@@ -140,6 +155,8 @@ impl LakeFSLogStore {
     }
 
     pub async fn pre_execute(&self, operation_id: Uuid) -> DeltaResult<()> {
+        self.ensure_writable()?;
+
         // Create LakeFS Branch for transaction
         let (lakefs_url, tnx_branch) = self
             .client
@@ -163,6 +180,7 @@ impl LakeFSLogStore {
     }
 
     pub async fn commit_merge(&self, operation_id: Uuid) -> DeltaResult<()> {
+        self.ensure_writable()?;
         let (transaction_url, _, _) = self.get_transaction_objectstore(operation_id)?;
 
         // Do LakeFS Commit
@@ -259,6 +277,7 @@ impl LogStore for LakeFSLogStore {
         commit_or_bytes: CommitOrBytes,
         operation_id: Uuid,
     ) -> Result<(), TransactionError> {
+        self.ensure_writable()?;
         let (transaction_url, store, _root_store) = self
             .get_transaction_objectstore(operation_id)
             .map_err(|e| TransactionError::LogStoreError {
@@ -389,7 +408,10 @@ impl LogStore for LakeFSLogStore {
 
     fn transaction_url(&self, operation_id: Option<Uuid>) -> DeltaResult<Url> {
         match operation_id {
-            Some(op) => self.get_transaction_url(op, self.config.location().to_string()),
+            Some(op) => {
+                self.ensure_writable()?;
+                self.get_transaction_url(op, self.config.location().to_string())
+            }
             None => Err(DeltaTableError::Generic(
                 "LakeFS must use operation_ids for operations".into(),
             )),
@@ -0,0 +1,126 @@
+//! Read-only access to tables at LakeFS tags and commits
+//!
+//! Besides branches (`lakefs://repo/branch/table`), tables can be loaded at a tag or commit with
+//! `lakefs://repo@tag/table` and `lakefs://repo@<commit>/table`. These references are immutable,
+//! so every write against them fails before any branch is created. Delta time travel keeps working
+//! on top of the reference, e.g. loading version 3 of the table as it was tagged in `v1.0`:
+//!
+//! ```ignore
+//! let table = DeltaTableBuilder::from_url(Url::parse("lakefs://repo@v1.0/table")?)?
+//!     .with_storage_options(storage_options)
+//!     .with_version(3)
+//!     .load()
+//!     .await?;
+//! ```
+use std::collections::{BTreeMap, HashMap};
+
+use deltalake_core::logstore::LogStore as _;
+use deltalake_core::table::builder::DeltaTableBuilder;
+use deltalake_core::table::normalize_table_url;
+use deltalake_core::{DeltaResult, DeltaTableError};
+use tracing::debug;
+use url::Url;
+
+use crate::LakeFSLogStoreFactory;
+use crate::client::{LakeFSClient, LakeFSConfig};
+use crate::storage::S3StorageOptionsConversion;
+
+/// A table at a read-only LakeFS reference, parsed from `lakefs://repo@ref/table`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LakeFSReference {
+    /// Name of the repository
+    pub repo: String,
+    /// Tag or commit id
+    pub reference: String,
+    /// Path of the table inside the repository
+    pub table: String,
+}
+
+impl LakeFSReference {
+    /// Parse a reference url, returns `None` for urls pointing at a branch.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        if url.scheme() != "lakefs" || url.username().is_empty() {
+            return None;
+        }
+        Some(Self {
+            repo: url.username().to_string(),
+            reference: url.host_str()?.to_string(),
+            table: url.path().trim_matches('/').to_string(),
+        })
+    }
+
+    /// Url of the table in the `lakefs://repo/ref/table` layout.
+    ///
+    /// The LakeFS S3 gateway resolves any reference in place of the branch, so this is the
+    /// location used to read the table.
+    pub fn resolved_url(&self) -> DeltaResult<Url> {
+        let string_url = format!("lakefs://{}/{}/{}", self.repo, self.reference, self.table);
+        Ok(normalize_table_url(&Url::parse(&string_url).map_err(
+            |_| {
+                DeltaTableError::NotATable(format!(
+                    "Could not convert {string_url} into a table URL"
+                ))
+            },
+        )?))
+    }
+}
+
+/// List the LakeFS tags of the table's repository grouped by the Delta version the table had
+/// at each tag.
+///
+/// Tags at which the table did not exist yet are left out.
+pub async fn tags_by_version(
+    table_url: &Url,
+    storage_options: HashMap<String, String>,
+) -> DeltaResult<BTreeMap<i64, Vec<String>>> {
+    if table_url.scheme() != "lakefs" {
+        return Err(DeltaTableError::InvalidTableLocation(table_url.to_string()));
+    }
+    let options = LakeFSLogStoreFactory::default().with_env_s3(&storage_options);
+    let client = LakeFSClient::with_config(LakeFSConfig::try_from_options(&options)?);
+    let (repo, _, table) = client.decompose_url(table_url.to_string());
+
+    let mut versions: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    for tag in client.list_tags(&repo).await? {
+        let reference = LakeFSReference {
+            repo: repo.clone(),
+            reference: tag.id.clone(),
+            table: table.trim_matches('/').to_string(),
+        };
+        let log_store = DeltaTableBuilder::from_url(reference.resolved_url()?)?
+            .with_storage_options(storage_options.clone())
+            .build_storage()?;
+        if !log_store.is_delta_table_location().await? {
+            debug!("Table {table} does not exist at LakeFS tag `{}`", tag.id);
+            continue;
+        }
+        let version = log_store.get_latest_version(0).await?;
+        versions.entry(version).or_default().push(tag.id);
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_from_url() {
+        let url = Url::parse("lakefs://repo@v1.0/data/table").unwrap();
+        let reference = LakeFSReference::from_url(&url).unwrap();
+        assert_eq!(reference.repo, "repo");
+        assert_eq!(reference.reference, "v1.0");
+        assert_eq!(reference.table, "data/table");
+        assert_eq!(
+            reference.resolved_url().unwrap().as_str(),
+            "lakefs://repo/v1.0/data/table/"
+        );
+
+        let url = Url::parse("lakefs://repo@a1b2c3d4e5f6/table").unwrap();
+        let reference = LakeFSReference::from_url(&url).unwrap();
+        assert_eq!(reference.reference, "a1b2c3d4e5f6");
+
+        let url = Url::parse("lakefs://repo/main/table").unwrap();
+        assert!(LakeFSReference::from_url(&url).is_none());
+    }
+}
@@ -13,6 +13,8 @@ use std::sync::Arc;
 use tracing::log::*;
 use url::Url;
 
+use crate::refs::LakeFSReference;
+
 #[derive(Clone, Default, Debug)]
 pub struct LakeFSObjectStoreFactory {}
 
@@ -62,6 +64,11 @@ impl ObjectStoreFactory for LakeFSObjectStoreFactory {
         url: &Url,
         config: &StorageConfig,
     ) -> DeltaResult<(ObjectStoreRef, Path)> {
+        // Tags and commits are addressed like branches in the S3 gateway
+        let url = match LakeFSReference::from_url(url) {
+            Some(reference) => reference.resolved_url()?,
+            None => url.clone(),
+        };
         // Convert LakeFS URI to equivalent S3 URI.
         let s3_url = url.to_string().replace("lakefs://", "s3://");
         let s3_url = Url::parse(&s3_url)
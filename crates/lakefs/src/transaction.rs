@@ -0,0 +1,400 @@
+//! Multi-table transactions on a single LakeFS branch
+//!
+//! Every delta operation executed through the [`LakeFSLogStore`](crate::logstore::LakeFSLogStore)
+//! runs in its own ephemeral branch that is merged back as soon as the operation commits.
+//! A [`LakeFSTransaction`] adds one level on top of this: it forks a transaction branch from the
+//! target branch, tables are loaded from that transaction branch, and all their operations merge
+//! into it. Only [`LakeFSTransaction::commit`] merges the transaction branch into the target
+//! branch, so the changes to all tables become visible atomically.
+//!
+//! ```ignore
+//! let txn = LakeFSTransaction::begin(Url::parse("lakefs://repo/main")?, storage_options).await?;
+//!
+//! let orders = txn.table("tables/orders").await?;
+//! let customers = txn.table("tables/customers").await?;
+//!
+//! let result = async {
+//!     orders
+//!         .write(order_batches)
+//!         .with_custom_execute_handler(Arc::new(LakeFSCustomExecuteHandler {}))
+//!         .await?;
+//!     customers
+//!         .write(customer_batches)
+//!         .with_custom_execute_handler(Arc::new(LakeFSCustomExecuteHandler {}))
+//!         .await
+//! }
+//! .await;
+//!
+//! match result {
+//!     Ok(_) => txn.commit("Nightly ETL").await?,
+//!     Err(_) => txn.rollback().await?,
+//! }
+//! ```
+use std::collections::HashMap;
+
+use deltalake_core::kernel::transaction::TransactionError;
+use deltalake_core::table::builder::DeltaTableBuilder;
+use deltalake_core::table::normalize_table_url;
+use deltalake_core::{DeltaResult, DeltaTable, DeltaTableError};
+use tracing::debug;
+use url::Url;
+use uuid::Uuid;
+
+use crate::LakeFSLogStoreFactory;
+use crate::client::{LakeFSClient, LakeFSConfig};
+use crate::errors::LakeFSOperationError;
+use crate::storage::S3StorageOptionsConversion;
+
+/// A transaction branch spanning operations on several tables of one LakeFS repository.
+///
+/// The transaction has to be finished with either [`commit`](Self::commit) or
+/// [`rollback`](Self::rollback). A dropped transaction leaves its (hidden) branch behind,
+/// in the same way a failed operation leaves its `delta-tx` branch behind.
+#[must_use = "a LakeFS transaction must be committed or rolled back"]
+#[derive(Debug)]
+pub struct LakeFSTransaction {
+    client: LakeFSClient,
+    storage_options: HashMap<String, String>,
+    repo: String,
+    target_branch: String,
+    transaction_branch: String,
+}
+
+impl LakeFSTransaction {
+    /// Open a new transaction branch forked from the branch of `branch_url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `branch_url` - the branch the transaction merges into, i.e. `lakefs://repo/branch`.
+    /// * `storage_options` - the same storage options used to load tables from LakeFS.
+    pub async fn begin(
+        branch_url: Url,
+        storage_options: HashMap<String, String>,
+    ) -> DeltaResult<Self> {
+        if branch_url.scheme() != "lakefs" {
+            return Err(DeltaTableError::InvalidTableLocation(branch_url.into()));
+        }
+        let options = LakeFSLogStoreFactory::default().with_env_s3(&storage_options);
+        let client = LakeFSClient::with_config(LakeFSConfig::try_from_options(&options)?);
+
+        let source_url = Url::parse(&format!("{}/", branch_url.as_str().trim_end_matches('/')))
+            .map_err(|_| DeltaTableError::InvalidTableLocation(branch_url.to_string()))?;
+        let (repo, target_branch, _) = client.decompose_url(source_url.to_string());
+        if repo.is_empty() || target_branch.is_empty() {
+            return Err(DeltaTableError::InvalidTableLocation(branch_url.into()));
+        }
+
+        let (_, transaction_branch) = client.create_branch(&source_url, Uuid::new_v4()).await?;
+        debug!(
+            "Opened LakeFS transaction branch `{transaction_branch}` on `{repo}/{target_branch}`"
+        );
+
+        Ok(Self {
+            client,
+            storage_options,
+            repo,
+            target_branch,
+            transaction_branch,
+        })
+    }
+
+    /// Name of the LakeFS repository the transaction runs in.
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    /// Name of the branch the transaction is merged into on commit.
+    pub fn target_branch(&self) -> &str {
+        &self.target_branch
+    }
+
+    /// Name of the transaction branch all operations commit into.
+    pub fn branch(&self) -> &str {
+        &self.transaction_branch
+    }
+
+    /// Url of the table at `table_path` (relative to the repository branch) inside the
+    /// transaction branch.
+    pub fn table_url(&self, table_path: &str) -> DeltaResult<Url> {
+        let string_url = format!(
+            "lakefs://{}/{}/{}",
+            self.repo,
+            self.transaction_branch,
+            table_path.trim_matches('/')
+        );
+        Ok(normalize_table_url(&Url::parse(&string_url).map_err(
+            |_| {
+                DeltaTableError::NotATable(format!(
+                    "Could not convert {string_url} into a table URL"
+                ))
+            },
+        )?))
+    }
+
+    /// Load the table at `table_path` from the transaction branch.
+    ///
+    /// Operations on the returned table should use the
+    /// [`LakeFSCustomExecuteHandler`](crate::LakeFSCustomExecuteHandler), their commits are
+    /// merged into the transaction branch and stay invisible on the target branch until the
+    /// transaction is committed. Like [`DeltaTable::try_from_url`] the location does not need to
+    /// contain a table yet, so tables can also be created inside a transaction.
+    pub async fn table(&self, table_path: &str) -> DeltaResult<DeltaTable> {
+        let mut table = DeltaTableBuilder::from_url(self.table_url(table_path)?)?
+            .with_storage_options(self.storage_options.clone())
+            .build()?;
+        match table.load().await {
+            Ok(_) => Ok(table),
+            Err(DeltaTableError::NotATable(_)) => Ok(table),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Merge the transaction branch into the target branch and delete it.
+    ///
+    /// If the merge fails, e.g. because of conflicting changes on the target branch, the
+    /// transaction is rolled back and the merge error is returned.
+    pub async fn commit(self, message: impl Into<String>) -> DeltaResult<()> {
+        let message = message.into();
+        self.client
+            .commit(
+                self.repo.clone(),
+                self.transaction_branch.clone(),
+                message.clone(),
+                true, // Operations have already committed their changes, this only catches leftovers.
+            )
+            .await?;
+
+        let has_changes = self
+            .client
+            .has_changes(&self.repo, &self.target_branch, &self.transaction_branch)
+            .await
+            .map_err(|e| DeltaTableError::generic(format!("Failed to check for changes: {e}")))?;
+
+        if has_changes {
+            debug!(
+                "Merging LakeFS transaction branch `{}` into `{}`",
+                self.transaction_branch, self.target_branch
+            );
+            let merged = self
+                .client
+                .merge(
+                    self.repo.clone(),
+                    self.target_branch.clone(),
+                    self.transaction_branch.clone(),
+                    0,
+                    message,
+                    true,
+                )
+                .await;
+            if let Err(err) = merged {
+                let err = match err {
+                    TransactionError::VersionAlreadyExists(_) => {
+                        LakeFSOperationError::MergeFailed(format!(
+                            "transaction branch `{}` conflicts with `{}`",
+                            self.transaction_branch, self.target_branch
+                        ))
+                        .into()
+                    }
+                    err => DeltaTableError::Transaction { source: err },
+                };
+                self.rollback().await?;
+                return Err(err);
+            }
+        } else {
+            debug!("No changes in LakeFS transaction, skipping merge");
+        }
+
+        self.client
+            .delete_branch(self.repo, self.transaction_branch)
+            .await?;
+        Ok(())
+    }
+
+    /// Discard all changes of the transaction by deleting the transaction branch.
+    pub async fn rollback(self) -> DeltaResult<()> {
+        debug!(
+            "Rolling back LakeFS transaction branch `{}`",
+            self.transaction_branch
+        );
+        self.client
+            .delete_branch(self.repo, self.transaction_branch)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::StatusCode;
+    use maplit::hashmap;
+    use mockito::Matcher;
+
+    fn storage_options(server_url: String) -> HashMap<String, String> {
+        hashmap! {
+            "ACCESS_KEY_ID".to_string() => "options_key".to_string(),
+            "ENDPOINT_URL".to_string() => server_url,
+            "SECRET_ACCESS_KEY".to_string() => "options_key".to_string(),
+            "REGION".to_string() => "options_key".to_string()
+        }
+    }
+
+    async fn begin(server: &mut mockito::ServerGuard) -> (LakeFSTransaction, mockito::Mock) {
+        let create_branch_mock = server
+            .mock("POST", "/api/v1/repositories/repo/branches")
+            .match_body(Matcher::PartialJson(serde_json::json!({"source": "main"})))
+            .with_status(StatusCode::CREATED.as_u16().into())
+            .create_async()
+            .await;
+        let txn = LakeFSTransaction::begin(
+            Url::parse("lakefs://repo/main").unwrap(),
+            storage_options(server.url()),
+        )
+        .await
+        .unwrap();
+        (txn, create_branch_mock)
+    }
+
+    #[tokio::test]
+    async fn test_begin() {
+        let mut server = mockito::Server::new_async().await;
+        let (txn, create_branch_mock) = begin(&mut server).await;
+        create_branch_mock.assert_async().await;
+
+        assert_eq!(txn.repo(), "repo");
+        assert_eq!(txn.target_branch(), "main");
+        assert!(txn.branch().starts_with("delta-tx-"));
+
+        let url = txn.table_url("tables/orders").unwrap();
+        assert_eq!(
+            url.as_str(),
+            format!("lakefs://repo/{}/tables/orders/", txn.branch())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_begin_invalid_url() {
+        let result =
+            LakeFSTransaction::begin(Url::parse("s3://repo/main").unwrap(), HashMap::new()).await;
+        assert!(matches!(
+            result,
+            Err(DeltaTableError::InvalidTableLocation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_commit() {
+        let mut server = mockito::Server::new_async().await;
+        let (txn, _) = begin(&mut server).await;
+        let branch = txn.branch().to_string();
+
+        let commit_mock = server
+            .mock(
+                "POST",
+                format!("/api/v1/repositories/repo/branches/{branch}/commits").as_str(),
+            )
+            .with_status(StatusCode::CREATED.as_u16().into())
+            .create_async()
+            .await;
+        let diff_mock = server
+            .mock(
+                "GET",
+                format!("/api/v1/repositories/repo/refs/main/diff/{branch}").as_str(),
+            )
+            .with_status(StatusCode::OK.as_u16().into())
+            .with_body(r#"{"results": [{"some": "change"}]}"#)
+            .create_async()
+            .await;
+        let merge_mock = server
+            .mock(
+                "POST",
+                format!("/api/v1/repositories/repo/refs/{branch}/merge/main").as_str(),
+            )
+            .with_status(StatusCode::OK.as_u16().into())
+            .expect(1)
+            .create_async()
+            .await;
+        let delete_mock = server
+            .mock(
+                "DELETE",
+                format!("/api/v1/repositories/repo/branches/{branch}").as_str(),
+            )
+            .with_status(StatusCode::NO_CONTENT.as_u16().into())
+            .expect(1)
+            .create_async()
+            .await;
+
+        txn.commit("multi table commit").await.unwrap();
+
+        commit_mock.assert_async().await;
+        diff_mock.assert_async().await;
+        merge_mock.assert_async().await;
+        delete_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_commit_conflict_rolls_back() {
+        let mut server = mockito::Server::new_async().await;
+        let (txn, _) = begin(&mut server).await;
+        let branch = txn.branch().to_string();
+
+        server
+            .mock(
+                "POST",
+                format!("/api/v1/repositories/repo/branches/{branch}/commits").as_str(),
+            )
+            .with_status(StatusCode::CREATED.as_u16().into())
+            .create_async()
+            .await;
+        server
+            .mock(
+                "GET",
+                format!("/api/v1/repositories/repo/refs/main/diff/{branch}").as_str(),
+            )
+            .with_status(StatusCode::OK.as_u16().into())
+            .with_body(r#"{"results": [{"some": "change"}]}"#)
+            .create_async()
+            .await;
+        server
+            .mock(
+                "POST",
+                format!("/api/v1/repositories/repo/refs/{branch}/merge/main").as_str(),
+            )
+            .with_status(StatusCode::CONFLICT.as_u16().into())
+            .create_async()
+            .await;
+        let delete_mock = server
+            .mock(
+                "DELETE",
+                format!("/api/v1/repositories/repo/branches/{branch}").as_str(),
+            )
+            .with_status(StatusCode::NO_CONTENT.as_u16().into())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = txn.commit("multi table commit").await;
+        assert!(result.is_err());
+        delete_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rollback() {
+        let mut server = mockito::Server::new_async().await;
+        let (txn, _) = begin(&mut server).await;
+        let branch = txn.branch().to_string();
+
+        let delete_mock = server
+            .mock(
+                "DELETE",
+                format!("/api/v1/repositories/repo/branches/{branch}").as_str(),
+            )
+            .with_status(StatusCode::NO_CONTENT.as_u16().into())
+            .create_async()
+            .await;
+
+        txn.rollback().await.unwrap();
+        delete_mock.assert_async().await;
+    }
+}